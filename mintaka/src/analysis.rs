@@ -0,0 +1,308 @@
+use crate::checkpoint::SearchCheckpoint;
+use crate::config::{Config, SearchObjective};
+use crate::eval::evaluator::{ActiveEvaluator, Evaluator};
+use crate::game_agent::{GameAgent, GameError};
+use crate::game_state::GameState;
+use crate::memo::history_table::HistoryTable;
+use crate::principal_variation::PrincipalVariation;
+use crate::protocol::response::{Response, ResponseSender};
+use crate::protocol::timer::Timer;
+use crate::search_endgame;
+use crate::thread_data::ThreadData;
+use crate::thread_type::WorkerThread;
+use crate::utils::monotonic_clock::MonotonicClock;
+use rusty_renju::history::History;
+use rusty_renju::notation::color::Color;
+use rusty_renju::notation::pos;
+use rusty_renju::notation::pos::MaybePos;
+use rusty_renju::notation::rule::RuleKind;
+use rusty_renju::notation::score::{Score, Scores};
+use rusty_renju::utils::empty::Empty;
+use rusty_renju::win_rate::calculate_win_rate;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+
+const INACCURACY_THRESHOLD: f32 = 0.1;
+const MISTAKE_THRESHOLD: f32 = 0.2;
+const BLUNDER_THRESHOLD: f32 = 0.4;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MoveLabel {
+    Best,
+    Forced,
+    Inaccuracy,
+    Mistake,
+    Blunder,
+    MissedVcf,
+}
+
+#[cfg_attr(feature = "typeshare", typeshare::typeshare)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveAnalysis {
+    pub ply: u32,
+    pub color: Color,
+    pub played: MaybePos,
+    pub best_move: MaybePos,
+    // both scores are from the mover's perspective
+    pub score_before: Score,
+    pub score_after: Score,
+    pub win_rate_before: f32,
+    pub win_rate_after: f32,
+    pub win_rate_delta: f32,
+    pub labels: Vec<MoveLabel>,
+    pub pv: PrincipalVariation,
+}
+
+#[cfg_attr(feature = "typeshare", typeshare::typeshare)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameAnalysis {
+    pub moves: Vec<MoveAnalysis>,
+    pub completed: bool,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PlyScore {
+    pub score: Score,
+    pub best_move: MaybePos,
+    pub pv: PrincipalVariation,
+}
+
+// everything needed to continue an interrupted analysis, the tt is not kept so resumed plies
+// continue from the checkpointed depth on a cold table
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AnalysisProgress {
+    // indexed by the number of moves played before the position
+    pub ply_scores: Vec<Option<PlyScore>>,
+    // last completed iteration of the position being searched
+    pub checkpoint: Option<SearchCheckpoint>,
+}
+
+impl AnalysisProgress {
+    pub fn analyzed_plies(&self) -> usize {
+        self.ply_scores.iter().filter(|ply_score| ply_score.is_some()).count()
+    }
+
+    pub fn total_plies(&self) -> usize {
+        self.ply_scores.len()
+    }
+}
+
+struct CheckpointSender<F: Fn(&SearchCheckpoint) + Send> {
+    consumer: F,
+}
+
+impl<F: Fn(&SearchCheckpoint) + Send> ResponseSender for CheckpointSender<F> {
+    fn response(&self, _response: Response) {}
+
+    fn checkpoint(&self, checkpoint: &SearchCheckpoint) {
+        (self.consumer)(checkpoint);
+    }
+}
+
+impl<const R: RuleKind> GameAgent<R> {
+    // searches every position of the game from the last one, so that later plies warm the tt for earlier ones.
+    // progress is reported after every ply and every completed iteration, and can be passed back to resume
+    pub fn analyze_game<CLK: MonotonicClock>(
+        config: Config,
+        history: &History,
+        nodes_in_1k_per_ply: u32,
+        resume: Option<AnalysisProgress>,
+        progress: impl Fn(&AnalysisProgress) + Sync,
+        aborted: Arc<AtomicBool>,
+    ) -> Result<GameAnalysis, GameError> {
+        let mut states = Vec::with_capacity(history.len() + 1);
+        let mut state = GameState::<R>::empty();
+
+        states.push(state);
+
+        for action in history.iter() {
+            match action.ok() {
+                Some(pos) => {
+                    if !state.board.is_legal_move(pos) {
+                        return Err(GameError::ForbiddenMove);
+                    }
+
+                    state.play_mut(pos);
+                },
+                None => state.pass_mut(),
+            }
+
+            states.push(state);
+        }
+
+        let mut config = config;
+        config.max_nodes_in_1k = Some(nodes_in_1k_per_ply);
+        config.skill_level = None;
+
+        let mut agent = Self::from_state(config, GameState::empty());
+
+        // progress of another game is discarded, the checkpoint is matched against each position on launch
+        let mut analysis_progress = resume
+            .filter(|resume| resume.ply_scores.len() == states.len())
+            .unwrap_or_else(|| AnalysisProgress {
+                ply_scores: vec![None; states.len()],
+                checkpoint: None,
+            });
+
+        agent.restore_checkpoint(analysis_progress.checkpoint.take());
+
+        let mut completed = true;
+
+        for (idx, state) in states.iter().enumerate().rev() {
+            if aborted.load(Ordering::Relaxed) {
+                completed = false;
+                break;
+            }
+
+            if analysis_progress.ply_scores[idx].is_some() {
+                continue;
+            }
+
+            progress(&analysis_progress);
+
+            let terminal_score = if idx > 0
+                && let Some(pos) = history[idx - 1].ok()
+                && state.board.find_winner(pos).is_some()
+            {
+                Some(Score::lose_in(0))
+            } else if state.board.stones == pos::U8_BOARD_SIZE {
+                Some(Score::DRAW)
+            } else {
+                None
+            };
+
+            if let Some(score) = terminal_score {
+                analysis_progress.ply_scores[idx] = Some(PlyScore { score, best_move: MaybePos::NONE, pv: PrincipalVariation::EMPTY });
+                continue;
+            }
+
+            agent.sync_state(*state);
+
+            let ply_scores = &analysis_progress.ply_scores;
+
+            let response_sender = CheckpointSender {
+                consumer: |checkpoint: &SearchCheckpoint| progress(&AnalysisProgress {
+                    ply_scores: ply_scores.clone(),
+                    checkpoint: Some(checkpoint.clone()),
+                }),
+            };
+
+            let best_move = agent.nested_launch::<CLK>(
+                config,
+                Timer::INFINITE,
+                SearchObjective::Best,
+                response_sender,
+                Arc::new(AtomicU32::new(0)),
+                &aborted,
+            );
+
+            // an interrupted ply is left to the checkpoint, its score is only partial
+            if aborted.load(Ordering::Relaxed) {
+                analysis_progress.checkpoint = agent.checkpoint().cloned();
+                completed = false;
+                break;
+            }
+
+            analysis_progress.ply_scores[idx] = Some(PlyScore { score: best_move.score, best_move: best_move.best_move, pv: best_move.pv });
+        }
+
+        progress(&analysis_progress);
+
+        let ply_scores = analysis_progress.ply_scores;
+
+        let moves = history.iter()
+            .enumerate()
+            .filter_map(|(idx, &played)| {
+                let before = ply_scores[idx]?;
+                let after = ply_scores[idx + 1]?;
+
+                // a lost win is only a missed vcf when the position had one to begin with
+                let missed_vcf = Score::is_winning(before.score)
+                    && !Score::is_winning(-after.score)
+                    && agent.has_vcf::<CLK>(config, &states[idx]);
+
+                Some(analyze_move(&states[idx], idx, played, before, after, missed_vcf))
+            })
+            .collect();
+
+        Ok(GameAnalysis { moves, completed })
+    }
+
+    fn has_vcf<CLK: MonotonicClock>(&self, config: Config, state: &GameState<R>) -> bool {
+        let aborted = AtomicBool::new(false);
+        let global_counter_in_1k = AtomicU32::new(0);
+
+        let mut td = ThreadData::new(
+            WorkerThread::<CLK>::new(), 0, SearchObjective::Best, config.without_search_limits(),
+            ActiveEvaluator::from_state(state),
+            self.tt_view(), HistoryTable::empty(), &aborted, &global_counter_in_1k
+        );
+
+        search_endgame::full_vcf_sequence(&mut td, state).is_some()
+    }
+}
+
+fn analyze_move<const R: RuleKind>(
+    state: &GameState<R>,
+    idx: usize,
+    played: MaybePos,
+    before: PlyScore,
+    after: PlyScore,
+    missed_vcf: bool,
+) -> MoveAnalysis {
+    let color = state.board.player_color;
+
+    let score_before = before.score;
+    let score_after = -after.score;
+
+    let win_rate_before = calculate_win_rate(score_before);
+    let win_rate_after = calculate_win_rate(score_after);
+    let win_rate_delta = win_rate_after - win_rate_before;
+
+    let forced = state.board.patterns.five_pos[!color].is_some();
+
+    let mut labels = vec![];
+
+    if played == before.best_move {
+        labels.push(MoveLabel::Best);
+    }
+
+    if forced {
+        labels.push(MoveLabel::Forced);
+    }
+
+    if missed_vcf {
+        labels.push(MoveLabel::MissedVcf);
+    }
+
+    if !forced && played != before.best_move {
+        let loss = -win_rate_delta;
+
+        if loss >= BLUNDER_THRESHOLD {
+            labels.push(MoveLabel::Blunder);
+        } else if loss >= MISTAKE_THRESHOLD {
+            labels.push(MoveLabel::Mistake);
+        } else if loss >= INACCURACY_THRESHOLD {
+            labels.push(MoveLabel::Inaccuracy);
+        }
+    }
+
+    MoveAnalysis {
+        ply: idx as u32 + 1,
+        color,
+        played,
+        best_move: before.best_move,
+        score_before,
+        score_after,
+        win_rate_before,
+        win_rate_after,
+        win_rate_delta,
+        labels,
+        pv: before.pv,
+    }
+}
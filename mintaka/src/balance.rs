@@ -0,0 +1,315 @@
+use crate::checkpoint::SearchCheckpoint;
+use crate::config::{Config, SearchObjective};
+use crate::game_agent::GameAgent;
+use crate::game_state::GameState;
+use crate::protocol::response::NullResponseSender;
+use crate::protocol::results::BestMove;
+use crate::protocol::timer::Timer;
+use crate::utils::monotonic_clock::MonotonicClock;
+use rusty_renju::notation::pos;
+use rusty_renju::notation::pos::Pos;
+use rusty_renju::notation::rule::RuleKind;
+use rusty_renju::notation::score::{Score, Scores};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+
+const MOVE_SEARCH_NODES_IN_1K: u32 = 30;
+const MAX_BALANCE_TWO_CANDIDATES: usize = 6;
+const SWAP2_BALANCE_MARGIN: Score = 100;
+
+#[cfg_attr(feature = "typeshare", typeshare::typeshare)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct MoveScore {
+    pub pos: Pos,
+    // from the perspective of the player who makes the move
+    pub score: Score,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Swap2Decision {
+    // the three opening stones, black, white, black
+    Place([Pos; 3]),
+    Swap,
+    Play(Pos),
+    // a white and a black stone, handing the color choice back to the opponent
+    Add([Pos; 2]),
+}
+
+impl<const R: RuleKind> GameAgent<R> {
+    // the best moves of the current position, each verified by searching the position after it
+    pub fn rank_moves<CLK: MonotonicClock>(
+        &mut self,
+        config: Config,
+        count: usize,
+        aborted: Arc<AtomicBool>,
+    ) -> Vec<MoveScore> {
+        aborted.store(false, Ordering::Relaxed);
+
+        let config = move_search_config(config);
+        let state = self.state;
+        let checkpoint = self.checkpoint().cloned();
+
+        self.node_limited_search::<CLK>(config, &aborted);
+
+        let mut candidates = nearby_moves(&state);
+
+        // root scores of the refuted moves are only bounds, but good enough to pick what to verify
+        if let Some(checkpoint) = self.checkpoint()
+            && checkpoint.position_hash == state.board.hash_key
+        {
+            let root_scores = checkpoint.root_scores_array();
+            candidates.sort_by_key(|pos| -root_scores[pos.idx_usize()]);
+        }
+
+        candidates.truncate(count.max(1) * 2);
+
+        let mut move_scores = self.score_moves::<CLK>(config, &state, &candidates, &aborted);
+        move_scores.sort_by_key(|move_score| -move_score.score);
+        move_scores.truncate(count);
+
+        self.restore(state, checkpoint);
+
+        move_scores
+    }
+
+    // the move whose resulting position is closest to the target score, for the player to move
+    pub fn balance_one<CLK: MonotonicClock>(
+        &mut self,
+        config: Config,
+        target: Score,
+        aborted: Arc<AtomicBool>,
+    ) -> Option<MoveScore> {
+        aborted.store(false, Ordering::Relaxed);
+
+        let config = move_search_config(config);
+        let state = self.state;
+        let checkpoint = self.checkpoint().cloned();
+
+        let balanced = self.balanced_move::<CLK>(config, &state, target, &aborted);
+
+        self.restore(state, checkpoint);
+
+        balanced
+    }
+
+    // two consecutive moves, one for each player, that leave the player to move closest to the target score
+    pub fn balance_two<CLK: MonotonicClock>(
+        &mut self,
+        config: Config,
+        target: Score,
+        aborted: Arc<AtomicBool>,
+    ) -> Option<[MoveScore; 2]> {
+        aborted.store(false, Ordering::Relaxed);
+
+        let config = move_search_config(config);
+        let state = self.state;
+        let checkpoint = self.checkpoint().cloned();
+
+        let balanced = self.balanced_pair::<CLK>(config, &state, target, &aborted);
+
+        self.restore(state, checkpoint);
+
+        balanced
+    }
+
+    fn balanced_pair<CLK: MonotonicClock>(
+        &mut self,
+        config: Config,
+        state: &GameState<R>,
+        target: Score,
+        aborted: &AtomicBool,
+    ) -> Option<[MoveScore; 2]> {
+        let mut candidates = nearby_moves(state);
+
+        // the balance search scores every first move exactly, only the closest ones get a reply search
+        self.balanced_move::<CLK>(config, state, target, aborted);
+
+        if let Some(checkpoint) = self.checkpoint()
+            && checkpoint.position_hash == state.board.hash_key
+        {
+            let root_scores = checkpoint.root_scores_array();
+            candidates.sort_by_key(|pos| root_scores[pos.idx_usize()].abs_diff(target));
+        }
+
+        candidates.truncate(MAX_BALANCE_TWO_CANDIDATES);
+
+        let mut balanced: Option<[MoveScore; 2]> = None;
+
+        for first in candidates {
+            if aborted.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let mut child = *state;
+            child.play_mut(first);
+
+            if child.board.find_winner(first).is_some() {
+                continue;
+            }
+
+            let Some(second) = self.balanced_move::<CLK>(config, &child, -target, aborted) else {
+                continue;
+            };
+
+            let score = -second.score;
+
+            if balanced.is_none_or(|[_, best]| score.abs_diff(target) < (-best.score).abs_diff(target)) {
+                balanced = Some([MoveScore { pos: first, score }, second]);
+            }
+        }
+
+        balanced
+    }
+
+    // decides the current swap2 stage, the position must hold zero, three or five stones
+    pub fn swap2_decision<CLK: MonotonicClock>(
+        &mut self,
+        config: Config,
+        aborted: Arc<AtomicBool>,
+    ) -> Option<Swap2Decision> {
+        aborted.store(false, Ordering::Relaxed);
+
+        let config = move_search_config(config);
+        let state = self.state;
+        let checkpoint = self.checkpoint().cloned();
+
+        let decision = match state.board.stones {
+            0 => {
+                let mut opening = state;
+                opening.play_mut(pos::CENTER);
+
+                self.balanced_pair::<CLK>(config, &opening, 0, &aborted)
+                    .map(|[white, black]| Swap2Decision::Place([pos::CENTER, white.pos, black.pos]))
+            },
+            3 | 5 => {
+                let best_move = self.node_limited_search::<CLK>(config, &aborted);
+
+                if aborted.load(Ordering::Relaxed) {
+                    None
+                } else if best_move.score < -SWAP2_BALANCE_MARGIN
+                    || (state.board.stones == 5 && best_move.score < 0)
+                {
+                    Some(Swap2Decision::Swap)
+                } else if best_move.score <= SWAP2_BALANCE_MARGIN && state.board.stones == 3 {
+                    self.balanced_pair::<CLK>(config, &state, 0, &aborted)
+                        .map(|[white, black]| Swap2Decision::Add([white.pos, black.pos]))
+                } else {
+                    best_move.best_move.ok().map(Swap2Decision::Play)
+                }
+            },
+            _ => None,
+        };
+
+        self.restore(state, checkpoint);
+
+        decision
+    }
+
+    // a single search that scores every root move, instead of one search per candidate
+    fn balanced_move<CLK: MonotonicClock>(
+        &mut self,
+        config: Config,
+        state: &GameState<R>,
+        target: Score,
+        aborted: &AtomicBool,
+    ) -> Option<MoveScore> {
+        if aborted.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        self.sync_state(*state);
+
+        let balanced = self.objective_search::<CLK>(config, SearchObjective::Balance { target }, aborted);
+
+        // an interrupted search only scored part of the root moves
+        if aborted.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        balanced.best_move.ok()
+            .map(|pos| MoveScore { pos, score: balanced.score })
+    }
+
+    fn score_moves<CLK: MonotonicClock>(
+        &mut self,
+        config: Config,
+        state: &GameState<R>,
+        candidates: &[Pos],
+        aborted: &AtomicBool,
+    ) -> Vec<MoveScore> {
+        let mut move_scores = Vec::with_capacity(candidates.len());
+
+        for &pos in candidates {
+            if aborted.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let mut child = *state;
+            child.play_mut(pos);
+
+            let score = if child.board.find_winner(pos).is_some() {
+                Score::win_in(1)
+            } else if child.board.stones == pos::U8_BOARD_SIZE {
+                Score::DRAW
+            } else {
+                self.sync_state(child);
+                -self.node_limited_search::<CLK>(config, aborted).score
+            };
+
+            if aborted.load(Ordering::Relaxed) {
+                break;
+            }
+
+            move_scores.push(MoveScore { pos, score });
+        }
+
+        move_scores
+    }
+
+    fn node_limited_search<CLK: MonotonicClock>(&mut self, config: Config, aborted: &AtomicBool) -> BestMove {
+        self.objective_search::<CLK>(config, SearchObjective::Best, aborted)
+    }
+
+    fn objective_search<CLK: MonotonicClock>(
+        &mut self,
+        config: Config,
+        search_objective: SearchObjective,
+        aborted: &AtomicBool,
+    ) -> BestMove {
+        self.nested_launch::<CLK>(
+            config,
+            Timer::INFINITE,
+            search_objective,
+            NullResponseSender,
+            Arc::new(AtomicU32::new(0)),
+            aborted,
+        )
+    }
+
+    fn restore(&mut self, state: GameState<R>, checkpoint: Option<SearchCheckpoint>) {
+        self.sync_state(state);
+        self.restore_checkpoint(checkpoint);
+    }
+}
+
+fn move_search_config(mut config: Config) -> Config {
+    config.max_nodes_in_1k = Some(config.max_nodes_in_1k.unwrap_or(MOVE_SEARCH_NODES_IN_1K));
+    config.skill_level = None;
+    config
+}
+
+// legal moves around the stones, closest to the last move first
+fn nearby_moves<const R: RuleKind>(state: &GameState<R>) -> Vec<Pos> {
+    let anchor = state.history.last_action_or_none().ok().unwrap_or(pos::CENTER);
+
+    let mut moves: Vec<Pos> = (state.movegen_window.movegen_field & state.board.legal_field(state.board.player_color))
+        .iter_hot_pos()
+        .filter(|pos| pos.idx_usize() < pos::BOARD_SIZE)
+        .collect();
+
+    moves.sort_by_key(|pos| pos.distance(anchor));
+
+    moves
+}
@@ -33,6 +33,20 @@ impl<'a> BatchCounter<'a> {
         self.local_counter_in_1k + (self.buffer / 1000)
     }
 
+    pub fn count_local(&self) -> u64 {
+        self.local_counter_in_1k as u64 * 1000 + self.buffer as u64
+    }
+
+    // the global counter is shared with other threads and the caller, so it only counts exactly when
+    // this thread is the only one searching
+    pub fn count_in_1k(&self, deterministic: bool) -> u32 {
+        if deterministic {
+            self.local_counter_in_1k
+        } else {
+            self.count_global_in_1k()
+        }
+    }
+
     pub fn buffer_zero(&self) -> bool {
         self.buffer == 0
     }
@@ -0,0 +1,144 @@
+use crate::config::{Config, SearchObjective};
+use crate::eval::evaluator::{ActiveEvaluator, Evaluator};
+use crate::game_state::GameState;
+use crate::memo::history_table::HistoryTable;
+use crate::memo::transposition_table::TranspositionTable;
+use crate::search::iterative_deepening;
+use crate::thread_data::ThreadData;
+use crate::thread_type::WorkerThread;
+use crate::utils::monotonic_clock::MonotonicClock;
+use crate::value::Depth;
+use rusty_renju::history::History;
+use rusty_renju::notation::pos::MaybePos;
+use rusty_renju::notation::rule::RuleKind;
+use rusty_renju::notation::score::Score;
+use rusty_renju::utils::byte_size::ByteSize;
+use rusty_renju::utils::empty::Empty;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU32};
+use std::time::Duration;
+
+pub const BENCH_DEPTH: Depth = 8;
+
+const BENCH_TT_SIZE: ByteSize = ByteSize::from_mib(16);
+
+// changing this list changes the signature, keep it in sync with the expected value in reviews
+const BENCH_POSITIONS: [(RuleKind, &str); 12] = [
+    (RuleKind::Renju, ""),
+    (RuleKind::Renju, "h8,h9,i8"),
+    (RuleKind::Renju, "h8,i9,j10,g8,i7,h7"),
+    (RuleKind::Renju, "h8,h9,i10,i8,j9,g7,j7,k8,j8"),
+    (RuleKind::Gomoku, ""),
+    (RuleKind::Gomoku, "h8,i9,g7"),
+    (RuleKind::Gomoku, "h8,h7,i9,g9,j10,k11,i8"),
+    (RuleKind::Gomoku, "h8,i8,h9,h10,g9,i9,g10,f11,j9,i10"),
+    (RuleKind::Freestyle, ""),
+    (RuleKind::Freestyle, "h8,g7,i9"),
+    (RuleKind::Freestyle, "h8,i9,h9,h10,g10,f11,i8,j7"),
+    (RuleKind::Freestyle, "h8,j10,i9,g7,g9,h9,i10,i8,j11"),
+];
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BenchResult {
+    pub rule: RuleKind,
+    pub history: History,
+    pub best_move: MaybePos,
+    pub score: Score,
+    pub nodes: u64,
+    pub time_elapsed: Duration,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BenchReport {
+    pub depth: Depth,
+    pub results: Vec<BenchResult>,
+}
+
+impl BenchReport {
+    // the total node count only changes when the search itself changes
+    pub fn signature(&self) -> u64 {
+        self.results.iter().map(|result| result.nodes).sum()
+    }
+
+    pub fn time_elapsed(&self) -> Duration {
+        self.results.iter().map(|result| result.time_elapsed).sum()
+    }
+
+    pub fn nps(&self) -> u64 {
+        (self.signature() as f64 / self.time_elapsed().as_secs_f64().max(f64::EPSILON)) as u64
+    }
+}
+
+impl Display for BenchReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f, "depth={}, positions={}, nodes={}, elapsed={:?}, nps={}",
+            self.depth, self.results.len(), self.signature(), self.time_elapsed(), self.nps()
+        )
+    }
+}
+
+// searches every position on a single thread with fresh tables, so the node count is reproducible
+pub fn bench<CLK: MonotonicClock>(depth: Depth, progress: impl Fn(&BenchResult)) -> BenchReport {
+    let config = Config {
+        max_depth: Some(depth),
+        deterministic: true,
+        ..Config::default()
+    };
+
+    let results = BENCH_POSITIONS.iter()
+        .map(|&(rule, source)| {
+            let history = match source {
+                "" => History::empty(),
+                source => History::from_str(source).expect("invalid bench position"),
+            };
+
+            let result = match rule {
+                RuleKind::Renju => bench_position::<{ RuleKind::Renju }, CLK>(config, history),
+                RuleKind::Gomoku => bench_position::<{ RuleKind::Gomoku }, CLK>(config, history),
+                RuleKind::Freestyle => bench_position::<{ RuleKind::Freestyle }, CLK>(config, history),
+            };
+
+            progress(&result);
+
+            result
+        })
+        .collect();
+
+    BenchReport { depth, results }
+}
+
+fn bench_position<const R: RuleKind, CLK: MonotonicClock>(config: Config, history: History) -> BenchResult {
+    let state: GameState<R> = history.into();
+
+    let tt = TranspositionTable::new_with_size(BENCH_TT_SIZE);
+
+    let aborted = AtomicBool::new(false);
+    let global_counter_in_1k = AtomicU32::new(0);
+
+    let mut td = ThreadData::new(
+        WorkerThread::<CLK>::new(), 0,
+        SearchObjective::Best,
+        config,
+        ActiveEvaluator::from_state(&state),
+        tt.view(),
+        HistoryTable::empty(),
+        &aborted, &global_counter_in_1k,
+    );
+
+    let started_time = CLK::now();
+
+    let (score, best_move) = iterative_deepening::<R, _>(&mut td, state, None);
+
+    BenchResult {
+        rule: R,
+        history,
+        best_move,
+        score,
+        nodes: td.batch_counter.count_local(),
+        time_elapsed: started_time.elapsed(),
+    }
+}
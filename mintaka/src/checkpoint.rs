@@ -0,0 +1,39 @@
+use crate::config::SearchObjective;
+use crate::principal_variation::PrincipalVariation;
+use crate::value::Depth;
+use rusty_renju::hash_key::HashKey;
+use rusty_renju::notation::pos;
+use rusty_renju::notation::pos::MaybePos;
+use rusty_renju::notation::score::{Score, Scores};
+use std::time::Duration;
+
+// state of the last completed iteration, a later search on the same position continues from the next depth.
+// the aspiration window is rebuilt around the stored score
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchCheckpoint {
+    pub position_hash: HashKey,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub objective: SearchObjective,
+    pub depth: Depth,
+    pub score: Score,
+    pub best_move: MaybePos,
+    pub pv: PrincipalVariation,
+    pub selective_depth: u32,
+    pub root_scores: Vec<Score>,
+    pub total_nodes_in_1k: u32,
+    pub time_elapsed: Duration,
+}
+
+impl SearchCheckpoint {
+    pub fn root_scores_array(&self) -> [Score; pos::BOARD_SIZE] {
+        self.root_scores.as_slice()
+            .try_into()
+            .unwrap_or([Score::NAN; pos::BOARD_SIZE])
+    }
+
+    // nodes and time spent before the resumed search, carried over into the next checkpoint
+    pub fn carried_totals(resume: Option<&SearchCheckpoint>) -> (u32, Duration) {
+        resume.map_or((0, Duration::ZERO), |checkpoint| (checkpoint.total_nodes_in_1k, checkpoint.time_elapsed))
+    }
+}
@@ -1,5 +1,6 @@
-use crate::protocol::timer::Timer;
+use crate::protocol::timer::{TimeControl, Timer};
 use crate::value::{Depth, Depths};
+use rusty_renju::notation::score::Score;
 use rusty_renju::utils::byte_size::ByteSize;
 use std::cmp::Ordering;
 use std::fmt::Display;
@@ -8,9 +9,25 @@ use std::time::Duration;
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
 pub enum SearchObjective {
-    #[default] Best = 0,
-    Zeroing = 1,
-    Pondering = 2
+    #[default] Best,
+    Zeroing,
+    Pondering,
+    // scores every root move and picks the one closest to the target, for the player to move
+    Balance { target: Score },
+}
+
+impl SearchObjective {
+    pub fn balance_target(&self) -> Option<Score> {
+        match self {
+            SearchObjective::Balance { target } => Some(*target),
+            _ => None,
+        }
+    }
+
+    // searches whose completed iterations are checkpointed and can be continued
+    pub fn is_resumable(&self) -> bool {
+        matches!(self, SearchObjective::Best | SearchObjective::Balance { .. })
+    }
 }
 
 #[cfg_attr(feature = "typeshare", typeshare::typeshare)]
@@ -31,6 +48,12 @@ pub struct Config {
     pub initial_timer: Timer,
 
     pub spawn_depth_specialist: bool,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub skill_level: Option<u8>,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub deterministic: bool,
 }
 
 impl Default for Config {
@@ -45,6 +68,8 @@ impl Default for Config {
             pondering: false,
             initial_timer: Timer::default(),
             spawn_depth_specialist: false,
+            skill_level: None,
+            deterministic: false,
         }
     }
 }
@@ -91,13 +116,39 @@ impl Config {
             total_remaining: None,
             increment: Duration::from_secs(u32::MAX as u64),
             turn: None,
+            control: TimeControl::Fischer,
         },
         spawn_depth_specialist: true,
+        skill_level: None,
+        deterministic: false,
     };
 
     pub fn max_depth(&self) -> Depth {
         self.max_depth.unwrap_or(Depth::PLY_LIMIT)
     }
+
+    // deterministic threads each search a private tt and stop on their own budget instead of the clock,
+    // which needs a node or depth limit to end the search
+    pub fn independent_threads(&self) -> bool {
+        self.deterministic && (self.max_nodes_in_1k.is_some() || self.max_depth.is_some())
+    }
+
+    // vcf searches check the budget on independent threads, where a tripped one would read as no vcf
+    pub fn without_search_limits(self) -> Self {
+        Self {
+            max_nodes_in_1k: None,
+            deterministic: false,
+            ..self
+        }
+    }
+
+    pub fn thread_nodes_in_1k(&self) -> Option<u32> {
+        if self.independent_threads() {
+            self.max_nodes_in_1k.map(|in_1k| in_1k.div_ceil(self.workers.max(1)))
+        } else {
+            self.max_nodes_in_1k
+        }
+    }
 }
 
 #[derive(Debug)]
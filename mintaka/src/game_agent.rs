@@ -1,50 +1,94 @@
+use crate::checkpoint::SearchCheckpoint;
 use crate::config::{Config, SearchObjective};
 use crate::eval::evaluator::{ActiveEvaluator, Evaluator};
 use crate::eval::heuristic_evaluator::HeuristicEvaluator;
-use crate::game_state::{GameState, GameStateData};
+use crate::game_state::GameState;
 use crate::memo::history_table::HistoryTable;
-use crate::memo::transposition_table::{TTImportError, TranspositionTable};
+use crate::memo::transposition_table::{TTImportError, TTView, TranspositionTable};
+use crate::principal_variation::PrincipalVariation;
 use crate::protocol::command::Command;
 pub use crate::protocol::response::{ComputingResource, Response, ResponseSender};
 use crate::protocol::results::{BestMove, CommandResult, GameResult};
 use crate::protocol::timer::Timer;
 use crate::search::iterative_deepening;
-use crate::thread_data::ThreadData;
-use crate::thread_type::{MainThread, WorkerThread};
+use crate::search_endgame;
+use crate::search_endgame::VcfDefenses;
+use crate::skill::Skill;
+use crate::telemetry::TelemetryBoard;
+use crate::thread_data::{ThreadBuffers, ThreadData};
+use crate::thread_type::{MainThread, ThreadType, WorkerThread};
 use crate::time_manager::TimeManager;
 use crate::utils::monotonic_clock::MonotonicClock;
+use crate::value::Depth;
+#[cfg(not(feature = "rayon"))]
+use crate::worker_pool::{PoolScope, WorkerPool};
+use rusty_renju::board_validation::PositionError;
 use rusty_renju::notation::color::Color;
 use rusty_renju::notation::pos;
+use rusty_renju::notation::pos::MaybePos;
 use rusty_renju::notation::rule::RuleKind;
+use rusty_renju::notation::score::Score;
 use rusty_renju::utils::empty::Empty;
 use std::fmt::{Display, Formatter};
+use std::hash::{BuildHasher, RandomState};
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 #[cfg(not(feature = "rayon"))]
 macro_rules! search_scope {
-    ($body:expr) => {
-        std::thread::scope($body)
+    ($pool:expr, $body:expr) => {
+        $pool.scope($body)
     };
 }
 
 #[cfg(feature = "rayon")]
 macro_rules! search_scope {
-    ($body:expr) => {
+    ($pool:expr, $body:expr) => {
         rayon::in_place_scope($body)
     };
 }
 
 #[cfg(not(feature = "rayon"))]
-fn spawn_search_worker<'scope, 'env, F>(scope: &'scope std::thread::Scope<'scope, 'env>, worker: F)
-where F: FnOnce() + Send + 'scope {
-    scope.spawn(worker);
+fn spawn_search_worker<'env, F>(scope: &PoolScope<'_, 'env>, tid: u32, _config: Config, worker: F)
+where F: FnOnce(ThreadBuffers) -> ThreadBuffers + Send + 'env {
+    scope.spawn(tid as usize - 1, worker);
 }
 
 #[cfg(feature = "rayon")]
-fn spawn_search_worker<'scope, F>(scope: &rayon::Scope<'scope>, worker: F)
-where F: FnOnce() + Send + 'scope {
-    scope.spawn(move |_| worker());
+fn spawn_search_worker<'scope, F>(scope: &rayon::Scope<'scope>, _tid: u32, config: Config, worker: F)
+where F: FnOnce(ThreadBuffers) -> ThreadBuffers + Send + 'scope {
+    scope.spawn(move |_| { worker(ThreadBuffers::new(config)); });
+}
+
+// what a search thread settled on, independent threads are compared after the search
+struct ThreadResult {
+    tid: u32,
+    score: Score,
+    best_move: MaybePos,
+    pv: PrincipalVariation,
+    root_scores: [Score; pos::BOARD_SIZE],
+    completed_depth: Depth,
+    selective_depth: usize,
+    nodes_in_1k: u32,
+}
+
+impl ThreadResult {
+    fn new<const R: RuleKind, TH: ThreadType, E: Evaluator<R>>(
+        td: &ThreadData<R, TH, E>,
+        score: Score,
+        best_move: MaybePos,
+    ) -> Self {
+        Self {
+            tid: td.tid,
+            score,
+            best_move,
+            pv: td.root_pv,
+            root_scores: td.root_scores,
+            completed_depth: td.completed_depth,
+            selective_depth: td.selective_depth,
+            nodes_in_1k: td.batch_counter.count_in_1k(td.config.deterministic),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -56,6 +100,7 @@ pub enum GameError {
     ForbiddenMove,
     NoHistoryToUndo,
     NoTimeManagement,
+    InvalidPosition(PositionError),
 }
 
 impl Display for GameError {
@@ -68,6 +113,7 @@ impl Display for GameError {
             GameError::ForbiddenMove => write!(f, "forbidden move"),
             GameError::NoHistoryToUndo => write!(f, "no history to undo"),
             GameError::NoTimeManagement => write!(f, "no time management"),
+            GameError::InvalidPosition(error) => write!(f, "invalid position: {error}"),
         }
     }
 }
@@ -81,6 +127,9 @@ pub struct GameAgent<const R: RuleKind> {
     pub evaluator: ActiveEvaluator<R>,
     tt: TranspositionTable,
     ht: HistoryTable,
+    checkpoint: Option<SearchCheckpoint>,
+    #[cfg(not(feature = "rayon"))]
+    pool: WorkerPool,
 }
 
 impl<const R: RuleKind> GameAgent<R> {
@@ -96,6 +145,9 @@ impl<const R: RuleKind> GameAgent<R> {
             evaluator: ActiveEvaluator::from_state(&state),
             tt,
             ht: HistoryTable::empty(),
+            checkpoint: None,
+            #[cfg(not(feature = "rayon"))]
+            pool: WorkerPool::default(),
         }
     }
 
@@ -106,13 +158,48 @@ impl<const R: RuleKind> GameAgent<R> {
 
         self.tt.clear();
         self.ht = HistoryTable::empty();
+        self.checkpoint = None;
     }
 
-    fn sync_state(&mut self, data: GameStateData) {
-        self.state = data.into();
+    pub(crate) fn sync_state(&mut self, state: GameState<R>) {
+        self.state = state;
         self.evaluator = ActiveEvaluator::from_state(&self.state);
     }
 
+    pub(crate) fn tt_view(&self) -> TTView<'_> {
+        self.tt.view()
+    }
+
+    pub fn checkpoint(&self) -> Option<&SearchCheckpoint> {
+        self.checkpoint.as_ref()
+    }
+
+    pub fn restore_checkpoint(&mut self, checkpoint: Option<SearchCheckpoint>) {
+        self.checkpoint = checkpoint;
+    }
+
+    // joins the pooled search workers, the next launch spawns them again
+    pub fn release_workers(&mut self) {
+        #[cfg(not(feature = "rayon"))]
+        self.pool.clear();
+    }
+
+    fn validate_state(state: &GameState<R>) -> Result<(), GameError> {
+        // passes break the stone count parity, trust the caller instead
+        if state.history.iter().any(MaybePos::is_none) {
+            return Ok(());
+        }
+
+        // a history covering every stone already is a move order, only board-only inputs need the search
+        let result = if state.history.len() == state.board.stones as usize {
+            state.board.validate_stones()
+        } else {
+            state.board.validate().map(|_| ())
+        };
+
+        result.map_err(GameError::InvalidPosition)
+    }
+
     pub fn command(&mut self, command: Command) -> Result<CommandResult, GameError> {
         match command {
             Command::Play { hash, pos, draw_condition } => {
@@ -212,22 +299,33 @@ impl<const R: RuleKind> GameAgent<R> {
                     !self.state.board.player_color
                 };
 
-                self.state.board.batch_set_each_color_mut(
+                let mut state = self.state;
+
+                state.board.batch_set_each_color_mut(
                     black_stones.into_boxed_slice(),
                     white_stones.into_boxed_slice(),
                     player
                 );
 
+                Self::validate_state(&state)?;
+
+                self.state = state;
                 self.evaluator = HeuristicEvaluator::from_state(&self.state);
             },
             Command::Clear => {
                 self.reinit_from_state(GameState::empty());
             },
-            Command::Init(state) => {
-                self.reinit_from_state((*state).into());
+            Command::Init(data) => {
+                let state = (*data).into();
+                Self::validate_state(&state)?;
+
+                self.reinit_from_state(state);
             },
-            Command::Sync(state) => {
-                self.sync_state(*state);
+            Command::Sync(data) => {
+                let state = (*data).into();
+                Self::validate_state(&state)?;
+
+                self.sync_state(state);
             }
             Command::RebuildTT(size)  => {
                 self.tt.resize(size);
@@ -273,13 +371,36 @@ impl<const R: RuleKind> GameAgent<R> {
         response_sender: impl ResponseSender,
         global_counter_in_1k: Arc<AtomicU32>,
         aborted: Arc<AtomicBool>,
+    ) -> BestMove {
+        aborted.store(false, Ordering::Relaxed);
+
+        self.nested_launch::<CLK>(config, timer, search_objective, response_sender, global_counter_in_1k, &aborted)
+    }
+
+    // a search on behalf of an outer command, which owns the abort flag and keeps a pending stop request
+    pub(crate) fn nested_launch<CLK: MonotonicClock>(
+        &mut self,
+        config: Config,
+        timer: Timer,
+        search_objective: SearchObjective,
+        response_sender: impl ResponseSender,
+        global_counter_in_1k: Arc<AtomicU32>,
+        aborted: &AtomicBool,
     ) -> BestMove {
         let started_time = CLK::now();
 
+        // sampling near-best moves would undo the balancing
+        let skill = config.skill_level
+            .filter(|_| search_objective.balance_target().is_none())
+            .map(Skill::new);
+        let config = skill.map_or(config, |skill| skill.limit_config(config));
+
         let (computing_resource, time_manager) = self.next_computing_resource(config, timer, started_time);
 
         global_counter_in_1k.store(0, Ordering::Relaxed);
-        aborted.store(false, Ordering::Relaxed);
+
+        // raised by the main thread once it is done, which stops the workers
+        let stopped = AtomicBool::new(false);
 
         response_sender.response(Response::Begins(computing_resource));
 
@@ -287,26 +408,63 @@ impl<const R: RuleKind> GameAgent<R> {
             self.evaluator.init(&self.state.board)
         }
 
-        let tt_view = self.tt.view();
+        // results would otherwise depend on what earlier searches left behind
+        if config.deterministic {
+            self.tt.clear();
+            self.ht = HistoryTable::empty();
+        }
+
+        let resume = self.checkpoint.clone().filter(|checkpoint|
+            search_objective.is_resumable()
+                && checkpoint.objective == search_objective
+                && !config.deterministic
+                && checkpoint.position_hash == self.state.board.hash_key
+        );
+
+        #[cfg(not(feature = "rayon"))]
+        self.pool.resize(config.workers.saturating_sub(1) as usize, config);
+
+        // shared tt entries depend on thread timing, so deterministic threads get one slice each
+        let tt_views = if config.deterministic {
+            self.tt.partitioned_views(config.workers.max(1) as usize)
+        } else {
+            vec![self.tt.view(); config.workers.max(1) as usize]
+        };
+
+        let telemetry = TelemetryBoard::new(config.workers);
+        let worker_results = Mutex::new(Vec::new());
 
-        let (main_td, score, best_move) = search_scope!(|s| {
+        let (main_td, score, best_move) = search_scope!(self.pool, |s| {
             let state = self.state;
+            let resume = resume.as_ref();
 
             for tid in 1 .. config.workers {
-                let mut worker_td = ThreadData::new(
-                    WorkerThread::new(), tid,
-                    search_objective,
-                    config,
-                    self.evaluator.clone(),
-                    tt_view,
-                    self.ht,
-                    &aborted, &global_counter_in_1k
-                );
-
-                spawn_search_worker(s, move || {
-                    iterative_deepening::<R, WorkerThread<CLK>>(
-                        &mut worker_td, state
+                let evaluator = self.evaluator.clone();
+                let ht = self.ht;
+                let tt_view = tt_views[tid as usize % tt_views.len()];
+                let (stopped, global_counter_in_1k, telemetry, worker_results) =
+                    (&stopped, &global_counter_in_1k, &telemetry, &worker_results);
+
+                spawn_search_worker(s, tid, config, move |buffers| {
+                    let mut worker_td = ThreadData::from_buffers(
+                        WorkerThread::new(), tid,
+                        evaluator,
+                        tt_view,
+                        stopped, global_counter_in_1k,
+                        buffers.prepare(config, search_objective, ht),
+                    )
+                        .with_telemetry(telemetry)
+                        .with_abort_request(aborted);
+
+                    let (score, best_move) = iterative_deepening::<R, WorkerThread<CLK>>(
+                        &mut worker_td, state, resume
                     );
+
+                    if config.independent_threads() {
+                        worker_results.lock().unwrap().push(ThreadResult::new(&worker_td, score, best_move));
+                    }
+
+                    worker_td.into_buffers()
                 });
             }
 
@@ -319,13 +477,15 @@ impl<const R: RuleKind> GameAgent<R> {
                 search_objective,
                 config,
                 self.evaluator.clone(),
-                tt_view,
+                tt_views[0],
                 self.ht,
-                &aborted, &global_counter_in_1k,
-            );
+                &stopped, &global_counter_in_1k,
+            )
+                .with_telemetry(&telemetry)
+                .with_abort_request(aborted);
 
             let (score, best_move) = iterative_deepening::<R, MainThread<_, _>>(
-                &mut main_td, state
+                &mut main_td, state, resume
             );
 
             (main_td, score, best_move)
@@ -336,16 +496,93 @@ impl<const R: RuleKind> GameAgent<R> {
         self.tt.increase_age();
         self.ht.increase_age();
 
+        let time_elapsed = started_time.elapsed();
+
+        let mut result = ThreadResult::new(&main_td, score, best_move);
+        let mut total_nodes_in_1k = result.nodes_in_1k;
+
+        if config.independent_threads() {
+            let mut worker_results = worker_results.into_inner().unwrap();
+            worker_results.sort_by_key(|worker_result| worker_result.tid);
+
+            total_nodes_in_1k += worker_results.iter()
+                .map(|worker_result| worker_result.nodes_in_1k)
+                .sum::<u32>();
+
+            // the deepest completed iteration wins, ties go to the lower thread id
+            for worker_result in worker_results {
+                if worker_result.completed_depth > result.completed_depth {
+                    result = worker_result;
+                }
+            }
+        }
+
+        let ThreadResult { score, best_move, mut pv, root_scores, completed_depth, selective_depth, .. } = result;
+
+        if search_objective.is_resumable()
+            && completed_depth > resume.as_ref().map_or(0, |checkpoint| checkpoint.depth)
+        {
+            let (prev_nodes_in_1k, prev_time_elapsed) = SearchCheckpoint::carried_totals(resume.as_ref());
+
+            self.checkpoint = Some(SearchCheckpoint {
+                position_hash: self.state.board.hash_key,
+                objective: search_objective,
+                depth: completed_depth,
+                score,
+                best_move,
+                pv,
+                selective_depth: selective_depth as u32,
+                root_scores: root_scores.to_vec(),
+                total_nodes_in_1k: prev_nodes_in_1k + total_nodes_in_1k,
+                time_elapsed: prev_time_elapsed + time_elapsed,
+            });
+        }
+
+        let (best_move, score) = match skill {
+            Some(skill) => {
+                let seed = if config.deterministic {
+                    u64::from(self.state.board.hash_key)
+                } else {
+                    RandomState::new().hash_one(u64::from(self.state.board.hash_key))
+                };
+                let (picked_move, picked_score) = skill.pick(&root_scores, best_move, score, seed);
+
+                if picked_move != best_move {
+                    pv.init(picked_move);
+                }
+
+                (picked_move, picked_score)
+            },
+            None => (best_move, score),
+        };
+
         BestMove {
             position_hash: self.state.board.hash_key,
             best_move,
             score,
-            selective_depth: main_td.selective_depth as u32,
-            total_nodes_in_1k: main_td.batch_counter.count_global_in_1k(),
-            time_elapsed: started_time.elapsed(),
-            pv: main_td.root_pv
+            selective_depth: selective_depth as u32,
+            total_nodes_in_1k,
+            time_elapsed,
+            pv,
         }
     }
+
+    pub fn vcf_defenses<CLK: MonotonicClock>(&self, config: Config) -> Option<VcfDefenses> {
+        let aborted = AtomicBool::new(false);
+        let global_counter_in_1k = AtomicU32::new(0);
+
+        let mut td = ThreadData::new(
+            WorkerThread::<CLK>::new(), 0,
+            SearchObjective::Best,
+            config.without_search_limits(),
+            self.evaluator.clone(),
+            self.tt.view(),
+            self.ht,
+            &aborted, &global_counter_in_1k,
+        );
+
+        search_endgame::vcf_defenses(&mut td, &self.state)
+    }
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -353,6 +590,8 @@ pub struct GameAgentData<const R: RuleKind> {
     state: GameState<R>,
     tt: Vec<u8>,
     ht: HistoryTable,
+    #[cfg_attr(feature = "serde", serde(default))]
+    checkpoint: Option<SearchCheckpoint>,
 }
 
 impl<const R: RuleKind> From<&GameAgent<R>> for GameAgentData<R> {
@@ -361,6 +600,7 @@ impl<const R: RuleKind> From<&GameAgent<R>> for GameAgentData<R> {
             state: agent.state,
             tt: agent.tt.export(9),
             ht: agent.ht,
+            checkpoint: agent.checkpoint.clone(),
         }
     }
 }
@@ -377,6 +617,9 @@ impl<const R: RuleKind> TryFrom<GameAgentData<R>> for GameAgent<R> {
             evaluator,
             tt,
             ht: data.ht,
+            checkpoint: data.checkpoint,
+            #[cfg(not(feature = "rayon"))]
+            pool: WorkerPool::default(),
         })
     }
 }
@@ -4,7 +4,11 @@
 #![cfg_attr(target_arch = "aarch64", feature(stdarch_aarch64_prefetch))]
 extern crate core;
 
+pub mod analysis;
+pub mod balance;
 pub mod batch_counter;
+pub mod bench;
+pub mod checkpoint;
 pub mod config;
 pub mod eval;
 pub mod game_agent;
@@ -12,13 +16,19 @@ pub mod game_state;
 pub mod memo;
 pub mod movegen;
 pub mod principal_variation;
+pub mod proof_tree;
 pub mod protocol;
 pub mod search;
+pub mod search_handle;
+pub mod skill;
 pub mod tablebase;
+pub mod telemetry;
+pub mod test_suite;
 pub mod thread_data;
 pub mod thread_type;
 pub mod time_manager;
 pub mod value;
+pub mod worker_pool;
 pub mod utils;
 pub mod search_endgame;
 pub mod params;
@@ -79,6 +79,16 @@ impl TranspositionTable {
         }
     }
 
+    // disjoint slices of the table, threads searching separate slices never observe each other
+    pub fn partitioned_views(&self, parts: usize) -> Vec<TTView<'_>> {
+        let len = (self.table.len() / parts.max(1)).max(1);
+
+        self.table.chunks(len)
+            .take(parts)
+            .map(|table| TTView { table, age: self.fetch_age() })
+            .collect()
+    }
+
     pub fn optimal_size(nps: usize, expected_runtime: Duration) -> ByteSize {
         const FILL_FACTOR: f64 = 0.75;
         const ENTRY_SIZE: f64 = size_of::<TTEntryBucket>() as f64 / TTEntryBucket::BUCKET_SIZE as f64;
@@ -88,14 +98,7 @@ impl TranspositionTable {
     }
 
     pub fn hash_full_permille(&self) -> usize {
-        const SAMPLE: usize = 1000;
-
-        let used: usize = self.table.iter()
-            .take(SAMPLE)
-            .map(|entry| entry.usage(self.age.load(Ordering::Relaxed) as u8))
-            .sum();
-
-        used * 1000 / SAMPLE * TTEntryBucket::BUCKET_SIZE as usize
+        self.view().hash_full_permille()
     }
 
     // compression level: 0-9
@@ -154,6 +157,19 @@ pub struct TTView<'a> {
 }
 
 impl TTView<'_> {
+    pub fn hash_full_permille(&self) -> usize {
+        const SAMPLE: usize = 1000;
+
+        let sampled = self.table.len().min(SAMPLE);
+
+        let used: usize = self.table.iter()
+            .take(sampled)
+            .map(|entry| entry.usage(self.age as u8))
+            .sum();
+
+        used * 1000 / (sampled.max(1) * TTEntryBucket::BUCKET_SIZE as usize)
+    }
+
     fn calculate_index(&self, key: HashKey) -> usize {
         ((u64::from(key) as u128 * (self.table.len() as u128)) >> 64) as usize
     }
@@ -0,0 +1,404 @@
+use crate::config::{Config, SearchObjective};
+use crate::eval::evaluator::{ActiveEvaluator, Evaluator};
+use crate::game_agent::GameAgent;
+use crate::game_state::GameState;
+use crate::memo::history_table::HistoryTable;
+use crate::memo::transposition_table::decode_mate_distance;
+use crate::memo::tt_entry::ScoreKind;
+use crate::protocol::response::NullResponseSender;
+use crate::protocol::timer::Timer;
+use crate::search_endgame;
+use crate::thread_data::ThreadData;
+use crate::thread_type::WorkerThread;
+use crate::utils::monotonic_clock::MonotonicClock;
+use rusty_renju::notation::color::Color;
+use rusty_renju::notation::pos;
+use rusty_renju::notation::pos::Pos;
+use rusty_renju::notation::rule::RuleKind;
+use rusty_renju::notation::score::{Score, Scores};
+use rusty_renju::utils::empty::Empty;
+use std::fmt::{Display, Formatter, Write};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+
+const PROOF_SEARCH_NODES_IN_1K: u32 = 100;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ProofError {
+    NotWinning,
+    Unproven,
+    TooLarge,
+    Aborted,
+}
+
+impl Display for ProofError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProofError::NotWinning => write!(f, "no winning move found"),
+            ProofError::Unproven => write!(f, "failed to verify every defense"),
+            ProofError::TooLarge => write!(f, "proof tree exceeds the node limit"),
+            ProofError::Aborted => write!(f, "proof search aborted"),
+        }
+    }
+}
+
+impl std::error::Error for ProofError {}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProofNode {
+    pub attack: Pos,
+    // empty once the attack completes a five
+    pub defenses: Vec<ProofDefense>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProofDefense {
+    pub defense: Pos,
+    pub answer: ProofNode,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProofTree {
+    pub attacker: Color,
+    pub black_stones: Vec<Pos>,
+    pub white_stones: Vec<Pos>,
+    pub root: ProofNode,
+    pub nodes: usize,
+}
+
+impl ProofTree {
+    pub fn depth(&self) -> usize {
+        fn node_depth(node: &ProofNode) -> usize {
+            1 + node.defenses.iter()
+                .map(|defense| 1 + node_depth(&defense.answer))
+                .max()
+                .unwrap_or(0)
+        }
+
+        node_depth(&self.root)
+    }
+
+    pub fn to_sgf(&self) -> String {
+        let mut sgf = format!("(;FF[4]GM[4]SZ[{}]", pos::BOARD_WIDTH);
+
+        for (tag, stones) in [("AB", &self.black_stones), ("AW", &self.white_stones)] {
+            if !stones.is_empty() {
+                sgf.push_str(tag);
+
+                for &pos in stones {
+                    write!(sgf, "[{}]", sgf_coord(pos)).unwrap();
+                }
+            }
+        }
+
+        write!(sgf, "PL[{}]", sgf_color(self.attacker)).unwrap();
+
+        write_attack_sgf(&mut sgf, &self.root, self.attacker);
+
+        sgf.push(')');
+        sgf
+    }
+}
+
+fn sgf_color(color: Color) -> char {
+    match color {
+        Color::Black => 'B',
+        Color::White => 'W',
+    }
+}
+
+// sgf rows count from the top edge
+fn sgf_coord(pos: Pos) -> String {
+    let col = (b'a' + pos.col()) as char;
+    let row = (b'a' + pos::BOARD_WIDTH - 1 - pos.row()) as char;
+
+    format!("{col}{row}")
+}
+
+fn write_attack_sgf(sgf: &mut String, node: &ProofNode, attacker: Color) {
+    write!(sgf, ";{}[{}]", sgf_color(attacker), sgf_coord(node.attack)).unwrap();
+
+    let write_defense = |sgf: &mut String, defense: &ProofDefense| {
+        write!(sgf, ";{}[{}]", sgf_color(!attacker), sgf_coord(defense.defense)).unwrap();
+        write_attack_sgf(sgf, &defense.answer, attacker);
+    };
+
+    match node.defenses.as_slice() {
+        [] => {},
+        [defense] => write_defense(sgf, defense),
+        defenses => for defense in defenses {
+            sgf.push('(');
+            write_defense(sgf, defense);
+            sgf.push(')');
+        },
+    }
+}
+
+type Candidate<P, const R: RuleKind> = fn(&mut P, &GameState<R>) -> Result<Option<Pos>, ProofError>;
+
+struct Prover<'a, const R: RuleKind> {
+    agent: &'a mut GameAgent<R>,
+    config: Config,
+    aborted: Arc<AtomicBool>,
+    max_nodes: usize,
+    nodes: usize,
+    searched_nodes: usize,
+}
+
+impl<const R: RuleKind> Prover<'_, R> {
+    // searched nodes share the budget with the tree nodes
+    fn remaining_nodes(&self) -> usize {
+        self.max_nodes.saturating_sub(self.nodes + self.searched_nodes)
+    }
+
+    // every candidate is re-verified, so the tt and the search only have to suggest moves
+    fn prove_attack<CLK: MonotonicClock>(&mut self, state: &GameState<R>, vcf_only: bool) -> Result<ProofNode, ProofError> {
+        if self.aborted.load(Ordering::Relaxed) {
+            return Err(ProofError::Aborted);
+        }
+
+        self.nodes += 1;
+        if self.remaining_nodes() == 0 {
+            return Err(ProofError::TooLarge);
+        }
+
+        let attacker = state.board.player_color;
+
+        if let Some(five) = state.board.patterns.five_pos[attacker].ok()
+            && state.board.is_legal_move(five)
+        {
+            return Ok(ProofNode { attack: five, defenses: vec![] });
+        }
+
+        // an opponent four leaves a single candidate
+        if let Some(block) = state.board.patterns.five_pos[!attacker].ok() {
+            return if state.board.is_legal_move(block) {
+                self.prove_defenses::<CLK>(state, block, vcf_only)
+            } else {
+                Err(ProofError::Unproven)
+            };
+        }
+
+        let mut tried = vec![];
+
+        // a defense that leaves the threat vcf on board is answered by the vcf alone
+        let candidates: &[Candidate<Self, R>] = if vcf_only {
+            &[Self::vcf_candidate::<CLK>]
+        } else {
+            &[Self::tt_candidate, Self::vcf_candidate::<CLK>, Self::search_candidate::<CLK>]
+        };
+
+        for candidate in candidates {
+            let Some(attack) = candidate(self, state)? else {
+                continue;
+            };
+
+            if tried.contains(&attack) || !state.board.is_legal_move(attack) {
+                continue;
+            }
+
+            tried.push(attack);
+
+            match self.prove_defenses::<CLK>(state, attack, vcf_only) {
+                Err(ProofError::Unproven) => continue,
+                result => return result,
+            }
+        }
+
+        Err(ProofError::Unproven)
+    }
+
+    fn prove_defenses<CLK: MonotonicClock>(
+        &mut self,
+        state: &GameState<R>,
+        attack: Pos,
+        vcf_only: bool,
+    ) -> Result<ProofNode, ProofError> {
+        let attacker = state.board.player_color;
+
+        let mut state = *state;
+        state.play_mut(attack);
+
+        if state.board.find_winner(attack).is_some() {
+            return Ok(ProofNode { attack, defenses: vec![] });
+        }
+
+        let defender = state.board.player_color;
+
+        if state.board.patterns.five_pos[defender].is_some() {
+            return Err(ProofError::Unproven);
+        }
+
+        let has_four = state.board.patterns.five_pos[attacker].is_some();
+
+        // against a four only the blocks are considered, every other move loses to the five
+        let candidates: Vec<Pos> = state.board.legal_field(defender)
+            .iter_hot_idx()
+            .filter(|&idx| idx < pos::BOARD_SIZE
+                && (!has_four || state.board.patterns.field[attacker][idx].has_five())
+            )
+            .map(|idx| Pos::from_index(idx as u8))
+            .collect();
+
+        // a four without a legal block is already won, anything else running out of moves is not
+        if candidates.is_empty() && !has_four {
+            return Err(ProofError::Unproven);
+        }
+
+        // a quiet attack is only expanded when it threatens a vcf, then only the moves refuting that vcf need a search
+        let threat_defenses = if has_four {
+            None
+        } else if vcf_only {
+            return Err(ProofError::Unproven);
+        } else {
+            let threat = self.endgame_search::<CLK, _>(&state, search_endgame::vcf_defenses)
+                .ok_or(ProofError::Unproven)?;
+
+            Some(threat.defenses.into_iter()
+                .map(|defense| defense.defense)
+                .collect::<Vec<_>>())
+        };
+
+        let mut defenses = Vec::with_capacity(candidates.len());
+
+        for defense in candidates {
+            let mut next = state;
+            next.play_mut(defense);
+
+            let answer_vcf_only = vcf_only || threat_defenses.as_ref()
+                .is_some_and(|threat_defenses| !threat_defenses.contains(&defense));
+
+            let answer = self.prove_attack::<CLK>(&next, answer_vcf_only)?;
+
+            defenses.push(ProofDefense { defense, answer });
+        }
+
+        Ok(ProofNode { attack, defenses })
+    }
+
+    fn endgame_search<CLK: MonotonicClock, T>(
+        &mut self,
+        state: &GameState<R>,
+        search: impl FnOnce(&mut ThreadData<R, WorkerThread<CLK>, ActiveEvaluator<R>>, &GameState<R>) -> T,
+    ) -> T {
+        let aborted = AtomicBool::new(false);
+        let global_counter_in_1k = AtomicU32::new(0);
+
+        let mut td = ThreadData::new(
+            WorkerThread::<CLK>::new(), 0, SearchObjective::Best, self.config.without_search_limits(),
+            ActiveEvaluator::from_state(state),
+            self.agent.tt_view(), HistoryTable::empty(), &aborted, &global_counter_in_1k
+        ).with_abort_request(&self.aborted);
+
+        let result = search(&mut td, state);
+
+        self.searched_nodes += td.batch_counter.count_local() as usize;
+
+        result
+    }
+
+    fn tt_candidate(&mut self, state: &GameState<R>) -> Result<Option<Pos>, ProofError> {
+        let Some(entry) = self.agent.tt_view().probe(state.board.hash_key) else {
+            return Ok(None);
+        };
+
+        Ok((entry.tt_flag.maybe_score_kind() != Some(ScoreKind::UpperBound)
+            && Score::is_winning(decode_mate_distance(entry.score as Score, 0))
+        ).then_some(entry.best_move.ok()).flatten())
+    }
+
+    fn vcf_candidate<CLK: MonotonicClock>(&mut self, state: &GameState<R>) -> Result<Option<Pos>, ProofError> {
+        Ok(self.endgame_search::<CLK, _>(state, search_endgame::endgame_sequence::<R, false>)
+            .and_then(|sequence| sequence.first().copied()))
+    }
+
+    fn search_candidate<CLK: MonotonicClock>(&mut self, state: &GameState<R>) -> Result<Option<Pos>, ProofError> {
+        let budget_in_1k = (self.remaining_nodes() / 1000) as u32;
+
+        if budget_in_1k == 0 {
+            return Err(ProofError::TooLarge);
+        }
+
+        let mut config = self.config;
+        config.max_nodes_in_1k = config.max_nodes_in_1k.map(|in_1k| in_1k.min(budget_in_1k));
+
+        self.agent.sync_state(*state);
+
+        let best_move = self.agent.nested_launch::<CLK>(
+            config,
+            Timer::INFINITE,
+            SearchObjective::Best,
+            NullResponseSender,
+            Arc::new(AtomicU32::new(0)),
+            &self.aborted,
+        );
+
+        self.searched_nodes += best_move.total_nodes_in_1k as usize * 1000;
+
+        if self.aborted.load(Ordering::Relaxed) {
+            return Err(ProofError::Aborted);
+        }
+
+        Ok(Score::is_winning(best_move.score)
+            .then_some(best_move.best_move.ok())
+            .flatten())
+    }
+}
+
+impl<const R: RuleKind> GameAgent<R> {
+    // expands a proven win into every legal defense and a verified answer to each of them,
+    // max_nodes bounds the tree nodes and the nodes searched to find the answers together
+    pub fn prove_win<CLK: MonotonicClock>(
+        &mut self,
+        config: Config,
+        max_nodes: usize,
+        aborted: Arc<AtomicBool>,
+    ) -> Result<ProofTree, ProofError> {
+        aborted.store(false, Ordering::Relaxed);
+
+        let root_state = self.state;
+        let root_checkpoint = self.checkpoint().cloned();
+        let attacker = root_state.board.player_color;
+
+        let mut config = config;
+        config.max_nodes_in_1k = Some(config.max_nodes_in_1k.unwrap_or(PROOF_SEARCH_NODES_IN_1K));
+        config.skill_level = None;
+
+        let mut prover = Prover { agent: self, config, aborted, max_nodes, nodes: 0, searched_nodes: 0 };
+
+        let result = prover.prove_attack::<CLK>(&root_state, false);
+        let nodes = prover.nodes;
+
+        // an interrupted endgame search reads as a missing answer, which must not pass for a refutation
+        let result = if prover.aborted.load(Ordering::Relaxed) {
+            Err(ProofError::Aborted)
+        } else {
+            result
+        };
+
+        // the searches on sub-positions replaced the checkpoint of the root search
+        self.sync_state(root_state);
+        self.restore_checkpoint(root_checkpoint);
+
+        let root = result.map_err(|err| match err {
+            ProofError::Unproven if nodes == 1 => ProofError::NotWinning,
+            err => err,
+        })?;
+
+        let stones_of = |color: Color| (0 .. pos::U8_BOARD_SIZE)
+            .map(Pos::from_index)
+            .filter(|&pos| root_state.board.stone_kind(pos) == Some(color))
+            .collect();
+
+        Ok(ProofTree {
+            attacker,
+            black_stones: stones_of(Color::Black),
+            white_stones: stones_of(Color::White),
+            root,
+            nodes,
+        })
+    }
+}
@@ -1,3 +1,4 @@
+use crate::checkpoint::SearchCheckpoint;
 use crate::principal_variation::PrincipalVariation;
 use crate::value::Depth;
 use rusty_renju::hash_key::HashKey;
@@ -15,6 +16,31 @@ pub struct ComputingResource {
     pub nodes_in_1k: Option<u32>,
 }
 
+#[cfg_attr(feature = "typeshare", typeshare::typeshare)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SearchStatics {
+    pub search_nodes_in_1k: u32,
+    pub vcf_nodes_in_1k: u32,
+    pub tt_hit_rate: f32,
+    pub tt_cutoff_rate: f32,
+    pub beta_cutoff_rate: f32,
+    pub avg_cutoff_distance: f32,
+}
+
+#[cfg_attr(feature = "typeshare", typeshare::typeshare)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchTelemetry {
+    pub nps_in_1k: u32,
+    pub hash_full_permille: u32,
+    pub thread_depths: Vec<Depth>,
+    // collected only with the debug-statics feature
+    pub statics: Option<SearchStatics>,
+    pub time_elapsed: Duration,
+}
+
 #[cfg(feature = "serde")]
 #[cfg_attr(feature = "typeshare", typeshare::typeshare)]
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -30,6 +56,7 @@ pub enum Response {
         pv: PrincipalVariation,
         time_elapsed: Duration,
     },
+    Telemetry(SearchTelemetry),
 }
 
 #[cfg(not(feature = "serde"))]
@@ -45,12 +72,16 @@ pub enum Response {
         pv: PrincipalVariation,
         time_elapsed: Duration,
     },
+    Telemetry(SearchTelemetry),
 }
 
 pub trait ResponseSender: Send {
 
     fn response(&self, response: Response);
 
+    // called after every completed iteration of a resumable search
+    fn checkpoint(&self, _checkpoint: &SearchCheckpoint) {}
+
 }
 
 #[derive(Clone)]
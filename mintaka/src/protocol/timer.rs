@@ -1,5 +1,60 @@
 use std::time::Duration;
 
+// typeshare-cli does not read `cfg_attr(..., serde(...))`, so keep serde attrs direct.
+#[cfg(feature = "serde")]
+#[cfg_attr(feature = "typeshare", typeshare::typeshare)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Default, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", content = "content")]
+pub enum TimeControl {
+    // increment is credited after each move
+    #[default]
+    Fischer,
+    // main time followed by periods that reset when a move is made within them
+    ByoYomi {
+        periods: u32,
+        period_time: Duration,
+    },
+    // the clock starts running only after the delay elapsed
+    SimpleDelay {
+        delay: Duration,
+    },
+    // the time used is refunded after each move, up to the delay
+    Bronstein {
+        delay: Duration,
+    },
+    // period_time is credited once moves_to_go reaches zero
+    MovesToGo {
+        moves_to_go: u32,
+        moves_per_period: u32,
+        period_time: Duration,
+    },
+    // the time used is credited to the opponent
+    Hourglass,
+}
+
+#[cfg(not(feature = "serde"))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Default)]
+pub enum TimeControl {
+    #[default]
+    Fischer,
+    ByoYomi {
+        periods: u32,
+        period_time: Duration,
+    },
+    SimpleDelay {
+        delay: Duration,
+    },
+    Bronstein {
+        delay: Duration,
+    },
+    MovesToGo {
+        moves_to_go: u32,
+        moves_per_period: u32,
+        period_time: Duration,
+    },
+    Hourglass,
+}
+
 #[cfg_attr(feature = "typeshare", typeshare::typeshare)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
@@ -8,6 +63,8 @@ pub struct Timer {
     pub total_remaining: Option<Duration>,
     pub increment: Duration,
     pub turn: Option<Duration>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub control: TimeControl,
 }
 
 impl Default for Timer {
@@ -16,6 +73,7 @@ impl Default for Timer {
             total_remaining: Some(Duration::from_secs(60 * 5)),
             increment: Duration::from_secs(0),
             turn: Some(Duration::from_secs(30)),
+            control: TimeControl::Fischer,
         }
     }
 }
@@ -32,12 +90,67 @@ impl Timer {
             total_remaining: total_time,
             increment,
             turn,
+            control: TimeControl::Fischer,
         }
     }
 
+    pub const fn with_control(mut self, control: TimeControl) -> Self {
+        self.control = control;
+        self
+    }
+
+    pub fn is_flagged(&self) -> bool {
+        self.total_remaining == Some(Duration::ZERO)
+            && !matches!(self.control, TimeControl::ByoYomi { periods: 1 .., .. })
+    }
+
     pub fn consume(&mut self, running_time: Duration) {
-        if let Some(total_remaining) = &mut self.total_remaining {
-            *total_remaining = total_remaining.saturating_sub(running_time);
+        let Some(total_remaining) = &mut self.total_remaining else {
+            return;
+        };
+
+        match &mut self.control {
+            TimeControl::Fischer | TimeControl::Hourglass => {
+                *total_remaining = total_remaining.saturating_sub(running_time);
+            },
+            TimeControl::ByoYomi { periods, period_time } => {
+                let overflow = running_time.saturating_sub(*total_remaining);
+                *total_remaining = total_remaining.saturating_sub(running_time);
+
+                // every period fully spent is lost, the last one entered resets
+                if !overflow.is_zero() && !period_time.is_zero() {
+                    let lost = (overflow.as_nanos() / period_time.as_nanos()) as u32;
+                    *periods = periods.saturating_sub(lost);
+                }
+            },
+            TimeControl::SimpleDelay { delay } => {
+                *total_remaining = total_remaining.saturating_sub(running_time.saturating_sub(*delay));
+            },
+            TimeControl::Bronstein { delay } => {
+                if *total_remaining > running_time {
+                    *total_remaining = *total_remaining - running_time + running_time.min(*delay);
+                } else {
+                    *total_remaining = Duration::ZERO;
+                }
+            },
+            TimeControl::MovesToGo { moves_to_go, moves_per_period, period_time } => {
+                *total_remaining = total_remaining.saturating_sub(running_time);
+
+                if *moves_to_go > 0 {
+                    *moves_to_go -= 1;
+
+                    if *moves_to_go == 0 && !total_remaining.is_zero() {
+                        *total_remaining += *period_time;
+                        *moves_to_go = *moves_per_period;
+                    }
+                }
+            },
+        }
+    }
+
+    pub fn opponent_consume(&mut self, running_time: Duration) {
+        if self.control == TimeControl::Hourglass {
+            self.append(running_time);
         }
     }
 
@@ -1,3 +1,4 @@
+use crate::checkpoint::SearchCheckpoint;
 use crate::eval::evaluator::Evaluator;
 use crate::game_state::GameState;
 use crate::memo::history_table::{QuietPlied, TacticalPlied};
@@ -15,6 +16,7 @@ use crate::value::Depth;
 use crate::{params, value};
 use rusty_renju::const_for;
 use rusty_renju::notation::color::Color;
+use rusty_renju::notation::pos;
 use rusty_renju::notation::pos::MaybePos;
 use rusty_renju::notation::rule::RuleKind;
 use rusty_renju::notation::score::{Score, Scores};
@@ -47,20 +49,37 @@ struct OffPVNode; impl NodeType for OffPVNode {
 pub fn iterative_deepening<const R: RuleKind, TH: ThreadType>(
     td: &mut ThreadData<R, TH, impl Evaluator<R>>,
     mut state: GameState<R>,
+    resume: Option<&SearchCheckpoint>,
 ) -> (Score, MaybePos) {
     let position_hash = state.board.hash_key;
 
     let mut score: Score = 0;
     let mut best_move = MaybePos::NONE;
     let mut root_pv = PrincipalVariation::EMPTY;
+    let mut root_scores = [Score::NAN; pos::BOARD_SIZE];
     let mut selective_depth = 0;
+    let mut completed_depth = 0;
+
+    if let Some(checkpoint) = resume {
+        score = checkpoint.score;
+        best_move = checkpoint.best_move;
+        root_pv = checkpoint.pv;
+        root_scores = checkpoint.root_scores_array();
+        selective_depth = checkpoint.selective_depth as usize;
+        completed_depth = checkpoint.depth;
+
+        td.best_move = best_move;
+    }
 
     let mut mate_count = 0;
     let mut best_move_changes = 0;
 
-    let starting_depth = (td.tid % 10 + 1) as Depth;
+    let starting_depth = completed_depth + (td.tid % 10 + 1) as Depth;
     'iterative_deepening: for depth in starting_depth ..= td.config.max_depth() {
-        let iter_score = if depth < 5 {
+        td.root_scores = [Score::NAN; pos::BOARD_SIZE];
+
+        // exact root scores are searched with a full window anyway
+        let iter_score = if depth < 5 || td.requires_exact_root_scores() {
             pvs::<R, TH, RootNode>(td, &mut state, depth, -Score::INF, Score::INF, false)
         } else {
             aspiration::<R, TH>(td, &mut state, depth, score)
@@ -77,7 +96,9 @@ pub fn iterative_deepening<const R: RuleKind, TH: ThreadType>(
         score = iter_score;
         best_move = td.best_move;
         root_pv = td.pvs[0];
+        root_scores = td.root_scores;
         selective_depth = td.selective_depth;
+        completed_depth = depth;
 
         if TH::IS_MAIN {
             td.thread_type.make_response(Response::Status {
@@ -85,10 +106,35 @@ pub fn iterative_deepening<const R: RuleKind, TH: ThreadType>(
                 best_move,
                 score,
                 pv: td.pvs[0],
-                total_nodes_in_1k: td.batch_counter.count_global_in_1k(),
+                total_nodes_in_1k: td.batch_counter.count_in_1k(td.config.deterministic),
                 time_elapsed: td.thread_type.time_manager().elapsed(),
                 selective_depth: selective_depth as Depth,
-            })
+            });
+
+            if td.search_objective.is_resumable() {
+                let (prev_nodes_in_1k, prev_time_elapsed) = SearchCheckpoint::carried_totals(resume);
+
+                td.thread_type.make_checkpoint(&SearchCheckpoint {
+                    position_hash,
+                    objective: td.search_objective,
+                    depth,
+                    score,
+                    best_move,
+                    pv: root_pv,
+                    selective_depth: selective_depth as u32,
+                    root_scores: root_scores.to_vec(),
+                    total_nodes_in_1k: prev_nodes_in_1k + td.batch_counter.count_in_1k(td.config.deterministic),
+                    time_elapsed: prev_time_elapsed + td.thread_type.time_manager().elapsed(),
+                });
+            }
+        }
+
+        td.publish_telemetry(depth);
+
+        if TH::IS_MAIN
+            && let Some(telemetry) = td.collect_telemetry()
+        {
+            td.thread_type.make_response(Response::Telemetry(telemetry));
         }
 
         if Score::is_mate(iter_score) {
@@ -118,6 +164,7 @@ pub fn iterative_deepening<const R: RuleKind, TH: ThreadType>(
         }
 
         if TH::IS_MAIN
+            && !td.config.independent_threads()
             && td.thread_type.time_manager().is_soft_limit_reached()
         {
             break 'iterative_deepening;
@@ -134,6 +181,8 @@ pub fn iterative_deepening<const R: RuleKind, TH: ThreadType>(
 
     td.selective_depth = selective_depth;
     td.root_pv = root_pv;
+    td.root_scores = root_scores;
+    td.completed_depth = completed_depth;
 
     (score, best_move)
 }
@@ -184,8 +233,7 @@ fn pvs<const R: RuleKind, TH: ThreadType, NT: NodeType>(
     mut beta: Score,
     cut_node: bool,
 ) -> Score {
-    if TH::IS_MAIN
-        && td.should_check_limit()
+    if td.should_check_limit()
         && td.search_limit_exceeded()
     {
         td.set_aborted();
@@ -201,6 +249,7 @@ fn pvs<const R: RuleKind, TH: ThreadType, NT: NodeType>(
     }
 
     td.batch_counter.increment();
+    td.record_node();
 
     td.pvs[td.ply].clear();
 
@@ -302,6 +351,8 @@ fn pvs<const R: RuleKind, TH: ThreadType, NT: NodeType>(
 
     // endgame-hit
     if let Some(entry) = tt_entry && entry.tt_flag.is_endgame_proven() {
+        td.record_tt_hit(true);
+
         if NT::IS_ROOT {
             td.best_move = entry.best_move;
             td.singular_root = true;
@@ -323,14 +374,17 @@ fn pvs<const R: RuleKind, TH: ThreadType, NT: NodeType>(
             tt_endgame_depth = entry.tt_flag.endgame_depth();
 
             // tt-cutoff
-            if !NT::IS_PV
+            let tt_cutoff = !NT::IS_PV
                 && depth_left <= entry.depth as Depth
                 && match entry.tt_flag.score_kind() {
                     ScoreKind::LowerBound => tt_score >= beta,
                     ScoreKind::UpperBound => tt_score <= alpha,
                     ScoreKind::Exact => true,
-                }
-            {
+                };
+
+            td.record_tt_hit(tt_cutoff);
+
+            if tt_cutoff {
                 if tt_score >= beta
                     && let Some(pos) = tt_move.ok()
                     && threat_kind.is_none()
@@ -350,6 +404,8 @@ fn pvs<const R: RuleKind, TH: ThreadType, NT: NodeType>(
             static_eval = entry.eval as Score;
         }
         Some(entry) => { // endgame-tt
+            td.record_tt_hit(false);
+
             tt_move = MaybePos::NONE;
             tt_pv = false;
             tt_endgame_depth = entry.tt_flag.endgame_depth();
@@ -421,6 +477,11 @@ fn pvs<const R: RuleKind, TH: ThreadType, NT: NodeType>(
 
     td.clear_killer();
 
+    let exact_root_scores = NT::IS_ROOT && td.requires_exact_root_scores();
+    let balance_target = NT::IS_ROOT
+        .then(|| td.search_objective.balance_target())
+        .flatten();
+
     let original_alpha = alpha;
     let mut best_score = -Score::INF;
     let mut best_move = MaybePos::NONE;
@@ -529,7 +590,9 @@ fn pvs<const R: RuleKind, TH: ThreadType, NT: NodeType>(
 
         searched_moves += 1;
 
-        let score = if moves_made == 1 { // full-window search
+        let score = if exact_root_scores { // every root move needs an exact score
+            -pvs::<R, TH, NT::NextType>(td, state, new_full_depth, -Score::INF, Score::INF, false)
+        } else if moves_made == 1 { // full-window search
             -pvs::<R, TH, NT::NextType>(td, state, new_depth, -beta, -alpha, !NT::IS_PV && !cut_node)
         } else { // zero-window search
             let mut score = -pvs::<R, TH, OffPVNode>(
@@ -567,6 +630,22 @@ fn pvs<const R: RuleKind, TH: ThreadType, NT: NodeType>(
             return Score::DRAW;
         }
 
+        if NT::IS_ROOT {
+            td.root_scores[pos.idx_usize()] = score;
+        }
+
+        if let Some(target) = balance_target {
+            if score.abs_diff(target) < best_score.abs_diff(target) {
+                best_score = score;
+                best_move = pos.into();
+
+                let sub_pv = td.pvs[td.ply + 1];
+                td.pvs[td.ply].load(pos.into(), sub_pv);
+            }
+
+            continue 'position_search;
+        }
+
         if score <= best_score {
             continue;
         }
@@ -583,6 +662,7 @@ fn pvs<const R: RuleKind, TH: ThreadType, NT: NodeType>(
             }
 
             if alpha >= beta { // beta cutoff
+                td.record_beta_cutoff(moves_made);
                 break 'position_search;
             }
         }
@@ -621,16 +701,19 @@ fn pvs<const R: RuleKind, TH: ThreadType, NT: NodeType>(
         }
     }
 
-    td.tt.store(
-        state.board.hash_key,
-        best_move,
-        Some(score_kind),
-        tt_endgame_depth,
-        depth_left,
-        static_eval,
-        transposition_table::encode_mate_distance(best_score, td.ply),
-        tt_pv | NT::IS_PV,
-    );
+    // the balanced root score is not the value of the position
+    if balance_target.is_none() {
+        td.tt.store(
+            state.board.hash_key,
+            best_move,
+            Some(score_kind),
+            tt_endgame_depth,
+            depth_left,
+            static_eval,
+            transposition_table::encode_mate_distance(best_score, td.ply),
+            tt_pv | NT::IS_PV,
+        );
+    }
 
     best_score
 }
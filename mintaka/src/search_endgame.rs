@@ -239,6 +239,150 @@ pub fn endgame_sequence<const R: RuleKind, const VCT: bool>(
     })
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DefenseKind {
+    Five,
+    Block,
+    ForbiddenTrap,
+    CounterFour,
+    Indirect,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct VcfDefense {
+    pub defense: Pos,
+    pub kind: DefenseKind,
+    // ply of the threat sequence that no longer works after the defense
+    pub breaks_at: Option<usize>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct VcfDefenses {
+    pub threat: Vec<Pos>,
+    pub defenses: Vec<VcfDefense>,
+}
+
+// every legal move is re-verified by searching the opponent's vcf again, an empty defense list means the position is lost.
+// a listed defense only refutes the vcf, it carries no refuting line and the attacker may still win by slower threats
+pub fn vcf_defenses<const R: RuleKind>(
+    td: &mut ThreadData<R, impl ThreadType, impl Evaluator<R>>,
+    state: &GameState<R>
+) -> Option<VcfDefenses> {
+    let defender = state.board.player_color;
+
+    let mut threat_state = *state;
+    threat_state.pass_mut();
+
+    let threat = full_vcf_sequence(td, &threat_state)?;
+
+    let mut defenses = vec![];
+
+    for defense in state.board.legal_field(defender).iter_hot_idx()
+        .filter(|&idx| idx < pos::BOARD_SIZE)
+        .map(|idx| Pos::from_index(idx as u8))
+    {
+        let mut next = *state;
+        next.play_mut(defense);
+
+        let wins = next.board.find_winner(defense).is_some();
+
+        if !wins && full_vcf_sequence(td, &next).is_some() {
+            continue;
+        }
+
+        defenses.push(VcfDefense {
+            defense,
+            kind: classify_defense(state, &next, &threat, defense, wins),
+            breaks_at: threat_breaking_ply(&next, &threat),
+        });
+    }
+
+    Some(VcfDefenses { threat, defenses })
+}
+
+pub fn full_vcf_sequence<const R: RuleKind, E: Evaluator<R>>(
+    td: &mut ThreadData<R, impl ThreadType, E>,
+    state: &GameState<R>
+) -> Option<Vec<Pos>> {
+    let recent_move = state.history.previous_action().unwrap_or(pos::CENTER);
+
+    let mut endgame_moves = generate_endgame_moves::<R, false>(&state.board, pos::BOARD_WIDTH, recent_move);
+
+    if endgame_moves.is_empty() {
+        return None;
+    }
+
+    endgame_moves.sort_moves(&state.board, recent_move);
+    endgame_moves.init();
+
+    td.evaluator = E::from_state(state);
+
+    vcf::<R, 5, VecSequenceTracker>(
+        td, VcfWin, Depth::MAX,
+        *state, endgame_moves,
+        0, Score::MIN, Score::MAX
+    ).map(|mut sq| {
+        sq.reverse();
+        sq
+    })
+}
+
+fn classify_defense<const R: RuleKind>(
+    state: &GameState<R>,
+    next: &GameState<R>,
+    threat: &[Pos],
+    defense: Pos,
+    wins: bool,
+) -> DefenseKind {
+    let attacker = !state.board.player_color;
+
+    let attacker_forbidden = |board: &Board<R>, pos: Pos|
+        R == RuleKind::Renju && attacker == Color::Black && board.patterns.is_forbidden(pos);
+
+    if wins {
+        DefenseKind::Five
+    } else if threat.contains(&defense) {
+        DefenseKind::Block
+    } else if next.board.patterns.five_pos[!attacker].ok().is_some_and(|five| attacker_forbidden(&next.board, five))
+        || threat.iter().step_by(2).any(|&attack|
+            attacker_forbidden(&next.board, attack) && !attacker_forbidden(&state.board, attack)
+        )
+    {
+        DefenseKind::ForbiddenTrap
+    } else if next.board.patterns.five_pos[!attacker].is_some() {
+        DefenseKind::CounterFour
+    } else {
+        DefenseKind::Indirect
+    }
+}
+
+// replays the threat sequence after the defense and reports the first ply where it diverges
+fn threat_breaking_ply<const R: RuleKind>(state: &GameState<R>, threat: &[Pos]) -> Option<usize> {
+    let attacker = state.board.player_color;
+
+    let mut state = *state;
+
+    for (ply, &pos) in threat.iter().enumerate() {
+        let diverged = if ply % 2 == 0 {
+            !state.board.is_legal_move(pos)
+                || state.board.patterns.five_pos[!attacker].ok().is_some_and(|five| five != pos)
+        } else {
+            state.board.patterns.five_pos[attacker] != pos.into()
+        };
+
+        if diverged {
+            return Some(ply);
+        }
+
+        state.play_mut(pos);
+    }
+
+    None
+}
+
 fn vcf<const R: RuleKind, const DW: u8, Sq: SequenceTracker>(
     td: &mut ThreadData<R, impl ThreadType, impl Evaluator<R>>,
     dest: impl VcfDestination,
@@ -296,8 +440,7 @@ fn try_vcf<const R: RuleKind, const C: Color, const DW: u8, TH: ThreadType, Sq:
 
     'vcf_search: loop {
         'position_search: while let Some(four_pos) = vcf_moves.next() {
-            if TH::IS_MAIN
-                && td.should_check_limit()
+            if td.should_check_limit()
                 && td.search_limit_exceeded()
             {
                 td.set_aborted();
@@ -331,6 +474,7 @@ fn try_vcf<const R: RuleKind, const C: Color, const DW: u8, TH: ThreadType, Sq:
             let parent_player_color = state.board.player_color;
 
             td.batch_counter.increment();
+            td.record_endgame_node();
             let artifact = state.board.set_mut(four_pos);
             td.evaluator.play(&state.board, artifact, four_pos.into());
             vcf_ply += 1;
@@ -411,6 +555,7 @@ fn try_vcf<const R: RuleKind, const C: Color, const DW: u8, TH: ThreadType, Sq:
             }
 
             td.batch_counter.increment();
+            td.record_endgame_node();
             let artifact = state.board.set_mut(response_pos);
             td.evaluator.play(&state.board, artifact, response_pos.into());
             vcf_ply += 1;
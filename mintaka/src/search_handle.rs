@@ -0,0 +1,248 @@
+use crate::config::{Config, SearchObjective};
+use crate::game_agent::GameAgent;
+use crate::protocol::response::{Response, ResponseSender};
+use crate::protocol::results::BestMove;
+use crate::protocol::timer::Timer;
+use crate::utils::monotonic_clock::MonotonicClock;
+use rusty_renju::notation::rule::RuleKind;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+#[cfg(feature = "async")]
+use std::{
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll, Waker},
+};
+
+pub struct SearchOutcome<const R: RuleKind> {
+    pub game_agent: GameAgent<R>,
+    pub best_move: BestMove,
+}
+
+#[derive(Default)]
+struct Wakers {
+    #[cfg(feature = "async")]
+    outcome: Mutex<Option<Waker>>,
+    #[cfg(feature = "async")]
+    response: Mutex<Option<Waker>>,
+}
+
+impl Wakers {
+    #[cfg(feature = "async")]
+    fn register(slot: &Mutex<Option<Waker>>, waker: &Waker) {
+        let mut slot = slot.lock().unwrap();
+
+        if !slot.as_ref().is_some_and(|registered| registered.will_wake(waker)) {
+            *slot = Some(waker.clone());
+        }
+    }
+
+    #[cfg(feature = "async")]
+    fn wake(slot: &Mutex<Option<Waker>>) {
+        if let Some(waker) = slot.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+
+    fn wake_response(&self) {
+        #[cfg(feature = "async")]
+        Self::wake(&self.response);
+    }
+
+    fn wake_all(&self) {
+        #[cfg(feature = "async")]
+        Self::wake(&self.outcome);
+        self.wake_response();
+    }
+}
+
+struct ChannelResponseSender {
+    tx: mpsc::Sender<Response>,
+    wakers: Arc<Wakers>,
+    abort_requested: Arc<AtomicBool>,
+    aborted: Arc<AtomicBool>,
+}
+
+impl ResponseSender for ChannelResponseSender {
+    fn response(&self, response: Response) {
+        // launch clears the abort flag on start, so re-raise an abort that arrived before it
+        if matches!(response, Response::Begins(_)) && self.abort_requested.load(Ordering::Relaxed) {
+            self.aborted.store(true, Ordering::Relaxed);
+        }
+
+        let _ = self.tx.send(response);
+        self.wakers.wake_response();
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SearchAbortHandle {
+    abort_requested: Arc<AtomicBool>,
+    aborted: Arc<AtomicBool>,
+}
+
+impl SearchAbortHandle {
+    pub fn abort(&self) {
+        self.abort_requested.store(true, Ordering::Relaxed);
+        self.aborted.store(true, Ordering::Relaxed);
+    }
+}
+
+// owns the search thread; dropping the handle aborts the search and detaches the thread
+pub struct SearchHandle<const R: RuleKind> {
+    abort_handle: SearchAbortHandle,
+    global_counter_in_1k: Arc<AtomicU32>,
+    responses: mpsc::Receiver<Response>,
+    outcome: mpsc::Receiver<SearchOutcome<R>>,
+    #[cfg(feature = "async")]
+    wakers: Arc<Wakers>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl<const R: RuleKind> SearchHandle<R> {
+    pub fn abort(&self) {
+        self.abort_handle.abort();
+    }
+
+    pub fn abort_handle(&self) -> SearchAbortHandle {
+        self.abort_handle.clone()
+    }
+
+    pub fn nodes_in_1k(&self) -> u32 {
+        self.global_counter_in_1k.load(Ordering::Relaxed)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.thread.as_ref().is_none_or(|thread| thread.is_finished())
+    }
+
+    pub fn try_recv_response(&self) -> Option<Response> {
+        self.responses.try_recv().ok()
+    }
+
+    // blocks until the next response, returns None once the search has finished
+    pub fn recv_response(&self) -> Option<Response> {
+        self.responses.recv().ok()
+    }
+
+    pub fn try_join(mut self) -> Result<SearchOutcome<R>, Self> {
+        match self.outcome.try_recv() {
+            Ok(outcome) => Ok(outcome),
+            Err(mpsc::TryRecvError::Empty) => Err(self),
+            Err(mpsc::TryRecvError::Disconnected) => self.resume_panic(),
+        }
+    }
+
+    pub fn join(mut self) -> SearchOutcome<R> {
+        match self.outcome.recv() {
+            Ok(outcome) => outcome,
+            Err(_) => self.resume_panic(),
+        }
+    }
+
+    fn resume_panic(&mut self) -> ! {
+        match self.thread.take().map(JoinHandle::join) {
+            Some(Err(payload)) => std::panic::resume_unwind(payload),
+            _ => panic!("search thread exited without an outcome"),
+        }
+    }
+}
+
+impl<const R: RuleKind> Drop for SearchHandle<R> {
+    fn drop(&mut self) {
+        if !self.is_finished() {
+            self.abort();
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<const R: RuleKind> Future for SearchHandle<R> {
+    type Output = SearchOutcome<R>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Wakers::register(&self.wakers.outcome, cx.waker());
+
+        match self.outcome.try_recv() {
+            Ok(outcome) => Poll::Ready(outcome),
+            Err(mpsc::TryRecvError::Empty) => Poll::Pending,
+            Err(mpsc::TryRecvError::Disconnected) => self.resume_panic(),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<const R: RuleKind> futures_core::Stream for SearchHandle<R> {
+    type Item = Response;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Wakers::register(&self.wakers.response, cx.waker());
+
+        match self.responses.try_recv() {
+            Ok(response) => Poll::Ready(Some(response)),
+            Err(mpsc::TryRecvError::Empty) => Poll::Pending,
+            Err(mpsc::TryRecvError::Disconnected) => Poll::Ready(None),
+        }
+    }
+}
+
+impl<const R: RuleKind> GameAgent<R> {
+    // moves the agent onto a dedicated thread; it is handed back with the search outcome
+    pub fn spawn_search<CLK: MonotonicClock + 'static>(
+        mut self,
+        config: Config,
+        timer: Timer,
+        search_objective: SearchObjective,
+    ) -> SearchHandle<R> {
+        let abort_handle = SearchAbortHandle::default();
+        let global_counter_in_1k = Arc::new(AtomicU32::new(0));
+        let wakers = Arc::new(Wakers::default());
+
+        let (response_tx, responses) = mpsc::channel();
+        let (outcome_tx, outcome) = mpsc::sync_channel(1);
+
+        let thread = std::thread::Builder::new()
+            .name("mintaka-search".to_string())
+            .spawn({
+                let abort_requested = abort_handle.abort_requested.clone();
+                let aborted = abort_handle.aborted.clone();
+                let global_counter_in_1k = global_counter_in_1k.clone();
+                let wakers = wakers.clone();
+
+                move || {
+                    let response_sender = ChannelResponseSender {
+                        tx: response_tx,
+                        wakers: wakers.clone(),
+                        abort_requested,
+                        aborted: aborted.clone(),
+                    };
+
+                    let best_move = self.launch::<CLK>(
+                        config,
+                        timer,
+                        search_objective,
+                        response_sender,
+                        global_counter_in_1k,
+                        aborted,
+                    );
+
+                    let _ = outcome_tx.send(SearchOutcome { game_agent: self, best_move });
+
+                    wakers.wake_all();
+                }
+            })
+            .expect("failed to spawn search thread");
+
+        SearchHandle {
+            abort_handle,
+            global_counter_in_1k,
+            responses,
+            outcome,
+            #[cfg(feature = "async")]
+            wakers,
+            thread: Some(thread),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::config::Config;
+use crate::value::Depth;
+use rusty_renju::notation::pos;
+use rusty_renju::notation::pos::{MaybePos, Pos};
+use rusty_renju::notation::score::{Score, Scores};
+
+pub const MAX_SKILL_LEVEL: u8 = 20;
+
+const MIN_ELO: u32 = 800;
+const MAX_ELO: u32 = 2600;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Skill {
+    level: u8,
+}
+
+impl Skill {
+    pub fn new(level: u8) -> Self {
+        Self { level: level.min(MAX_SKILL_LEVEL) }
+    }
+
+    pub fn from_elo(elo: u32) -> Self {
+        let elo = elo.clamp(MIN_ELO, MAX_ELO);
+
+        Self::new(((elo - MIN_ELO) * MAX_SKILL_LEVEL as u32 / (MAX_ELO - MIN_ELO)) as u8)
+    }
+
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    pub fn is_full_strength(&self) -> bool {
+        self.level == MAX_SKILL_LEVEL
+    }
+
+    pub fn limit_config(&self, mut config: Config) -> Config {
+        if self.is_full_strength() {
+            return config;
+        }
+
+        let level = self.level as Depth;
+
+        let max_nodes_in_1k = (4.0 * 2f64.powf(self.level as f64 / 2.0)) as u32;
+        let max_depth = 2 + level;
+        let max_vcf_depth = 6 + level * 2;
+
+        config.max_nodes_in_1k = Some(config.max_nodes_in_1k.map_or(max_nodes_in_1k, |nodes| nodes.min(max_nodes_in_1k)));
+        config.max_depth = Some(config.max_depth.map_or(max_depth, |depth| depth.min(max_depth)));
+        config.max_vcf_depth = Some(config.max_vcf_depth.map_or(max_vcf_depth, |depth| depth.min(max_vcf_depth)));
+
+        config
+    }
+
+    fn temperature(&self) -> f64 {
+        (MAX_SKILL_LEVEL - self.level) as f64 * 12.0
+    }
+
+    fn max_loss(&self) -> Score {
+        (MAX_SKILL_LEVEL - self.level) as Score * 20
+    }
+
+    // wins longer than the horizon are missed with the given probability
+    fn vcf_horizon(&self) -> usize {
+        5 + self.level as usize
+    }
+
+    fn vcf_miss_chance(&self) -> f64 {
+        (MAX_SKILL_LEVEL - self.level) as f64 / MAX_SKILL_LEVEL as f64 * 0.6
+    }
+
+    // samples among near-best root moves, root_scores holds exact scores and Score::NAN for unsearched moves
+    pub fn pick(
+        &self,
+        root_scores: &[Score; pos::BOARD_SIZE],
+        best_move: MaybePos,
+        best_score: Score,
+        seed: u64,
+    ) -> (MaybePos, Score) {
+        if self.is_full_strength() || best_move.is_none() || Score::is_losing(best_score) {
+            return (best_move, best_score);
+        }
+
+        let mut rng = seed;
+
+        if Score::is_winning(best_score) {
+            let mate_distance = (Score::WIN - best_score) as usize;
+
+            if mate_distance <= self.vcf_horizon() || next_f64(&mut rng) >= self.vcf_miss_chance() {
+                return (best_move, best_score);
+            }
+        }
+
+        let candidates = root_scores.iter()
+            .enumerate()
+            .filter(|&(_, &score)| score != Score::NAN && !Score::is_mate(score))
+            .map(|(idx, &score)| (Pos::from_index(idx as u8), score))
+            .collect::<Vec<_>>();
+
+        let Some(top) = candidates.iter().map(|&(_, score)| score).max() else {
+            return (best_move, best_score);
+        };
+
+        let temperature = self.temperature().max(1.0);
+
+        let weighted = candidates.into_iter()
+            .filter(|&(_, score)| score >= top - self.max_loss())
+            .map(|(pos, score)| (pos, score, ((score - top) as f64 / temperature).exp()))
+            .collect::<Vec<_>>();
+
+        let mut threshold = next_f64(&mut rng) * weighted.iter().map(|&(_, _, weight)| weight).sum::<f64>();
+
+        for &(pos, score, weight) in weighted.iter() {
+            threshold -= weight;
+
+            if threshold <= 0.0 {
+                return (pos.into(), score);
+            }
+        }
+
+        weighted.last()
+            .map_or((best_move, best_score), |&(pos, score, _)| (pos.into(), score))
+    }
+}
+
+// splitmix64
+fn next_u64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E3779B97F4A7C15);
+
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+fn next_f64(state: &mut u64) -> f64 {
+    (next_u64(state) >> 11) as f64 / (1u64 << 53) as f64
+}
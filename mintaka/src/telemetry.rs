@@ -0,0 +1,82 @@
+use crate::protocol::response::{SearchStatics, SearchTelemetry};
+use crate::thread_data::DebugStatics;
+use crate::value::Depth;
+use std::sync::atomic::{AtomicI32, AtomicU64, Ordering};
+use std::time::Duration;
+
+#[derive(Default)]
+struct ThreadTelemetry {
+    depth: AtomicI32,
+    visited_nodes: AtomicU64,
+    endgame_nodes: AtomicU64,
+    tt_hits: AtomicU64,
+    tt_cutoffs: AtomicU64,
+    beta_cutoffs: AtomicU64,
+    sum_cutoff_distance: AtomicU64,
+}
+
+// threads publish their counters after each completed iteration, the main thread collects them
+pub struct TelemetryBoard {
+    threads: Box<[ThreadTelemetry]>,
+}
+
+impl TelemetryBoard {
+    pub fn new(workers: u32) -> Self {
+        Self {
+            threads: (0 .. workers.max(1)).map(|_| ThreadTelemetry::default()).collect(),
+        }
+    }
+
+    pub fn publish(&self, tid: u32, depth: Depth, statics: &[DebugStatics]) {
+        let Some(thread) = self.threads.get(tid as usize) else {
+            return;
+        };
+
+        thread.depth.store(depth, Ordering::Relaxed);
+
+        if cfg!(feature = "debug-statics") {
+            let sum = statics.iter().fold(DebugStatics::EMPTY, |acc, ply| acc.merge(ply));
+
+            thread.visited_nodes.store(sum.visited_nodes, Ordering::Relaxed);
+            thread.endgame_nodes.store(sum.endgame_nodes, Ordering::Relaxed);
+            thread.tt_hits.store(sum.sum_tt_hit, Ordering::Relaxed);
+            thread.tt_cutoffs.store(sum.sum_tt_cutoff, Ordering::Relaxed);
+            thread.beta_cutoffs.store(sum.beta_cutoffs, Ordering::Relaxed);
+            thread.sum_cutoff_distance.store(sum.sum_cutoff_distance, Ordering::Relaxed);
+        }
+    }
+
+    pub fn collect(&self, total_nodes_in_1k: u32, time_elapsed: Duration, hash_full_permille: u32) -> SearchTelemetry {
+        let nps_in_1k = (total_nodes_in_1k as f64 / time_elapsed.as_secs_f64().max(1e-3)) as u32;
+
+        let statics = cfg!(feature = "debug-statics").then(|| {
+            let sum = |counter: fn(&ThreadTelemetry) -> &AtomicU64| -> u64 {
+                self.threads.iter().map(|thread| counter(thread).load(Ordering::Relaxed)).sum()
+            };
+
+            let visited_nodes = sum(|thread| &thread.visited_nodes);
+            let beta_cutoffs = sum(|thread| &thread.beta_cutoffs);
+
+            let ratio = |count: u64, total: u64| count as f32 / total.max(1) as f32;
+
+            SearchStatics {
+                search_nodes_in_1k: (visited_nodes / 1000) as u32,
+                vcf_nodes_in_1k: (sum(|thread| &thread.endgame_nodes) / 1000) as u32,
+                tt_hit_rate: ratio(sum(|thread| &thread.tt_hits), visited_nodes),
+                tt_cutoff_rate: ratio(sum(|thread| &thread.tt_cutoffs), visited_nodes),
+                beta_cutoff_rate: ratio(beta_cutoffs, visited_nodes),
+                avg_cutoff_distance: ratio(sum(|thread| &thread.sum_cutoff_distance), beta_cutoffs),
+            }
+        });
+
+        SearchTelemetry {
+            nps_in_1k,
+            hash_full_permille,
+            thread_depths: self.threads.iter()
+                .map(|thread| thread.depth.load(Ordering::Relaxed))
+                .collect(),
+            statics,
+            time_elapsed,
+        }
+    }
+}
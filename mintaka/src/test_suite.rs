@@ -0,0 +1,311 @@
+use crate::config::{Config, SearchObjective};
+use crate::game_agent::GameAgent;
+use crate::game_state::GameState;
+use crate::memo::history_table::HistoryTable;
+use crate::protocol::command::Command;
+use crate::protocol::response::NullResponseSender;
+use crate::search_endgame;
+use crate::thread_data::ThreadData;
+use crate::thread_type::WorkerThread;
+use crate::utils::monotonic_clock::MonotonicClock;
+use rusty_renju::history::History;
+use rusty_renju::notation::pos::{MaybePos, Pos};
+use rusty_renju::notation::rule::RuleKind;
+use rusty_renju::notation::score::Score;
+use rusty_renju::utils::empty::Empty;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+const FIELD_SEPARATOR: char = ';';
+const EMPTY_POSITION: &str = "-";
+
+// one case per line, `<history> ; <expectation> ; ...`, for example
+// h8,h9,i8,i9 ; bm j8 g8 ; id "open three"
+// h8,i9,h9,i8,h10 ; vcf
+// blank lines and lines starting with `#` are skipped
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Expectation {
+    BestMove(Vec<Pos>),
+    AvoidMove(Vec<Pos>),
+    Vcf,
+    NoVcf,
+    ScoreAbove(Score),
+    ScoreBelow(Score),
+}
+
+impl Expectation {
+    fn needs_search(&self) -> bool {
+        !matches!(self, Expectation::Vcf | Expectation::NoVcf)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SuiteCase {
+    pub id: String,
+    pub history: History,
+    pub expectations: Vec<Expectation>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TestSuite {
+    pub cases: Vec<SuiteCase>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SuiteParseError {
+    pub line: usize,
+    pub reason: &'static str,
+}
+
+impl Display for SuiteParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+impl std::error::Error for SuiteParseError {}
+
+impl FromStr for TestSuite {
+    type Err = SuiteParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let cases = source.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+            .map(|(idx, line)|
+                parse_case(line, idx + 1)
+                    .map_err(|reason| SuiteParseError { line: idx + 1, reason })
+            )
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { cases })
+    }
+}
+
+fn parse_case(line: &str, line_number: usize) -> Result<SuiteCase, &'static str> {
+    let mut fields = line.split(FIELD_SEPARATOR).map(str::trim);
+
+    let history = match fields.next().unwrap_or_default() {
+        "" | EMPTY_POSITION => History::empty(),
+        position => History::from_str(position)?,
+    };
+
+    let mut id = format!("line-{line_number}");
+    let mut expectations = vec![];
+
+    for field in fields.filter(|field| !field.is_empty()) {
+        let (opcode, operand) = field.split_once(char::is_whitespace)
+            .map_or((field, ""), |(opcode, operand)| (opcode, operand.trim()));
+
+        match opcode {
+            "id" => id = operand.trim_matches('"').to_string(),
+            "bm" => expectations.push(Expectation::BestMove(parse_moves(operand)?)),
+            "am" => expectations.push(Expectation::AvoidMove(parse_moves(operand)?)),
+            "vcf" => expectations.push(Expectation::Vcf),
+            "novcf" => expectations.push(Expectation::NoVcf),
+            "score" => {
+                let (comparator, value) = operand.split_once(char::is_whitespace)
+                    .ok_or("missing score bound")?;
+
+                let value = value.trim().parse::<Score>().map_err(|_| "invalid score bound")?;
+
+                expectations.push(match comparator {
+                    ">" => Expectation::ScoreAbove(value),
+                    "<" => Expectation::ScoreBelow(value),
+                    _ => return Err("unknown score comparator"),
+                });
+            },
+            _ => return Err("unknown expectation"),
+        }
+    }
+
+    if expectations.is_empty() {
+        return Err("case without expectations");
+    }
+
+    Ok(SuiteCase { id, history, expectations })
+}
+
+fn parse_moves(operand: &str) -> Result<Vec<Pos>, &'static str> {
+    let moves = operand.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .map(|token| Pos::from_str(&token.to_lowercase()).map_err(|_| "invalid move"))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if moves.is_empty() {
+        return Err("missing moves");
+    }
+
+    Ok(moves)
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuiteCaseResult {
+    pub id: String,
+    pub passed: bool,
+    pub best_move: MaybePos,
+    pub score: Option<Score>,
+    pub vcf: Option<Vec<Pos>>,
+    pub total_nodes_in_1k: u32,
+    pub time_elapsed: Duration,
+    pub error: Option<String>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuiteReport {
+    pub results: Vec<SuiteCaseResult>,
+    pub completed: bool,
+}
+
+impl SuiteReport {
+    pub fn passed(&self) -> usize {
+        self.results.iter().filter(|result| result.passed).count()
+    }
+
+    pub fn total_nodes_in_1k(&self) -> u64 {
+        self.results.iter().map(|result| result.total_nodes_in_1k as u64).sum()
+    }
+
+    pub fn time_elapsed(&self) -> Duration {
+        self.results.iter().map(|result| result.time_elapsed).sum()
+    }
+}
+
+impl Display for SuiteReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f, "passed {}/{}, nodes={}k, elapsed={:?}",
+            self.passed(), self.results.len(), self.total_nodes_in_1k(), self.time_elapsed()
+        )
+    }
+}
+
+impl<const R: RuleKind> GameAgent<R> {
+    // every case starts from cleared tables, so results do not depend on the order of the suite
+    pub fn run_suite<CLK: MonotonicClock>(
+        config: Config,
+        suite: &TestSuite,
+        progress: impl Fn(&SuiteCaseResult),
+        aborted: Arc<AtomicBool>,
+    ) -> SuiteReport {
+        aborted.store(false, Ordering::Relaxed);
+
+        let mut agent = Self::new(config);
+
+        let mut results = Vec::with_capacity(suite.cases.len());
+        let mut completed = true;
+
+        for case in suite.cases.iter() {
+            if aborted.load(Ordering::Relaxed) {
+                completed = false;
+                break;
+            }
+
+            let result = agent.run_suite_case::<CLK>(config, case);
+
+            progress(&result);
+
+            results.push(result);
+        }
+
+        SuiteReport { results, completed }
+    }
+
+    fn run_suite_case<CLK: MonotonicClock>(&mut self, config: Config, case: &SuiteCase) -> SuiteCaseResult {
+        let mut result = SuiteCaseResult {
+            id: case.id.clone(),
+            passed: false,
+            best_move: MaybePos::NONE,
+            score: None,
+            vcf: None,
+            total_nodes_in_1k: 0,
+            time_elapsed: Duration::ZERO,
+            error: None,
+        };
+
+        let state = match replay_history::<R>(&case.history) {
+            Ok(state) => state,
+            Err(err) => {
+                result.error = Some(err.to_string());
+                return result;
+            }
+        };
+
+        if let Err(err) = self.command(Command::Init(Box::new((&state).into()))) {
+            result.error = Some(err.to_string());
+            return result;
+        }
+
+        if case.expectations.iter().any(Expectation::needs_search) {
+            let best_move = self.launch::<CLK>(
+                config,
+                config.initial_timer,
+                SearchObjective::Best,
+                NullResponseSender,
+                Arc::new(AtomicU32::new(0)),
+                Arc::new(AtomicBool::new(false)),
+            );
+
+            result.best_move = best_move.best_move;
+            result.score = Some(best_move.score);
+            result.total_nodes_in_1k += best_move.total_nodes_in_1k;
+            result.time_elapsed += best_move.time_elapsed;
+        }
+
+        if case.expectations.iter().any(|expectation| !expectation.needs_search()) {
+            let started_time = CLK::now();
+
+            let aborted = AtomicBool::new(false);
+            let global_counter_in_1k = AtomicU32::new(0);
+
+            let mut td = ThreadData::new(
+                WorkerThread::<CLK>::new(), 0,
+                SearchObjective::Best,
+                config.without_search_limits(),
+                self.evaluator.clone(),
+                self.tt_view(),
+                HistoryTable::empty(),
+                &aborted, &global_counter_in_1k,
+            );
+
+            result.vcf = search_endgame::full_vcf_sequence(&mut td, &state);
+            result.total_nodes_in_1k += td.batch_counter.count_local_in_1k();
+            result.time_elapsed += started_time.elapsed();
+        }
+
+        result.passed = case.expectations.iter().all(|expectation| match expectation {
+            Expectation::BestMove(moves) => result.best_move.ok().is_some_and(|pos| moves.contains(&pos)),
+            Expectation::AvoidMove(moves) => result.best_move.ok().is_some_and(|pos| !moves.contains(&pos)),
+            Expectation::Vcf => result.vcf.is_some(),
+            Expectation::NoVcf => result.vcf.is_none(),
+            Expectation::ScoreAbove(bound) => result.score.is_some_and(|score| score > *bound),
+            Expectation::ScoreBelow(bound) => result.score.is_some_and(|score| score < *bound),
+        });
+
+        result
+    }
+}
+
+fn replay_history<const R: RuleKind>(history: &History) -> Result<GameState<R>, &'static str> {
+    let mut state = GameState::<R>::empty();
+
+    for action in history.iter() {
+        match action.ok() {
+            Some(pos) if state.board.is_legal_move(pos) => {
+                state.play_mut(pos);
+            },
+            Some(_) => return Err("illegal move in position"),
+            None => state.pass_mut(),
+        }
+    }
+
+    Ok(state)
+}
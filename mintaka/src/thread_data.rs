@@ -5,7 +5,10 @@ use crate::game_state::RecoveryState;
 use crate::memo::history_table::HistoryTable;
 use crate::memo::transposition_table::TTView;
 use crate::principal_variation::PrincipalVariation;
+use crate::protocol::response::SearchTelemetry;
 use crate::search_endgame::EndgameFrame;
+use crate::skill::MAX_SKILL_LEVEL;
+use crate::telemetry::TelemetryBoard;
 use crate::thread_type::ThreadType;
 use crate::value::Depth;
 use crate::{params, value};
@@ -13,6 +16,7 @@ use rusty_renju::notation::pos;
 use rusty_renju::notation::pos::{MaybePos, Pos};
 use rusty_renju::notation::rule::RuleKind;
 use rusty_renju::notation::score::{Score, Scores};
+use rusty_renju::utils::empty::Empty;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 
 pub const KILLER_MOVE_SLOTS: usize = 2;
@@ -40,19 +44,80 @@ impl SearchFrame {
 
 #[derive(Clone, Copy)]
 pub struct DebugStatics {
-    pub visited_nodes: u32,
-    pub sum_cutoff_distance: u32,
-    pub sum_tt_hit: u32,
-    pub sum_tt_cutoff: u32,
+    pub visited_nodes: u64,
+    pub endgame_nodes: u64,
+    pub beta_cutoffs: u64,
+    pub sum_cutoff_distance: u64,
+    pub sum_tt_hit: u64,
+    pub sum_tt_cutoff: u64,
 }
 
 impl DebugStatics {
-    const EMPTY: Self = Self {
+    pub const EMPTY: Self = Self {
         visited_nodes: 0,
+        endgame_nodes: 0,
+        beta_cutoffs: 0,
         sum_cutoff_distance: 0,
         sum_tt_hit: 0,
         sum_tt_cutoff: 0,
     };
+
+    pub fn merge(self, other: &Self) -> Self {
+        Self {
+            visited_nodes: self.visited_nodes + other.visited_nodes,
+            endgame_nodes: self.endgame_nodes + other.endgame_nodes,
+            beta_cutoffs: self.beta_cutoffs + other.beta_cutoffs,
+            sum_cutoff_distance: self.sum_cutoff_distance + other.sum_cutoff_distance,
+            sum_tt_hit: self.sum_tt_hit + other.sum_tt_hit,
+            sum_tt_cutoff: self.sum_tt_cutoff + other.sum_tt_cutoff,
+        }
+    }
+}
+
+// heap allocations of a thread data, kept by pooled workers between searches
+pub struct ThreadBuffers {
+    config: Config,
+    search_objective: SearchObjective,
+    ht: Box<HistoryTable>,
+    ss: Box<[SearchFrame; value::MAX_PLY_SLOTS]>,
+    pvs: Box<[PrincipalVariation; value::MAX_PLY_SLOTS]>,
+    killers: Box<[[MaybePos; KILLER_MOVE_SLOTS]; value::MAX_PLY_SLOTS]>,
+    debug_statics: Box<[DebugStatics; value::MAX_PLY_SLOTS]>,
+    lmr_table: Box<[[Depth; value::MAX_PLY_SLOTS]; 64]>,
+    endgame_stack: Box<[EndgameFrame; value::MAX_PLY_SLOTS]>,
+}
+
+impl ThreadBuffers {
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            search_objective: SearchObjective::Best,
+            ht: Box::new(HistoryTable::empty()),
+            ss: Box::new([SearchFrame::EMPTY; value::MAX_PLY_SLOTS]),
+            pvs: Box::new([PrincipalVariation::EMPTY; value::MAX_PLY_SLOTS]),
+            killers: Box::new([[MaybePos::NONE; KILLER_MOVE_SLOTS]; value::MAX_PLY_SLOTS]),
+            debug_statics: Box::new([DebugStatics::EMPTY; value::MAX_PLY_SLOTS]),
+            lmr_table: Box::new(build_lmr_table(config)),
+            endgame_stack: Box::new([EndgameFrame::EMPTY; value::MAX_PLY_SLOTS]),
+        }
+    }
+
+    // clears the leftovers of the previous search and loads the next one's setup
+    pub fn prepare(mut self, config: Config, search_objective: SearchObjective, ht: HistoryTable) -> Self {
+        *self.ht = ht;
+        self.ss.fill(SearchFrame::EMPTY);
+        self.pvs.fill(PrincipalVariation::EMPTY);
+        self.killers.fill([MaybePos::NONE; KILLER_MOVE_SLOTS]);
+        self.debug_statics.fill(DebugStatics::EMPTY);
+
+        if self.config.workers != config.workers {
+            *self.lmr_table = build_lmr_table(config);
+        }
+
+        self.config = config;
+        self.search_objective = search_objective;
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -75,6 +140,7 @@ pub struct ThreadData<'a, const R: RuleKind, TH: ThreadType, E: Evaluator<R>> {
 
     pub root_pv: PrincipalVariation,
     pub root_moves_in_1k: [u32; pos::BOARD_SIZE],
+    pub root_scores: [Score; pos::BOARD_SIZE],
     pub singular_root: bool,
 
     pub endgame_stack: Box<[EndgameFrame; value::MAX_PLY_SLOTS]>,
@@ -82,9 +148,13 @@ pub struct ThreadData<'a, const R: RuleKind, TH: ThreadType, E: Evaluator<R>> {
 
     pub batch_counter: BatchCounter<'a>,
     aborted: &'a AtomicBool,
+    abort_request: Option<&'a AtomicBool>,
+    stopped: bool,
+    telemetry: Option<&'a TelemetryBoard>,
 
     pub best_move: MaybePos,
     pub selective_depth: usize,
+    pub completed_depth: Depth,
 
     pub ply: usize,
 }
@@ -99,50 +169,162 @@ impl<'a, const R: RuleKind, TH: ThreadType, E: Evaluator<R>> ThreadData<'a, R, T
         ht: HistoryTable,
         aborted: &'a AtomicBool,
         global_counter_in_1k: &'a AtomicU32
+    ) -> Self {
+        Self::from_buffers(
+            thread_type, tid,
+            evaluator,
+            tt,
+            aborted,
+            global_counter_in_1k,
+            ThreadBuffers::new(config).prepare(config, search_objective, ht),
+        )
+    }
+
+    pub fn from_buffers(
+        thread_type: TH, tid: u32,
+        evaluator: E,
+        tt: TTView<'a>,
+        aborted: &'a AtomicBool,
+        global_counter_in_1k: &'a AtomicU32,
+        buffers: ThreadBuffers,
     ) -> Self {
         Self {
             thread_type,
-            search_objective,
+            search_objective: buffers.search_objective,
             tid,
-            config,
+            config: buffers.config,
             tt,
             evaluator,
-            ht: Box::new(ht),
-            ss: Box::new([SearchFrame::EMPTY; value::MAX_PLY_SLOTS]),
-            pvs: Box::new([PrincipalVariation::EMPTY; value::MAX_PLY_SLOTS]),
-            killers: Box::new([[MaybePos::NONE; 2]; value::MAX_PLY_SLOTS]),
-            lmr_table: Box::new(build_lmr_table(config)),
-            debug_statics: Box::new([DebugStatics::EMPTY; value::MAX_PLY_SLOTS]),
+            ht: buffers.ht,
+            ss: buffers.ss,
+            pvs: buffers.pvs,
+            killers: buffers.killers,
+            lmr_table: buffers.lmr_table,
+            debug_statics: buffers.debug_statics,
             root_pv: PrincipalVariation::EMPTY,
             root_moves_in_1k: [0; pos::BOARD_SIZE],
+            root_scores: [Score::NAN; pos::BOARD_SIZE],
             singular_root: false,
-            endgame_stack: Box::new([EndgameFrame::EMPTY; value::MAX_PLY_SLOTS]),
+            endgame_stack: buffers.endgame_stack,
             endgame_stack_top: 0,
             batch_counter: BatchCounter::new(global_counter_in_1k),
             aborted,
+            abort_request: None,
+            stopped: false,
+            telemetry: None,
             best_move: MaybePos::NONE,
             selective_depth: 0,
+            completed_depth: 0,
             ply: 0,
         }
     }
 
+    pub fn with_telemetry(mut self, telemetry: &'a TelemetryBoard) -> Self {
+        self.telemetry = Some(telemetry);
+        self
+    }
+
+    // a stop requested by the caller, kept apart from the flag the search raises once it is done
+    pub fn with_abort_request(mut self, abort_request: &'a AtomicBool) -> Self {
+        self.abort_request = Some(abort_request);
+        self
+    }
+
+    pub fn publish_telemetry(&self, depth: Depth) {
+        if let Some(telemetry) = self.telemetry {
+            telemetry.publish(self.tid, depth, &self.debug_statics[..]);
+        }
+    }
+
+    pub fn collect_telemetry(&self) -> Option<SearchTelemetry> {
+        self.telemetry.map(|telemetry| telemetry.collect(
+            self.batch_counter.count_in_1k(self.config.deterministic),
+            self.thread_type.time_manager().elapsed(),
+            self.tt.hash_full_permille() as u32,
+        ))
+    }
+
+    #[inline(always)]
+    pub fn record_node(&mut self) {
+        #[cfg(feature = "debug-statics")]
+        { self.debug_statics[self.ply].visited_nodes += 1; }
+    }
+
+    #[inline(always)]
+    pub fn record_endgame_node(&mut self) {
+        #[cfg(feature = "debug-statics")]
+        { self.debug_statics[self.ply].endgame_nodes += 1; }
+    }
+
+    #[inline(always)]
+    pub fn record_tt_hit(&mut self, cutoff: bool) {
+        #[cfg(feature = "debug-statics")]
+        {
+            self.debug_statics[self.ply].sum_tt_hit += 1;
+            self.debug_statics[self.ply].sum_tt_cutoff += cutoff as u64;
+        }
+        #[cfg(not(feature = "debug-statics"))]
+        let _ = cutoff;
+    }
+
+    #[inline(always)]
+    pub fn record_beta_cutoff(&mut self, moves_made: usize) {
+        #[cfg(feature = "debug-statics")]
+        {
+            self.debug_statics[self.ply].beta_cutoffs += 1;
+            self.debug_statics[self.ply].sum_cutoff_distance += moves_made as u64;
+        }
+        #[cfg(not(feature = "debug-statics"))]
+        let _ = moves_made;
+    }
+
+    pub fn into_buffers(self) -> ThreadBuffers {
+        ThreadBuffers {
+            config: self.config,
+            search_objective: self.search_objective,
+            ht: self.ht,
+            ss: self.ss,
+            pvs: self.pvs,
+            killers: self.killers,
+            debug_statics: self.debug_statics,
+            lmr_table: self.lmr_table,
+            endgame_stack: self.endgame_stack,
+        }
+    }
+
     pub fn should_check_limit(&self) -> bool {
-        self.batch_counter.buffer_zero()
+        (TH::IS_MAIN || self.config.independent_threads())
+            && self.batch_counter.buffer_zero()
     }
 
     pub fn search_limit_exceeded(&self) -> bool {
-        self.thread_type.time_manager().is_hard_limit_reached()
-            || self.config.max_nodes_in_1k.is_some_and(|in_1k|
-                self.batch_counter.count_global_in_1k() >= in_1k
-            )
+        (TH::IS_MAIN
+            && !self.config.independent_threads()
+            && self.thread_type.time_manager().is_hard_limit_reached()
+        ) || self.config.thread_nodes_in_1k().is_some_and(|in_1k|
+            self.batch_counter.count_in_1k(self.config.deterministic) >= in_1k
+        )
     }
 
-    pub fn set_aborted(&self) {
-        self.aborted.store(true, Ordering::Relaxed);
+    // independent threads stop on their own, a shared abort would cut the others short at a timing-dependent point
+    pub fn set_aborted(&mut self) {
+        if self.config.independent_threads() {
+            self.stopped = true;
+        } else {
+            self.aborted.store(true, Ordering::Relaxed);
+        }
     }
 
     pub fn is_aborted(&self) -> bool {
-        self.aborted.load(Ordering::Relaxed)
+        self.stopped
+            || self.aborted.load(Ordering::Relaxed)
+            || self.abort_request.is_some_and(|abort_request| abort_request.load(Ordering::Relaxed))
+    }
+
+    // balancing and skill sampling compare root moves with each other, so each needs an exact score
+    pub fn requires_exact_root_scores(&self) -> bool {
+        self.search_objective.balance_target().is_some()
+            || self.config.skill_level.is_some_and(|level| level < MAX_SKILL_LEVEL)
     }
 
     pub fn lookup_lmr_table(&self, depth_left: Depth, moves_made: usize) -> Depth {
@@ -1,3 +1,4 @@
+use crate::checkpoint::SearchCheckpoint;
 use crate::protocol::response::{Response, ResponseSender};
 use crate::time_manager::TimeManager;
 use crate::utils::monotonic_clock::MonotonicClock;
@@ -9,6 +10,8 @@ pub trait ThreadType {
 
     fn make_response(&self, response: Response);
 
+    fn make_checkpoint(&self, checkpoint: &SearchCheckpoint);
+
     fn time_manager(&self) -> &TimeManager<Self::Clock>;
 
     fn time_manager_mut(&mut self) -> &mut TimeManager<Self::Clock>;
@@ -40,6 +43,10 @@ impl<CLK: MonotonicClock, T: ResponseSender> ThreadType for MainThread<CLK, T> {
         self.response_sender.response(response);
     }
 
+    fn make_checkpoint(&self, checkpoint: &SearchCheckpoint) {
+        self.response_sender.checkpoint(checkpoint);
+    }
+
     fn time_manager(&self) -> &TimeManager<CLK> {
         &self.time_manager
     }
@@ -71,6 +78,10 @@ impl<CLK: MonotonicClock> ThreadType for WorkerThread<CLK> {
         unreachable!();
     }
 
+    fn make_checkpoint(&self, _checkpoint: &SearchCheckpoint) {
+        unreachable!();
+    }
+
     fn time_manager(&self) -> &TimeManager<CLK> {
         unreachable!()
     }
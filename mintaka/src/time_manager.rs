@@ -1,6 +1,6 @@
 use std::time::Duration;
 use rusty_renju::utils::empty::Empty;
-use crate::protocol::timer::Timer;
+use crate::protocol::timer::{TimeControl, Timer};
 use crate::utils::monotonic_clock::MonotonicClock;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -54,8 +54,14 @@ pub struct TimeManager<CLK: MonotonicClock> {
 
 impl<CLK: MonotonicClock> TimeManager<CLK> {
     pub fn init(timer: Timer, started_time: CLK) -> Self {
-        match (timer.total_remaining, timer.turn, timer.increment) {
-            (None, None, _) => {
+        // byo-yomi periods alone are still a budget to manage
+        let total_remaining = match timer.control {
+            TimeControl::ByoYomi { periods: 1 .., .. } => Some(timer.total_remaining.unwrap_or(Duration::ZERO)),
+            _ => timer.total_remaining,
+        };
+
+        match (total_remaining, timer.turn) {
+            (None, None) => {
                 Self {
                     started_time,
                     base_hard_limit: Duration::MAX,
@@ -63,7 +69,7 @@ impl<CLK: MonotonicClock> TimeManager<CLK> {
                     limit_kind: LimitKind::Infinite,
                 }
             },
-            (None, Some(turn), _) => {
+            (None, Some(turn)) => {
                 Self {
                     started_time,
                     base_hard_limit: turn,
@@ -71,10 +77,11 @@ impl<CLK: MonotonicClock> TimeManager<CLK> {
                     limit_kind: LimitKind::Static,
                 }
             },
-            (Some(total_remaining), turn, increment) => {
-                let turn = turn.unwrap_or(Duration::MAX);
+            (Some(total_remaining), turn) => {
+                let (allocation, available) = allocate_time(total_remaining, timer.increment, timer.control);
+                let turn = turn.unwrap_or(Duration::MAX).min(available);
 
-                let allocation = (total_remaining / 20 + increment / 2).min(turn);
+                let allocation = allocation.min(turn);
                 let soft_limit = multiply_clamp_duration(&allocation, 0.8, turn);
 
                 Self {
@@ -146,3 +153,29 @@ impl<CLK: MonotonicClock> TimeManager<CLK> {
 fn multiply_clamp_duration(duration: &Duration, factor: f64, max: Duration) -> Duration {
     Duration::from_millis((duration.as_millis() as f64 * factor) as u64).min(max)
 }
+
+// (allocation, maximum time usable without flagging)
+fn allocate_time(total_remaining: Duration, increment: Duration, control: TimeControl) -> (Duration, Duration) {
+    match control {
+        TimeControl::Fischer =>
+            (total_remaining / 20 + increment / 2, Duration::MAX),
+        TimeControl::ByoYomi { periods, period_time } => {
+            // keep a margin inside the period, it resets only if the move arrives in time
+            let period_budget = if periods > 0 { period_time - period_time / 10 } else { Duration::ZERO };
+
+            (total_remaining / 20 + period_budget, total_remaining + period_budget)
+        },
+        TimeControl::SimpleDelay { delay } =>
+            (total_remaining / 20 + delay, total_remaining + delay),
+        TimeControl::Bronstein { delay } =>
+            (total_remaining / 20 + delay / 2 + increment / 2, total_remaining),
+        TimeControl::MovesToGo { moves_to_go, .. } => {
+            let moves_to_go = moves_to_go.clamp(1, 20) + 1;
+
+            (total_remaining / moves_to_go + increment / 2, total_remaining)
+        },
+        // every second spent is handed to the opponent, so spend half as much
+        TimeControl::Hourglass =>
+            (total_remaining / 40, total_remaining),
+    }
+}
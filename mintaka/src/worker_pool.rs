@@ -0,0 +1,179 @@
+use crate::config::Config;
+use crate::thread_data::ThreadBuffers;
+use std::panic::AssertUnwindSafe;
+use std::sync::mpsc;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+
+type Job = Box<dyn FnOnce(ThreadBuffers) -> ThreadBuffers + Send + 'static>;
+
+struct PooledWorker {
+    jobs: mpsc::Sender<Job>,
+    thread: JoinHandle<()>,
+}
+
+impl PooledWorker {
+    fn spawn(index: usize, config: Config) -> Self {
+        let (jobs, job_receiver) = mpsc::channel::<Job>();
+
+        let thread = std::thread::Builder::new()
+            .name(format!("mintaka-worker-{index}"))
+            .spawn(move || {
+                let mut buffers = Some(ThreadBuffers::new(config));
+
+                // parks on the channel between searches, exits once the pool drops the sender
+                while let Ok(job) = job_receiver.recv() {
+                    let taken = buffers.take().unwrap_or_else(|| ThreadBuffers::new(config));
+
+                    buffers = std::panic::catch_unwind(AssertUnwindSafe(|| job(taken))).ok();
+                }
+            })
+            .expect("failed to spawn search worker");
+
+        Self { jobs, thread }
+    }
+}
+
+#[derive(Default)]
+struct PendingJobs {
+    pending: Mutex<(usize, bool)>,
+    finished: Condvar,
+}
+
+impl PendingJobs {
+    fn push(&self) {
+        self.pending.lock().unwrap().0 += 1;
+    }
+
+    fn complete(&self, panicked: bool) {
+        let mut pending = self.pending.lock().unwrap();
+        pending.0 -= 1;
+        pending.1 |= panicked;
+
+        if pending.0 == 0 {
+            self.finished.notify_all();
+        }
+    }
+
+    fn wait(&self) -> bool {
+        let mut pending = self.pending.lock().unwrap();
+
+        while pending.0 > 0 {
+            pending = self.finished.wait(pending).unwrap();
+        }
+
+        pending.1
+    }
+}
+
+struct CompletionGuard(Arc<PendingJobs>);
+
+impl Drop for CompletionGuard {
+    fn drop(&mut self) {
+        self.0.complete(std::thread::panicking());
+    }
+}
+
+pub struct PoolScope<'pool, 'env> {
+    pool: &'pool WorkerPool,
+    pending: Arc<PendingJobs>,
+    _env: std::marker::PhantomData<&'env mut &'env ()>,
+}
+
+impl<'env> PoolScope<'_, 'env> {
+    pub fn spawn<F>(&self, index: usize, job: F)
+    where F: FnOnce(ThreadBuffers) -> ThreadBuffers + Send + 'env {
+        let guard = CompletionGuard(self.pending.clone());
+
+        let job: Box<dyn FnOnce(ThreadBuffers) -> ThreadBuffers + Send + 'env> = Box::new(move |buffers| {
+            let _guard = guard;
+            job(buffers)
+        });
+
+        // SAFETY: WorkerPool::scope does not return before every spawned job has completed,
+        // so nothing borrowed for 'env is accessed after the scope ends
+        let job: Job = unsafe { std::mem::transmute(job) };
+
+        self.pending.push();
+
+        if let Err(mpsc::SendError(job)) = self.pool.workers[index].jobs.send(job) {
+            // completes the job accounting through the guard
+            drop(job);
+        }
+    }
+}
+
+struct ScopeGuard<'a>(&'a PendingJobs);
+
+impl Drop for ScopeGuard<'_> {
+    fn drop(&mut self) {
+        self.0.wait();
+    }
+}
+
+// persistent search workers that keep their thread data allocations between searches
+#[derive(Default)]
+pub struct WorkerPool {
+    workers: Vec<PooledWorker>,
+}
+
+impl WorkerPool {
+    pub fn len(&self) -> usize {
+        self.workers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.workers.is_empty()
+    }
+
+    pub fn resize(&mut self, size: usize, config: Config) {
+        if size < self.workers.len() {
+            shutdown_workers(self.workers.split_off(size));
+        }
+
+        while self.workers.len() < size {
+            self.workers.push(PooledWorker::spawn(self.workers.len(), config));
+        }
+    }
+
+    pub fn clear(&mut self) {
+        shutdown_workers(std::mem::take(&mut self.workers));
+    }
+
+    // runs jobs borrowing from the caller's stack on the pooled workers, blocking until all of them finish
+    pub fn scope<'env, T>(&self, body: impl FnOnce(&PoolScope<'_, 'env>) -> T) -> T {
+        let scope = PoolScope {
+            pool: self,
+            pending: Arc::new(PendingJobs::default()),
+            _env: std::marker::PhantomData,
+        };
+
+        let result = {
+            let _guard = ScopeGuard(&scope.pending);
+            body(&scope)
+        };
+
+        if scope.pending.wait() {
+            panic!("a pooled search worker panicked");
+        }
+
+        result
+    }
+}
+
+impl Drop for WorkerPool {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+fn shutdown_workers(workers: Vec<PooledWorker>) {
+    // dropping the senders first lets every worker leave its loop before joining
+    let threads = workers.into_iter()
+        .map(|worker| worker.thread)
+        .collect::<Vec<_>>();
+
+    for thread in threads {
+        let _ = thread.join();
+    }
+}
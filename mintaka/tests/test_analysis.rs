@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod test_analysis {
+    use mintaka::analysis::MoveLabel;
+    use mintaka::config::Config;
+    use mintaka::game_agent::{GameAgent, GameError};
+    use rusty_renju::history::History;
+    use rusty_renju::notation::color::Color;
+    use rusty_renju::notation::rule::RuleKind;
+    use rusty_renju::notation::score::{Score, Scores};
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    #[test]
+    fn analyze_game() {
+        let history = History::from_str("h8,h9,i8,a1,j8,a2,k8,a3,g8").unwrap();
+        let progress = AtomicUsize::new(0);
+
+        let analysis = GameAgent::<{ RuleKind::Renju }>::analyze_game::<Instant>(
+            Config::default(),
+            &history,
+            20,
+            None,
+            |analysis_progress| progress.store(analysis_progress.analyzed_plies(), Ordering::Relaxed),
+            Arc::new(AtomicBool::new(false)),
+        ).unwrap();
+
+        assert!(analysis.completed);
+        assert_eq!(analysis.moves.len(), history.len());
+        assert_eq!(progress.load(Ordering::Relaxed), history.len() + 1);
+
+        let careless = &analysis.moves[5];
+        assert_eq!(careless.color, Color::White);
+        assert!(!careless.labels.contains(&MoveLabel::Best), "{careless:?}");
+        assert!(careless.labels.iter().any(|label|
+            matches!(label, MoveLabel::Inaccuracy | MoveLabel::Mistake | MoveLabel::Blunder)
+        ), "{careless:?}");
+        assert!(careless.win_rate_delta < 0.0);
+
+        assert!(analysis.moves[7].labels.contains(&MoveLabel::Forced), "{:?}", analysis.moves[7]);
+
+        let winning = &analysis.moves[8];
+        assert_eq!(winning.score_after, Score::WIN);
+        assert!(!winning.labels.contains(&MoveLabel::MissedVcf));
+    }
+
+    fn missed_vcf_plies(history: &str) -> Vec<u32> {
+        let analysis = GameAgent::<{ RuleKind::Renju }>::analyze_game::<Instant>(
+            Config::default(),
+            &History::from_str(history).unwrap(),
+            20,
+            None,
+            |_| {},
+            Arc::new(AtomicBool::new(false)),
+        ).unwrap();
+
+        analysis.moves.iter()
+            .filter(|analysis| analysis.labels.contains(&MoveLabel::MissedVcf))
+            .map(|analysis| analysis.ply)
+            .collect()
+    }
+
+    #[test]
+    fn missed_vcf() {
+        // k8 is a four-three for black, o15 passes it up
+        assert_eq!(missed_vcf_plies("h8,g8,i8,a1,j8,c1,k9,e1,k10,a15,o15,l8"), vec![11]);
+
+        // the open three wins without a single four, so losing it is not a missed vcf
+        assert_eq!(missed_vcf_plies("h8,a1,i8,c1,j8,e1,a15,g8"), vec![]);
+    }
+
+    #[test]
+    fn resumed_analysis() {
+        let history = History::from_str("h8,h9,i8,a1,j8,a2,k8,a3,g8").unwrap();
+        let snapshots = Mutex::new(vec![]);
+        let aborted = Arc::new(AtomicBool::new(false));
+
+        let interrupted = GameAgent::<{ RuleKind::Renju }>::analyze_game::<Instant>(
+            Config::default(),
+            &history,
+            20,
+            None,
+            |analysis_progress| {
+                if analysis_progress.analyzed_plies() >= 4 {
+                    aborted.store(true, Ordering::Relaxed);
+                }
+
+                snapshots.lock().unwrap().push(analysis_progress.clone());
+            },
+            aborted.clone(),
+        ).unwrap();
+
+        assert!(!interrupted.completed);
+
+        let snapshots = snapshots.into_inner().unwrap();
+        assert!(snapshots.iter().any(|snapshot| snapshot.checkpoint.is_some()));
+
+        let last = snapshots.last().unwrap().clone();
+        assert_eq!(last.total_plies(), history.len() + 1);
+
+        let resumed_from = AtomicUsize::new(usize::MAX);
+
+        let resumed = GameAgent::<{ RuleKind::Renju }>::analyze_game::<Instant>(
+            Config::default(),
+            &history,
+            20,
+            Some(last.clone()),
+            |analysis_progress| {
+                resumed_from.fetch_min(analysis_progress.analyzed_plies(), Ordering::Relaxed);
+            },
+            Arc::new(AtomicBool::new(false)),
+        ).unwrap();
+
+        assert!(resumed.completed);
+        assert_eq!(resumed.moves.len(), history.len());
+        assert_eq!(resumed_from.load(Ordering::Relaxed), last.analyzed_plies());
+    }
+
+    #[test]
+    fn stopped_before_start() {
+        let history = History::from_str("h8,h9,i8,a1,j8").unwrap();
+
+        // a job deleted before its worker picked it up must not run
+        let analysis = GameAgent::<{ RuleKind::Renju }>::analyze_game::<Instant>(
+            Config::default(), &history, 20, None, |_| {}, Arc::new(AtomicBool::new(true))
+        ).unwrap();
+
+        assert!(!analysis.completed);
+        assert!(analysis.moves.is_empty());
+    }
+
+    #[test]
+    fn illegal_games() {
+        // h8 is a double-three for black
+        let forbidden = History::from_str("i8,a1,j8,a2,h9,a3,h10,a4,h8").unwrap();
+
+        assert!(matches!(
+            GameAgent::<{ RuleKind::Renju }>::analyze_game::<Instant>(
+                Config::default(), &forbidden, 20, None, |_| {}, Arc::new(AtomicBool::new(false))
+            ),
+            Err(GameError::ForbiddenMove)
+        ));
+    }
+}
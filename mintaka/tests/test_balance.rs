@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod test_balance {
+    use mintaka::balance::Swap2Decision;
+    use mintaka::config::{Config, SearchObjective};
+    use mintaka::game_agent::GameAgent;
+    use mintaka::game_state::GameState;
+    use mintaka::protocol::response::NullResponseSender;
+    use mintaka::protocol::timer::Timer;
+    use rusty_renju::history::History;
+    use rusty_renju::notation::pos;
+    use rusty_renju::notation::pos::pos_unchecked;
+    use rusty_renju::notation::rule::RuleKind;
+    use rusty_renju::notation::score::{Score, Scores};
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    fn config() -> Config {
+        Config {
+            max_nodes_in_1k: Some(3),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn ranked_moves() {
+        let state: GameState<{ RuleKind::Renju }> = History::from_str("h8,h9,i8,a1,j8").unwrap().into();
+
+        let mut agent = GameAgent::from_state(config(), state);
+
+        let move_scores = agent.rank_moves::<Instant>(config(), 3, Arc::new(AtomicBool::new(false)));
+
+        assert_eq!(move_scores.len(), 3);
+        assert!(move_scores.windows(2).all(|pair| pair[0].score >= pair[1].score));
+
+        // the open three has to be blocked
+        assert!([pos_unchecked("g8"), pos_unchecked("k8")].contains(&move_scores[0].pos));
+
+        assert_eq!(agent.state.board.hash_key, state.board.hash_key);
+    }
+
+    #[test]
+    fn winning_move() {
+        let state: GameState<{ RuleKind::Renju }> = History::from_str("h8,h9,i8,i9,j8,j9,k8,k9").unwrap().into();
+
+        let mut agent = GameAgent::from_state(config(), state);
+
+        let move_scores = agent.rank_moves::<Instant>(config(), 1, Arc::new(AtomicBool::new(false)));
+
+        assert_eq!(move_scores[0].score, Score::win_in(1));
+    }
+
+    #[test]
+    fn balanced_moves() {
+        let state: GameState<{ RuleKind::Renju }> = History::from_str("h8,h9").unwrap().into();
+
+        let mut agent = GameAgent::from_state(config(), state);
+
+        let one = agent.balance_one::<Instant>(config(), 0, Arc::new(AtomicBool::new(false))).unwrap();
+
+        assert!(state.board.is_legal_move(one.pos));
+        assert!(!Score::is_mate(one.score));
+
+        let [first, second] = agent.balance_two::<Instant>(config(), 0, Arc::new(AtomicBool::new(false))).unwrap();
+
+        assert_ne!(first.pos, second.pos);
+        assert_eq!(first.score, -second.score);
+        assert!(state.board.is_legal_move(first.pos) && state.board.is_legal_move(second.pos));
+
+        assert_eq!(agent.state.board.hash_key, state.board.hash_key);
+    }
+
+    #[test]
+    fn aborted_balance() {
+        let state: GameState<{ RuleKind::Renju }> = History::from_str("h8,h9").unwrap().into();
+
+        // large enough that only the stop request can end it in time
+        let config = Config {
+            max_nodes_in_1k: Some(1_000_000),
+            ..Config::default()
+        };
+
+        let mut agent = GameAgent::from_state(config, state);
+        let aborted = Arc::new(AtomicBool::new(false));
+
+        let stopper = {
+            let aborted = aborted.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(std::time::Duration::from_millis(200));
+                aborted.store(true, Ordering::Relaxed);
+            })
+        };
+
+        let started = Instant::now();
+
+        assert!(agent.balance_one::<Instant>(config, 0, aborted).is_none());
+        assert!(started.elapsed() < std::time::Duration::from_secs(10));
+        assert_eq!(agent.state.board.hash_key, state.board.hash_key);
+
+        stopper.join().unwrap();
+    }
+
+    #[test]
+    fn balance_objective() {
+        // every move but a block loses to the open three, so the closest to even is a block
+        let state: GameState<{ RuleKind::Renju }> = History::from_str("h8,a1,i8,o15,j8").unwrap().into();
+
+        let best_move = GameAgent::from_state(config(), state).launch::<Instant>(
+            config(),
+            Timer::INFINITE,
+            SearchObjective::Balance { target: 0 },
+            NullResponseSender,
+            Arc::new(AtomicU32::new(0)),
+            Arc::new(AtomicBool::new(false)),
+        );
+
+        let blocks = ["f8", "g8", "k8", "l8"].map(pos_unchecked);
+
+        assert!(blocks.contains(&best_move.best_move.unwrap()));
+        assert!(!Score::is_mate(best_move.score));
+    }
+
+    #[test]
+    fn swap2_opening() {
+        let mut agent = GameAgent::<{ RuleKind::Gomoku }>::new(config());
+
+        let Some(Swap2Decision::Place(stones)) = agent.swap2_decision::<Instant>(config(), Arc::new(AtomicBool::new(false))) else {
+            panic!("expected the opening stones");
+        };
+
+        assert_eq!(stones[0], pos::CENTER);
+        assert!(stones[1] != stones[0] && stones[2] != stones[0] && stones[1] != stones[2]);
+
+        let state: GameState<{ RuleKind::Gomoku }> = History::from_str("h8,h9,i8,i9,j8,a1").unwrap().into();
+        assert!(GameAgent::from_state(config(), state).swap2_decision::<Instant>(config(), Arc::new(AtomicBool::new(false))).is_none());
+    }
+
+    #[test]
+    fn swap2_losing_side() {
+        // black already has an open three, so taking black is better than answering it
+        let state: GameState<{ RuleKind::Gomoku }> = History::from_str("h8,a1,i8,o15,j8").unwrap().into();
+
+        let decision = GameAgent::from_state(config(), state)
+            .swap2_decision::<Instant>(config(), Arc::new(AtomicBool::new(false)))
+            .unwrap();
+
+        assert_eq!(decision, Swap2Decision::Swap);
+    }
+}
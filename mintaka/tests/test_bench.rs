@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod test_bench {
+    use mintaka::bench;
+    use rusty_renju::notation::rule::RuleKind;
+    use std::time::Instant;
+
+    #[test]
+    fn reproducible_signature() {
+        let report = bench::bench::<Instant>(4, |_| {});
+
+        for rule in [RuleKind::Renju, RuleKind::Gomoku, RuleKind::Freestyle] {
+            assert!(report.results.iter().any(|result| result.rule == rule));
+        }
+
+        assert!(report.results.iter().all(|result| result.nodes > 0 && result.best_move.is_some()));
+
+        let again = bench::bench::<Instant>(4, |_| {});
+
+        // a changed signature means the search itself changed, update it along with that change
+        assert_eq!(report.signature(), 29191);
+        assert_eq!(report.signature(), again.signature());
+        assert_eq!(
+            report.results.iter().map(|result| result.best_move).collect::<Vec<_>>(),
+            again.results.iter().map(|result| result.best_move).collect::<Vec<_>>(),
+        );
+    }
+}
@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod test_checkpoint {
+    use mintaka::checkpoint::SearchCheckpoint;
+    use mintaka::config::{Config, SearchObjective};
+    use mintaka::game_agent::{GameAgent, GameAgentData};
+    use mintaka::game_state::GameState;
+    use mintaka::protocol::response::{NullResponseSender, Response, ResponseSender};
+    use mintaka::protocol::results::BestMove;
+    use rusty_renju::history::History;
+    use rusty_renju::notation::rule::RuleKind;
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicBool, AtomicU32};
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    fn launch(agent: &mut GameAgent<{ RuleKind::Renju }>, config: Config, objective: SearchObjective) -> BestMove {
+        agent.launch::<Instant>(
+            config,
+            config.initial_timer,
+            objective,
+            NullResponseSender,
+            Arc::new(AtomicU32::new(0)),
+            Arc::new(AtomicBool::new(false))
+        )
+    }
+
+    #[test]
+    fn resumed_search() {
+        let state: GameState<{ RuleKind::Renju }> = History::from_str("h8,h9,i8,g8").unwrap().into();
+
+        let config = Config {
+            max_depth: Some(4),
+            ..Config::default()
+        };
+
+        let mut agent = GameAgent::from_state(config, state);
+
+        let first = launch(&mut agent, config, SearchObjective::Best);
+        let checkpoint = agent.checkpoint().cloned().unwrap();
+
+        assert_eq!(checkpoint.depth, 4);
+        assert_eq!(checkpoint.best_move, first.best_move);
+
+        // every depth is already completed, so nothing is searched again
+        let repeated = launch(&mut agent, config, SearchObjective::Best);
+
+        assert_eq!(repeated.best_move, first.best_move);
+        assert_eq!(repeated.total_nodes_in_1k, 0);
+
+        let mut restored: GameAgent<{ RuleKind::Renju }> = GameAgentData::from(&agent).try_into().unwrap();
+        assert_eq!(restored.checkpoint(), Some(&checkpoint));
+
+        launch(&mut restored, Config { max_depth: Some(6), ..config }, SearchObjective::Best);
+
+        let resumed = restored.checkpoint().unwrap();
+
+        assert_eq!(resumed.depth, 6);
+        assert!(resumed.total_nodes_in_1k >= checkpoint.total_nodes_in_1k);
+        assert!(resumed.time_elapsed >= checkpoint.time_elapsed);
+    }
+
+    #[test]
+    fn other_position() {
+        let state: GameState<{ RuleKind::Renju }> = History::from_str("h8,h9,i8").unwrap().into();
+
+        let config = Config {
+            max_depth: Some(3),
+            ..Config::default()
+        };
+
+        let mut agent = GameAgent::from_state(config, state);
+
+        launch(&mut agent, config, SearchObjective::Best);
+        assert!(agent.checkpoint().is_some());
+
+        let mut other = agent.state;
+        other.play_mut(other.board.legal_field(other.board.player_color).iter_hot_pos().next().unwrap());
+
+        let mut other_agent = GameAgent::from_state(config, other);
+        other_agent.restore_checkpoint(agent.checkpoint().cloned());
+
+        launch(&mut other_agent, config, SearchObjective::Best);
+
+        assert_eq!(other_agent.checkpoint().unwrap().position_hash, other.board.hash_key);
+    }
+
+    struct CheckpointCollector(Arc<Mutex<Vec<SearchCheckpoint>>>);
+
+    impl ResponseSender for CheckpointCollector {
+        fn response(&self, _response: Response) {}
+
+        fn checkpoint(&self, checkpoint: &SearchCheckpoint) {
+            self.0.lock().unwrap().push(checkpoint.clone());
+        }
+    }
+
+    #[test]
+    fn iteration_checkpoints() {
+        let state: GameState<{ RuleKind::Renju }> = History::from_str("h8,h9,i8,g8").unwrap().into();
+
+        let config = Config {
+            max_depth: Some(4),
+            ..Config::default()
+        };
+
+        let mut agent = GameAgent::from_state(config, state);
+
+        let checkpoints = Arc::new(Mutex::new(vec![]));
+
+        agent.launch::<Instant>(
+            config,
+            config.initial_timer,
+            SearchObjective::Best,
+            CheckpointCollector(checkpoints.clone()),
+            Arc::new(AtomicU32::new(0)),
+            Arc::new(AtomicBool::new(false))
+        );
+
+        let checkpoints = checkpoints.lock().unwrap();
+
+        assert_eq!(checkpoints.iter().map(|checkpoint| checkpoint.depth).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        let last = checkpoints.last().unwrap();
+        let stored = agent.checkpoint().unwrap();
+
+        assert_eq!((last.depth, last.best_move, last.score), (stored.depth, stored.best_move, stored.score));
+    }
+}
@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod test_deterministic {
+    use mintaka::config::{Config, SearchObjective};
+    use mintaka::game_agent::GameAgent;
+    use mintaka::game_state::GameState;
+    use mintaka::protocol::response::NullResponseSender;
+    use mintaka::protocol::results::BestMove;
+    use mintaka::protocol::timer::Timer;
+    use rusty_renju::history::History;
+    use rusty_renju::notation::rule::RuleKind;
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicBool, AtomicU32};
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    fn launch(agent: &mut GameAgent<{ RuleKind::Renju }>, config: Config) -> BestMove {
+        agent.launch::<Instant>(
+            config,
+            Timer::INFINITE,
+            SearchObjective::Best,
+            NullResponseSender,
+            Arc::new(AtomicU32::new(0)),
+            Arc::new(AtomicBool::new(false))
+        )
+    }
+
+    fn fingerprint(best_move: BestMove) -> String {
+        format!(
+            "{} {} {} {} {:?}",
+            best_move.best_move, best_move.score, best_move.selective_depth, best_move.total_nodes_in_1k, best_move.pv
+        )
+    }
+
+    #[test]
+    fn reproducible_search() {
+        let state: GameState<{ RuleKind::Renju }> = History::from_str("h8,h9,i8,g8,i10,i9").unwrap().into();
+
+        for workers in [1, 4] {
+            let config = Config {
+                max_nodes_in_1k: Some(80),
+                workers,
+                deterministic: true,
+                ..Config::default()
+            };
+
+            let mut agent = GameAgent::from_state(config, state);
+
+            let first = launch(&mut agent, config);
+
+            // every thread spends its own share of the node limit
+            assert!(first.total_nodes_in_1k + workers >= 80, "{}", first.total_nodes_in_1k);
+
+            let first = fingerprint(first);
+            let second = fingerprint(launch(&mut agent, config));
+            let fresh = fingerprint(launch(&mut GameAgent::from_state(config, state), config));
+
+            assert_eq!(first, second);
+            assert_eq!(first, fresh);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+#[cfg(test)]
+mod test_proof_tree {
+    use indoc::indoc;
+    use mintaka::config::{Config, SearchObjective};
+    use mintaka::game_agent::GameAgent;
+    use mintaka::game_state::GameState;
+    use mintaka::proof_tree::{ProofError, ProofNode};
+    use mintaka::protocol::response::NullResponseSender;
+    use rusty_renju::board;
+    use rusty_renju::history::History;
+    use rusty_renju::notation::pos;
+    use rusty_renju::notation::rule::RuleKind;
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    fn verify(state: &GameState<{ RuleKind::Renju }>, node: &ProofNode) {
+        let attacker = state.board.player_color;
+
+        assert!(state.board.is_legal_move(node.attack));
+
+        let mut state = *state;
+        state.play_mut(node.attack);
+
+        if state.board.find_winner(node.attack).is_some() {
+            assert!(node.defenses.is_empty());
+            return;
+        }
+
+        let has_four = state.board.patterns.five_pos[attacker].is_some();
+
+        let expected = state.board.legal_field(!attacker)
+            .iter_hot_idx()
+            .filter(|&idx| idx < pos::BOARD_SIZE
+                && (!has_four || state.board.patterns.field[attacker][idx].has_five())
+            )
+            .count();
+
+        assert_eq!(node.defenses.len(), expected);
+
+        for defense in node.defenses.iter() {
+            let mut next = state;
+            next.play_mut(defense.defense);
+
+            verify(&next, &defense.answer);
+        }
+    }
+
+    #[test]
+    fn vcf_proof() {
+        let state: GameState<{ RuleKind::Renju }> = board!(indoc! {"
+           A B C D E F G H I J K L M N O
+        15 . . . . . . . . . . . . . . . 15
+        14 . . . . . . . . . . . . . . . 14
+        13 . . . . . . . . . . . . . . . 13
+        12 . . . . . . . . . . . . . . . 12
+        11 . . . . . . . . . . O . . . . 11
+        10 . . . . . . . . . X . X . . . 10
+         9 . . . . . . . . . O . . . . . 9
+         8 . . . . . . . X . X X O . . . 8
+         7 . . . . . . X . X O . . . . . 7
+         6 . . . . . . . O O . . . . . . 6
+         5 . . . . . . O . . . . . . . . 5
+         4 . . . . . . . . . . . . . . . 4
+         3 . . . . . . . . . . . . . . . 3
+         2 . . . . . . . . . . . . . . . 2
+         1 . . . . . . . . . . . . . . . 1
+           A B C D E F G H I J K L M N O"}).into();
+
+        let config = Config::default();
+        let mut agent = GameAgent::from_state(config, state);
+
+        let tree = agent.prove_win::<Instant>(config, 1_000_000, Arc::new(AtomicBool::new(false))).unwrap();
+
+        verify(&state, &tree.root);
+
+        assert!(tree.depth() > 1);
+        assert_eq!(agent.state.board.hash_key, state.board.hash_key);
+
+        let sgf = tree.to_sgf();
+
+        assert!(sgf.starts_with("(;FF[4]GM[4]SZ[15]AB["));
+        assert!(sgf.ends_with(")"));
+    }
+
+    #[test]
+    fn open_four_proof() {
+        let state: GameState<{ RuleKind::Renju }> = board!(indoc! {"
+           A B C D E F G H I J K L M N O
+        15 X . . . . . . . . . . . . . X 15
+        14 . . . . . . . . . . . . . . . 14
+        13 . . . . . . . . . . . . . . . 13
+        12 . . . . . . . . . . . . . . . 12
+        11 . . . . . . . . . . . . . . . 11
+        10 . . . . . . . . . . . . . . . 10
+         9 . . . . . . . . . . . . . . . 9
+         8 . . . . . . O O O . . . . . . 8
+         7 . . . . . . . . . . . . . . . 7
+         6 . . . . . . . . . . . . . . . 6
+         5 . . . . . . . . . . . . . . . 5
+         4 . . . . . . . . . . . . . . . 4
+         3 . . . . . . . . . . . . . . . 3
+         2 . . . . . . . . . . . . . . . 2
+         1 X . . . . . . . . . . . . . X 1
+           A B C D E F G H I J K L M N O"}).into();
+
+        let config = Config::default();
+        let mut agent = GameAgent::from_state(config, state);
+
+        let tree = agent.prove_win::<Instant>(config, 1_000_000, Arc::new(AtomicBool::new(false))).unwrap();
+
+        verify(&state, &tree.root);
+
+        assert_eq!(tree.root.defenses.len(), 2);
+
+        assert_eq!(
+            agent.prove_win::<Instant>(config, 2, Arc::new(AtomicBool::new(false))).unwrap_err(),
+            ProofError::TooLarge
+        );
+    }
+
+    #[test]
+    fn not_winning() {
+        let state: GameState<{ RuleKind::Renju }> = History::from_str("h8,h9").unwrap().into();
+
+        let config = Config {
+            max_nodes_in_1k: Some(20),
+            ..Config::default()
+        };
+
+        let mut agent = GameAgent::from_state(config, state);
+
+        agent.launch::<Instant>(
+            config,
+            config.initial_timer,
+            SearchObjective::Best,
+            NullResponseSender,
+            Arc::new(AtomicU32::new(0)),
+            Arc::new(AtomicBool::new(false)),
+        );
+
+        let checkpoint = agent.checkpoint().cloned();
+        assert!(checkpoint.is_some());
+
+        assert_eq!(agent.prove_win::<Instant>(config, 1_000_000, Arc::new(AtomicBool::new(false))).unwrap_err(), ProofError::NotWinning);
+
+        // the root search can still be resumed after the proof searched its sub-positions
+        assert_eq!(agent.checkpoint().cloned(), checkpoint);
+        assert_eq!(agent.state.board.hash_key, state.board.hash_key);
+    }
+
+    #[test]
+    fn aborted_proof() {
+        let state: GameState<{ RuleKind::Renju }> = History::from_str("h8,h9").unwrap().into();
+
+        // large enough that only the stop request can end it in time
+        let config = Config {
+            max_nodes_in_1k: Some(1_000_000),
+            ..Config::default()
+        };
+
+        let mut agent = GameAgent::from_state(config, state);
+        let aborted = Arc::new(AtomicBool::new(false));
+
+        let stopper = {
+            let aborted = aborted.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(std::time::Duration::from_millis(200));
+                aborted.store(true, Ordering::Relaxed);
+            })
+        };
+
+        let started = Instant::now();
+
+        assert_eq!(agent.prove_win::<Instant>(config, usize::MAX, aborted).unwrap_err(), ProofError::Aborted);
+        assert!(started.elapsed() < std::time::Duration::from_secs(10));
+
+        stopper.join().unwrap();
+    }
+}
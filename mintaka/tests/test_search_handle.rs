@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod test_search_handle {
+    use mintaka::config::{Config, SearchObjective};
+    use mintaka::game_agent::GameAgent;
+    use mintaka::game_state::GameState;
+    use mintaka::protocol::response::Response;
+    use mintaka::protocol::timer::Timer;
+    use rusty_renju::history::History;
+    use rusty_renju::notation::rule::RuleKind;
+    use std::str::FromStr;
+    use std::time::Instant;
+
+    fn game_agent(config: Config) -> GameAgent<{ RuleKind::Renju }> {
+        let history = History::from_str("h8,h9,i8").unwrap();
+
+        GameAgent::from_state(config, GameState::from(history))
+    }
+
+    #[test]
+    fn blocking_search() {
+        let config = Config {
+            max_nodes_in_1k: Some(50),
+            ..Config::default()
+        };
+
+        let handle = game_agent(config).spawn_search::<Instant>(config, Timer::INFINITE, SearchObjective::Best);
+
+        let responses = std::iter::from_fn(|| handle.recv_response()).collect::<Vec<_>>();
+
+        assert!(matches!(responses.first(), Some(Response::Begins(_))));
+
+        let outcome = handle.join();
+
+        assert!(outcome.best_move.best_move.is_some());
+        assert_eq!(outcome.best_move.position_hash, outcome.game_agent.state.board.hash_key);
+    }
+
+    #[test]
+    fn aborted_search() {
+        let config = Config::default();
+        let hash_key = game_agent(config).state.board.hash_key;
+
+        let mut handle = game_agent(config).spawn_search::<Instant>(config, Timer::INFINITE, SearchObjective::Best);
+        handle.abort();
+
+        let outcome = loop {
+            match handle.try_join() {
+                Ok(outcome) => break outcome,
+                Err(pending) => handle = pending,
+            }
+
+            std::thread::yield_now();
+        };
+
+        assert_eq!(outcome.game_agent.state.board.hash_key, hash_key);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn polled_search() {
+        use futures_core::Stream;
+        use std::future::Future;
+        use std::pin::Pin;
+        use std::task::{Context, Poll, Waker};
+
+        let config = Config {
+            max_nodes_in_1k: Some(50),
+            ..Config::default()
+        };
+
+        let mut handle = game_agent(config).spawn_search::<Instant>(config, Timer::INFINITE, SearchObjective::Best);
+        let mut cx = Context::from_waker(Waker::noop());
+
+        let mut responses = 0;
+        loop {
+            match Pin::new(&mut handle).poll_next(&mut cx) {
+                Poll::Ready(Some(_)) => responses += 1,
+                Poll::Ready(None) => break,
+                Poll::Pending => std::thread::yield_now(),
+            }
+        }
+
+        let outcome = loop {
+            if let Poll::Ready(outcome) = Pin::new(&mut handle).poll(&mut cx) {
+                break outcome;
+            }
+        };
+
+        assert!(responses > 0);
+        assert!(outcome.best_move.best_move.is_some());
+    }
+}
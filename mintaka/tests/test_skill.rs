@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod test_skill {
+    use indoc::indoc;
+    use mintaka::config::{Config, SearchObjective};
+    use mintaka::game_agent::GameAgent;
+    use mintaka::game_state::GameState;
+    use mintaka::protocol::response::NullResponseSender;
+    use mintaka::skill::{Skill, MAX_SKILL_LEVEL};
+    use rusty_renju::board;
+    use rusty_renju::history::History;
+    use rusty_renju::notation::pos;
+    use rusty_renju::notation::pos::{pos_unchecked, MaybePos, Pos};
+    use rusty_renju::notation::rule::RuleKind;
+    use rusty_renju::notation::score::{Score, Scores};
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicBool, AtomicU32};
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    fn root_scores(scores: &[(&str, Score)]) -> [Score; pos::BOARD_SIZE] {
+        let mut root_scores = [Score::NAN; pos::BOARD_SIZE];
+
+        for &(pos, score) in scores {
+            root_scores[Pos::from_str(pos).unwrap().idx_usize()] = score;
+        }
+
+        root_scores
+    }
+
+    #[test]
+    fn skill_limits() {
+        assert_eq!(Skill::from_elo(0).level(), 0);
+        assert_eq!(Skill::from_elo(5000).level(), MAX_SKILL_LEVEL);
+        assert_eq!(Skill::new(100).level(), MAX_SKILL_LEVEL);
+
+        let config = Config::default();
+
+        assert_eq!(Skill::new(MAX_SKILL_LEVEL).limit_config(config), config);
+
+        let weak = Skill::new(0).limit_config(config);
+        let strong = Skill::new(10).limit_config(config);
+
+        assert!(weak.max_nodes_in_1k < strong.max_nodes_in_1k);
+        assert!(weak.max_depth < strong.max_depth);
+        assert!(weak.max_vcf_depth < strong.max_vcf_depth);
+    }
+
+    #[test]
+    fn never_picks_absurd_moves() {
+        let best_move = MaybePos::from(Pos::from_str("h8").unwrap());
+
+        let scores = root_scores(&[
+            ("h8", 120), ("h9", 100), ("i9", 90),
+            ("a1", -1500), ("o15", Score::lose_in(3)),
+        ]);
+
+        for seed in 0 .. 256 {
+            let (picked, _) = Skill::new(0).pick(&scores, best_move, 120, seed);
+
+            assert!(["h8", "h9", "i9"].contains(&picked.unwrap().to_string().as_str()), "{picked}");
+        }
+
+        let winning = root_scores(&[("h8", Score::win_in(3)), ("h9", 100)]);
+
+        for seed in 0 .. 256 {
+            assert_eq!(Skill::new(0).pick(&winning, best_move, Score::win_in(3), seed).0, best_move);
+        }
+
+        let deep_win = root_scores(&[("h8", Score::win_in(41)), ("h9", 100)]);
+
+        assert!((0 .. 256).any(|seed| Skill::new(0).pick(&deep_win, best_move, Score::win_in(41), seed).0 != best_move));
+        assert!((0 .. 256).all(|seed| Skill::new(MAX_SKILL_LEVEL).pick(&deep_win, best_move, Score::win_in(41), seed).0 == best_move));
+    }
+
+    #[test]
+    fn weakened_search() {
+        let state: GameState<{ RuleKind::Renju }> = board!(indoc! {"
+           A B C D E F G H I J K L M N O
+        15 . . . . . . . . . . . . . . . 15
+        14 . . . . . . . . . . . . . . . 14
+        13 . . . . . . . . . . . . . . . 13
+        12 . . . . . . . . . . . . . . . 12
+        11 . . . . . . . . . . . . . . . 11
+        10 . . . . . . . . . . . . . . . 10
+         9 . . . . . . . O . . . . . . . 9
+         8 . . . . . . . X X . . . . . . 8
+         7 . . . . . . . . . . . . . . . 7
+         6 . . . . . . . . . . . . . . . 6
+         5 . . . . . . . . . . . . . . . 5
+         4 . . . . . . . . . . . . . . . 4
+         3 . . . . . . . . . . . . . . . 3
+         2 . . . . . . . . . . . . . . . 2
+         1 . . . . . . . . . . . . . . . 1
+           A B C D E F G H I J K L M N O
+       "}).into();
+
+        let config = Config {
+            skill_level: Some(2),
+            ..Config::default()
+        };
+
+        let mut agent = GameAgent::from_state(config, state);
+
+        let best_move = agent.launch::<Instant>(
+            config,
+            config.initial_timer,
+            SearchObjective::Best,
+            NullResponseSender,
+            Arc::new(AtomicU32::new(0)),
+            Arc::new(AtomicBool::new(false))
+        );
+
+        assert!(best_move.best_move.is_some());
+        assert!(best_move.total_nodes_in_1k <= Skill::new(2).limit_config(config).max_nodes_in_1k.unwrap() + 1);
+    }
+
+    #[test]
+    fn weakened_search_blocks_open_three() {
+        // every move but a block loses, so sampling has to stay among the blocks
+        let state: GameState<{ RuleKind::Renju }> = History::from_str("h8,a1,i8,o15,j8").unwrap().into();
+
+        let config = Config {
+            skill_level: Some(0),
+            ..Config::default()
+        };
+
+        let blocks = ["f8", "g8", "k8", "l8"].map(pos_unchecked);
+
+        for _ in 0 .. 8 {
+            let best_move = GameAgent::from_state(config, state).launch::<Instant>(
+                config,
+                config.initial_timer,
+                SearchObjective::Best,
+                NullResponseSender,
+                Arc::new(AtomicU32::new(0)),
+                Arc::new(AtomicBool::new(false))
+            );
+
+            assert!(blocks.contains(&best_move.best_move.unwrap()), "{}", best_move.best_move);
+        }
+    }
+}
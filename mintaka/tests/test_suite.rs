@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod test_suite {
+    use mintaka::config::Config;
+    use mintaka::game_agent::GameAgent;
+    use mintaka::test_suite::{Expectation, TestSuite};
+    use rusty_renju::notation::pos::pos_unchecked;
+    use rusty_renju::notation::rule::RuleKind;
+    use std::str::FromStr;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    const SUITE: &str = r#"
+        # renju tactics
+        h8,h9,i8,i9,j8,j9,k8,k9 ; bm g8 l8 ; score > 0 ; id "five"
+        i8,h8,j8,a1,k8,a3,l9,a5,l10,a15 ; vcf ; id "four three"
+        h8,h9 ; novcf
+    "#;
+
+    #[test]
+    fn parse_suite() {
+        let suite = TestSuite::from_str(SUITE).unwrap();
+
+        assert_eq!(suite.cases.len(), 3);
+        assert_eq!(suite.cases[0].id, "five");
+        assert_eq!(suite.cases[1].id, "four three");
+        assert_eq!(suite.cases[2].id, "line-5");
+        assert_eq!(suite.cases[0].expectations, vec![
+            Expectation::BestMove(vec![pos_unchecked("g8"), pos_unchecked("l8")]),
+            Expectation::ScoreAbove(0),
+        ]);
+        assert_eq!(suite.cases[2].expectations, vec![Expectation::NoVcf]);
+
+        let err = TestSuite::from_str("h8 ; vcf\nh8,h9 ; bm\n").unwrap_err();
+        assert_eq!(err.line, 2);
+
+        assert!(TestSuite::from_str("h8 ; id \"no expectations\"").is_err());
+        assert!(TestSuite::from_str("h8 ; score >= 10").is_err());
+        assert!(TestSuite::from_str("h8 ; mate").is_err());
+    }
+
+    #[test]
+    fn run_suite() {
+        let config = Config {
+            max_nodes_in_1k: Some(3),
+            ..Config::default()
+        };
+
+        let suite = TestSuite::from_str(SUITE).unwrap();
+
+        let report = GameAgent::<{ RuleKind::Renju }>::run_suite::<Instant>(
+            config, &suite, |_| {}, Arc::new(AtomicBool::new(false))
+        );
+
+        assert!(report.completed);
+        assert_eq!(report.results.len(), 3);
+        assert!(report.results.iter().all(|result| result.passed && result.error.is_none()), "{report:?}");
+
+        let failing = TestSuite::from_str("h8,h9 ; bm a1").unwrap();
+
+        let report = GameAgent::<{ RuleKind::Renju }>::run_suite::<Instant>(
+            config, &failing, |_| {}, Arc::new(AtomicBool::new(false))
+        );
+
+        assert_eq!(report.passed(), 0);
+    }
+}
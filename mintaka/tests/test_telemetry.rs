@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod test_telemetry {
+    use mintaka::config::{Config, SearchObjective};
+    use mintaka::game_agent::GameAgent;
+    use mintaka::game_state::GameState;
+    use mintaka::protocol::response::{CallBackResponseSender, Response};
+    use mintaka::protocol::timer::Timer;
+    use rusty_renju::history::History;
+    use rusty_renju::notation::rule::RuleKind;
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicBool, AtomicU32};
+    use std::sync::{Arc, Mutex};
+    use std::time::Instant;
+
+    #[test]
+    fn telemetry_responses() {
+        let state: GameState<{ RuleKind::Renju }> = History::from_str("h8,h9,i8").unwrap().into();
+
+        let config = Config {
+            max_nodes_in_1k: Some(100),
+            workers: 3,
+            ..Config::default()
+        };
+
+        let mut agent = GameAgent::from_state(config, state);
+
+        let telemetries = Arc::new(Mutex::new(vec![]));
+        let response_sender = CallBackResponseSender::new({
+            let telemetries = telemetries.clone();
+
+            move |response| if let Response::Telemetry(telemetry) = response {
+                telemetries.lock().unwrap().push(telemetry);
+            }
+        });
+
+        agent.launch::<Instant>(
+            config,
+            Timer::INFINITE,
+            SearchObjective::Best,
+            response_sender,
+            Arc::new(AtomicU32::new(0)),
+            Arc::new(AtomicBool::new(false))
+        );
+
+        let telemetries = telemetries.lock().unwrap();
+        let last = telemetries.last().unwrap();
+
+        assert_eq!(last.thread_depths.len(), 3);
+        assert!(last.thread_depths[0] > 0);
+        assert!(last.hash_full_permille <= 1000);
+        assert_eq!(last.statics.is_some(), cfg!(feature = "debug-statics"));
+    }
+}
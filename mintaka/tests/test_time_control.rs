@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod test_time_control {
+    use mintaka::protocol::timer::{TimeControl, Timer};
+    use mintaka::time_manager::TimeManager;
+    use std::time::{Duration, Instant};
+
+    const fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    fn hard_limit(timer: Timer) -> Duration {
+        TimeManager::init(timer, Instant::now()).hard_limit().unwrap()
+    }
+
+    #[test]
+    fn byo_yomi_accounting() {
+        let mut timer = Timer::new(Some(secs(10)), Duration::ZERO, None)
+            .with_control(TimeControl::ByoYomi { periods: 3, period_time: secs(30) });
+
+        timer.consume(secs(25));
+        assert_eq!(timer.total_remaining, Some(Duration::ZERO));
+        assert_eq!(timer.control, TimeControl::ByoYomi { periods: 3, period_time: secs(30) });
+
+        timer.consume(secs(65));
+        assert_eq!(timer.control, TimeControl::ByoYomi { periods: 1, period_time: secs(30) });
+        assert!(!timer.is_flagged());
+
+        timer.consume(secs(31));
+        assert!(timer.is_flagged());
+
+        let limit = hard_limit(Timer::new(Some(Duration::ZERO), Duration::ZERO, None)
+            .with_control(TimeControl::ByoYomi { periods: 5, period_time: secs(30) }));
+
+        assert_eq!(limit, secs(27));
+
+        let limit = hard_limit(Timer::new(None, Duration::ZERO, Some(secs(10)))
+            .with_control(TimeControl::ByoYomi { periods: 5, period_time: secs(30) }));
+
+        assert_eq!(limit, secs(10));
+    }
+
+    #[test]
+    fn delay_accounting() {
+        let mut simple = Timer::new(Some(secs(60)), Duration::ZERO, None)
+            .with_control(TimeControl::SimpleDelay { delay: secs(5) });
+
+        simple.consume(secs(3));
+        assert_eq!(simple.total_remaining, Some(secs(60)));
+
+        simple.consume(secs(8));
+        assert_eq!(simple.total_remaining, Some(secs(57)));
+
+        let mut bronstein = Timer::new(Some(secs(60)), Duration::ZERO, None)
+            .with_control(TimeControl::Bronstein { delay: secs(5) });
+
+        bronstein.consume(secs(3));
+        assert_eq!(bronstein.total_remaining, Some(secs(60)));
+
+        bronstein.consume(secs(8));
+        assert_eq!(bronstein.total_remaining, Some(secs(57)));
+
+        assert!(hard_limit(Timer::new(Some(secs(2)), Duration::ZERO, None)
+            .with_control(TimeControl::SimpleDelay { delay: secs(5) })) <= secs(7));
+        assert!(hard_limit(Timer::new(Some(secs(2)), Duration::ZERO, None)
+            .with_control(TimeControl::Bronstein { delay: secs(5) })) <= secs(2));
+    }
+
+    #[test]
+    fn moves_to_go_accounting() {
+        let mut timer = Timer::new(Some(secs(100)), Duration::ZERO, None)
+            .with_control(TimeControl::MovesToGo { moves_to_go: 2, moves_per_period: 20, period_time: secs(600) });
+
+        assert_eq!(hard_limit(timer), secs(100) / 3);
+
+        timer.consume(secs(10));
+        timer.consume(secs(10));
+
+        assert_eq!(timer.total_remaining, Some(secs(680)));
+        assert_eq!(timer.control, TimeControl::MovesToGo { moves_to_go: 20, moves_per_period: 20, period_time: secs(600) });
+    }
+
+    #[test]
+    fn hourglass_accounting() {
+        let mut timer = Timer::new(Some(secs(60)), Duration::ZERO, None)
+            .with_control(TimeControl::Hourglass);
+
+        timer.consume(secs(10));
+        timer.opponent_consume(secs(4));
+
+        assert_eq!(timer.total_remaining, Some(secs(54)));
+        assert!(hard_limit(timer) < hard_limit(Timer::new(Some(secs(54)), Duration::ZERO, None)));
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod test_vcf_defense {
+    use indoc::indoc;
+    use mintaka::config::Config;
+    use mintaka::game_agent::GameAgent;
+    use mintaka::game_state::GameState;
+    use mintaka::search_endgame::DefenseKind;
+    use rusty_renju::board;
+    use rusty_renju::history::History;
+    use rusty_renju::notation::pos::pos_unchecked;
+    use rusty_renju::notation::rule::RuleKind;
+    use std::str::FromStr;
+    use std::time::Instant;
+
+    #[test]
+    fn refuting_moves() {
+        let state: GameState<{ RuleKind::Renju }> = board!(indoc! {"
+           A B C D E F G H I J K L M N O
+        15 . . . . . . . . . . . . . . . 15
+        14 . . . . . . . . . . . . . . . 14
+        13 . . . . . . . . . . . . . . . 13
+        12 . . . . . . . . . . . . . . . 12
+        11 . . . . . . . . . . O . . . . 11
+        10 . . . . . . . . . X . X . . . 10
+         9 . . . . . . . . . O . . . . . 9
+         8 . . . . . . . X . X X O . . . 8
+         7 . . . . . . X . X O . . . . . 7
+         6 . . . . . . . O O . . . . . . 6
+         5 . . . . . . O . . . . . . . . 5
+         4 . . . . . . . . . . . . . . . 4
+         3 . . . . . . . . . . . . . . . 3
+         2 . . . . . . . . . . . . . . . 2
+         1 X . . . . . . . . . . . . . . 1
+           A B C D E F G H I J K L M N O"}).into();
+
+        let config = Config::default();
+        let agent = GameAgent::from_state(config, state);
+
+        let result = agent.vcf_defenses::<Instant>(config).unwrap();
+
+        assert!(!result.threat.is_empty());
+        assert!(!result.defenses.is_empty());
+        assert!(result.defenses.len() < 50);
+
+        for defense in result.defenses.iter() {
+            if defense.kind == DefenseKind::Block {
+                assert!(result.threat.contains(&defense.defense));
+            }
+
+            // passing back keeps the defender to move, so any remaining vcf would be reported again
+            let mut next = state;
+            next.play_mut(defense.defense);
+            next.pass_mut();
+
+            assert!(GameAgent::from_state(config, next).vcf_defenses::<Instant>(config).is_none());
+        }
+
+        assert!(!result.defenses.iter().any(|defense| defense.defense == pos_unchecked("a15")));
+
+        // a tripped search budget must not turn every remaining move into a defense
+        let limited = Config {
+            max_nodes_in_1k: Some(0),
+            deterministic: true,
+            ..config
+        };
+
+        let limited_result = agent.vcf_defenses::<Instant>(limited).unwrap();
+
+        assert_eq!(
+            limited_result.defenses.iter().map(|defense| defense.defense).collect::<Vec<_>>(),
+            result.defenses.iter().map(|defense| defense.defense).collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn no_threat() {
+        let state: GameState<{ RuleKind::Renju }> = History::from_str("h8,h9,i8").unwrap().into();
+
+        let config = Config::default();
+
+        assert!(GameAgent::from_state(config, state).vcf_defenses::<Instant>(config).is_none());
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod test_worker_pool {
+    use mintaka::config::{Config, SearchObjective};
+    use mintaka::game_agent::GameAgent;
+    use mintaka::game_state::GameState;
+    use mintaka::protocol::response::NullResponseSender;
+    use mintaka::worker_pool::WorkerPool;
+    use rusty_renju::history::History;
+    use rusty_renju::notation::rule::RuleKind;
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    #[test]
+    fn scoped_jobs() {
+        let config = Config::default();
+        let mut pool = WorkerPool::default();
+
+        pool.resize(4, config);
+        assert_eq!(pool.len(), 4);
+
+        let visited = AtomicUsize::new(0);
+
+        for _ in 0 .. 3 {
+            pool.scope(|s| {
+                for index in 0 .. pool.len() {
+                    s.spawn(index, |buffers| {
+                        visited.fetch_add(1, Ordering::Relaxed);
+                        buffers
+                    });
+                }
+            });
+        }
+
+        assert_eq!(visited.load(Ordering::Relaxed), 12);
+
+        pool.resize(1, config);
+        assert_eq!(pool.len(), 1);
+
+        pool.resize(0, config);
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn panicked_job() {
+        let mut pool = WorkerPool::default();
+        pool.resize(2, Config::default());
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            pool.scope(|s| s.spawn(0, |_| panic!("worker failure")));
+        }));
+
+        assert!(result.is_err());
+
+        let visited = AtomicUsize::new(0);
+
+        pool.scope(|s| s.spawn(0, |buffers| {
+            visited.fetch_add(1, Ordering::Relaxed);
+            buffers
+        }));
+
+        assert_eq!(visited.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn repeated_searches() {
+        let history = History::from_str("h8,h9,i8,i9").unwrap();
+
+        let mut config = Config {
+            max_nodes_in_1k: Some(50),
+            ..Config::default()
+        };
+
+        let mut agent: GameAgent<{ RuleKind::Renju }> = GameAgent::from_state(config, GameState::from(history));
+
+        for workers in [4, 4, 2, 6, 1] {
+            config.workers = workers;
+
+            let best_move = agent.launch::<Instant>(
+                config,
+                config.initial_timer,
+                SearchObjective::Best,
+                NullResponseSender,
+                Arc::new(AtomicU32::new(0)),
+                Arc::new(AtomicBool::new(false))
+            );
+
+            assert!(best_move.best_move.is_some());
+        }
+
+        agent.release_workers();
+
+        let best_move = agent.launch::<Instant>(
+            config,
+            config.initial_timer,
+            SearchObjective::Best,
+            NullResponseSender,
+            Arc::new(AtomicU32::new(0)),
+            Arc::new(AtomicBool::new(false))
+        );
+
+        assert!(best_move.best_move.is_some());
+    }
+}
@@ -0,0 +1,8 @@
+#![feature(adt_const_params)]
+
+#[path = "../gtp.rs"]
+mod gtp;
+
+fn main() -> Result<(), impl std::error::Error> {
+    gtp::entry::<{ rusty_renju::notation::rule::RuleKind::Renju }>()
+}
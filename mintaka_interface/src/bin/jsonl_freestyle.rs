@@ -0,0 +1,8 @@
+#![feature(adt_const_params)]
+
+#[path = "../jsonl.rs"]
+mod jsonl;
+
+fn main() -> Result<(), impl std::error::Error> {
+    jsonl::entry::<{ rusty_renju::notation::rule::RuleKind::Freestyle }>()
+}
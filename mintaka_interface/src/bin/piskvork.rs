@@ -0,0 +1,9 @@
+#![feature(adt_const_params)]
+
+#[path = "../piskvork.rs"]
+mod piskvork;
+
+// managers announce the rule with `INFO rule` after START
+fn main() -> Result<(), impl std::error::Error> {
+    piskvork::entry(mintaka_interface::message::RuleSelection::switchable(rusty_renju::notation::rule::RuleKind::Freestyle))
+}
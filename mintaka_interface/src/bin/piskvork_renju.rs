@@ -4,5 +4,5 @@
 mod piskvork;
 
 fn main() -> Result<(), impl std::error::Error> {
-    piskvork::entry::<{ rusty_renju::notation::rule::RuleKind::Renju }>()
+    piskvork::entry(mintaka_interface::message::RuleSelection::fixed(rusty_renju::notation::rule::RuleKind::Renju))
 }
@@ -0,0 +1,8 @@
+#![feature(adt_const_params)]
+
+#[path = "../suite.rs"]
+mod suite;
+
+fn main() -> Result<(), impl std::error::Error> {
+    suite::entry::<{ rusty_renju::notation::rule::RuleKind::Gomoku }>()
+}
@@ -0,0 +1,13 @@
+#![feature(adt_const_params)]
+
+#[path = "../text_protocol.rs"]
+mod text_protocol;
+
+#[cfg(feature = "tui")]
+#[path = "../tui.rs"]
+mod tui;
+
+// starts with `--rule`, renju by default, and switches on `config rule`
+fn main() -> Result<(), impl std::error::Error> {
+    text_protocol::entry(mintaka_interface::message::RuleSelection::switchable(rusty_renju::notation::rule::RuleKind::Renju))
+}
@@ -3,6 +3,10 @@
 #[path = "../text_protocol.rs"]
 mod text_protocol;
 
+#[cfg(feature = "tui")]
+#[path = "../tui.rs"]
+mod tui;
+
 fn main() -> Result<(), impl std::error::Error> {
-    text_protocol::entry::<{ rusty_renju::notation::rule::RuleKind::Renju }>()
+    text_protocol::entry(mintaka_interface::message::RuleSelection::fixed(rusty_renju::notation::rule::RuleKind::Renju))
 }
@@ -0,0 +1,216 @@
+use mintaka::config::{Config, ConfigValidationError};
+use mintaka::protocol::timer::{TimeControl, Timer};
+use mintaka::value::Depth;
+use rusty_renju::utils::byte_size::ByteSize;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const CONFIG_FILE_NAME: &str = "mintaka.toml";
+pub const CONFIG_PATH_ENV: &str = "MINTAKA_CONFIG";
+
+// every key is optional, missing keys keep the defaults of the front-end, e.g.
+// [engine]
+// workers = 4
+// memory_in_mib = 256
+// [time]
+// turn_time_in_ms = 5000
+// control = "byo-yomi"
+// periods = 3
+// period_time_in_ms = 10000
+// [protocol]
+// realtime = true
+#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigFile {
+    pub engine: EngineSection,
+    pub time: TimeSection,
+    pub protocol: ProtocolSection,
+}
+
+#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EngineSection {
+    pub draw_condition: Option<u32>,
+    pub max_nodes_in_1k: Option<u32>,
+    pub max_depth: Option<Depth>,
+    pub max_vcf_depth: Option<Depth>,
+    pub memory_in_mib: Option<u64>,
+    pub workers: Option<u32>,
+    pub pondering: Option<bool>,
+    pub spawn_depth_specialist: Option<bool>,
+    pub skill_level: Option<u8>,
+    pub deterministic: Option<bool>,
+}
+
+// zero total or turn time means unlimited, same as the time flags
+#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TimeSection {
+    pub total_time_in_ms: Option<u64>,
+    pub increment_time_in_ms: Option<u64>,
+    pub turn_time_in_ms: Option<u64>,
+    pub control: Option<TimeControlKind>,
+    pub periods: Option<u32>,
+    pub period_time_in_ms: Option<u64>,
+    pub delay_in_ms: Option<u64>,
+    pub moves_to_go: Option<u32>,
+    pub moves_per_period: Option<u32>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimeControlKind {
+    Fischer,
+    ByoYomi,
+    SimpleDelay,
+    Bronstein,
+    MovesToGo,
+    Hourglass,
+}
+
+#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProtocolSection {
+    // piskvork realtime messages for yixin-style clients
+    pub realtime: Option<bool>,
+    // text protocol commands run before reading stdin
+    pub command_sequence: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum ConfigFileError {
+    Io(PathBuf, std::io::Error),
+    Parse(PathBuf, toml::de::Error),
+    Invalid(PathBuf, ConfigValidationError),
+}
+
+impl Display for ConfigFileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigFileError::Io(path, err) => write!(f, "{}: {err}", path.display()),
+            ConfigFileError::Parse(path, err) => write!(f, "{}: {}", path.display(), err.message()),
+            ConfigFileError::Invalid(path, err) => write!(f, "{}: {err}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for ConfigFileError {}
+
+impl ConfigFile {
+    pub fn load(path: &Path) -> Result<Self, ConfigFileError> {
+        let source = std::fs::read_to_string(path)
+            .map_err(|err| ConfigFileError::Io(path.to_path_buf(), err))?;
+
+        let file: Self = toml::from_str(&source)
+            .map_err(|err| ConfigFileError::Parse(path.to_path_buf(), err))?;
+
+        file.apply(Config::default())
+            .map_err(|err| ConfigFileError::Invalid(path.to_path_buf(), err))?;
+
+        Ok(file)
+    }
+
+    // a file named by the environment has to exist, the standard locations are optional
+    pub fn discover() -> Result<Option<Self>, ConfigFileError> {
+        if let Some(path) = std::env::var_os(CONFIG_PATH_ENV) {
+            return Self::load(Path::new(&path)).map(Some);
+        }
+
+        Self::load_first(&standard_locations())
+    }
+
+    pub fn load_from_folder(folder: &Path) -> Result<Option<Self>, ConfigFileError> {
+        Self::load_first(&[folder.join(CONFIG_FILE_NAME)])
+    }
+
+    fn load_first(candidates: &[PathBuf]) -> Result<Option<Self>, ConfigFileError> {
+        candidates.iter()
+            .find(|path| path.is_file())
+            .map(|path| Self::load(path))
+            .transpose()
+    }
+
+    pub fn apply(&self, config: Config) -> Result<Config, ConfigValidationError> {
+        let mut config = self.apply_engine(config)?;
+
+        config.initial_timer = self.apply_timer(config.initial_timer);
+
+        Ok(config)
+    }
+
+    pub fn apply_engine(&self, mut config: Config) -> Result<Config, ConfigValidationError> {
+        let engine = &self.engine;
+
+        config.draw_condition = engine.draw_condition.or(config.draw_condition);
+        config.max_nodes_in_1k = engine.max_nodes_in_1k.or(config.max_nodes_in_1k);
+        config.max_depth = engine.max_depth.or(config.max_depth);
+        config.max_vcf_depth = engine.max_vcf_depth.or(config.max_vcf_depth);
+        config.tt_size = engine.memory_in_mib.map_or(config.tt_size, ByteSize::from_mib);
+        config.workers = engine.workers.unwrap_or(config.workers);
+        config.pondering = engine.pondering.unwrap_or(config.pondering);
+        config.spawn_depth_specialist = engine.spawn_depth_specialist.unwrap_or(config.spawn_depth_specialist);
+        config.skill_level = engine.skill_level.or(config.skill_level);
+        config.deterministic = engine.deterministic.unwrap_or(config.deterministic);
+
+        config.validate()
+    }
+
+    pub fn apply_timer(&self, mut timer: Timer) -> Timer {
+        let time = &self.time;
+
+        if let Some(total_time_in_ms) = time.total_time_in_ms {
+            timer.total_remaining = (total_time_in_ms != 0).then_some(Duration::from_millis(total_time_in_ms));
+        }
+
+        if let Some(turn_time_in_ms) = time.turn_time_in_ms {
+            timer.turn = (turn_time_in_ms != 0).then_some(Duration::from_millis(turn_time_in_ms));
+        }
+
+        timer.increment = time.increment_time_in_ms.map_or(timer.increment, Duration::from_millis);
+
+        let period_time = Duration::from_millis(time.period_time_in_ms.unwrap_or_default());
+        let delay = Duration::from_millis(time.delay_in_ms.unwrap_or_default());
+
+        timer.control = match time.control {
+            Some(TimeControlKind::Fischer) => TimeControl::Fischer,
+            Some(TimeControlKind::ByoYomi) => TimeControl::ByoYomi {
+                periods: time.periods.unwrap_or(1),
+                period_time,
+            },
+            Some(TimeControlKind::SimpleDelay) => TimeControl::SimpleDelay { delay },
+            Some(TimeControlKind::Bronstein) => TimeControl::Bronstein { delay },
+            Some(TimeControlKind::MovesToGo) => TimeControl::MovesToGo {
+                moves_to_go: time.moves_to_go.unwrap_or(1),
+                moves_per_period: time.moves_per_period.or(time.moves_to_go).unwrap_or(1),
+                period_time,
+            },
+            Some(TimeControlKind::Hourglass) => TimeControl::Hourglass,
+            None => timer.control,
+        };
+
+        timer
+    }
+}
+
+// the working directory first, then next to the executable, where tournament managers keep brains,
+// then the user configuration directory
+fn standard_locations() -> Vec<PathBuf> {
+    let mut locations = vec![PathBuf::from(CONFIG_FILE_NAME)];
+
+    if let Some(exe_dir) = std::env::current_exe().ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+    {
+        locations.push(exe_dir.join(CONFIG_FILE_NAME));
+    }
+
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from)
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+
+    if let Some(config_dir) = config_dir {
+        locations.push(config_dir.join("mintaka").join(CONFIG_FILE_NAME));
+    }
+
+    locations
+}
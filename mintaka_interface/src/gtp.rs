@@ -0,0 +1,506 @@
+use mintaka::config::{Config, SearchObjective};
+use mintaka::game_agent::{ComputingResource, GameAgent, GameError};
+use mintaka::protocol::command::Command;
+use mintaka::protocol::response::{CallBackResponseSender, Response};
+use mintaka::protocol::timer::TimeControl;
+#[cfg(feature = "config-file")]
+use mintaka_interface::config_file::ConfigFile;
+use mintaka_interface::message::{ConfigCommand, Message, MessageCommand, MessageSender, StatusCommand, TimeControlUpdate, CHANNEL_CLOSED_MESSAGE};
+use rusty_renju::notation::color::{Color, ColorContainer};
+use rusty_renju::notation::pos;
+use rusty_renju::notation::pos::{MaybePos, Pos};
+use rusty_renju::notation::rule::RuleKind;
+use std::error::Error;
+use std::io::{BufRead, Write};
+use std::sync::atomic::{AtomicBool, AtomicU32};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+pub fn entry<const R: RuleKind>() -> Result<(), impl Error> {
+    gtp_protocol::<R>()
+}
+
+// gtp has no way to express an unlimited clock, a day is long enough for any game
+const UNLIMITED_TIME: Duration = Duration::from_secs(60 * 60 * 24);
+
+const KNOWN_COMMANDS: &[&str] = &[
+    "protocol_version",
+    "name",
+    "version",
+    "known_command",
+    "list_commands",
+    "quit",
+    "boardsize",
+    "clear_board",
+    "komi",
+    "play",
+    "genmove",
+    "undo",
+    "time_settings",
+    "kgs-time_settings",
+    "time_left",
+    "final_score",
+    "showboard",
+];
+
+type GtpReply = Result<String, String>;
+
+fn stdio_out(id: Option<u32>, reply: GtpReply) {
+    let id = id.map_or_else(String::new, |id| id.to_string());
+
+    match reply {
+        Ok(response) if response.is_empty() => println!("={id}\n"),
+        Ok(response) => println!("={id} {response}\n"),
+        Err(error) => println!("?{id} {error}\n"),
+    }
+
+    std::io::stdout().flush().expect("failed to flush stdout");
+}
+
+// stdout belongs to the controller, progress goes to stderr
+fn print_response(response: Response) {
+    match response {
+        Response::Begins(ComputingResource { workers, time_limit, nodes_in_1k }) =>
+            eprintln!("begins: workers={workers}, running-time={time_limit:?}, nodes={nodes_in_1k:?}k"),
+        Response::Status { best_move, score, pv, total_nodes_in_1k, selective_depth, .. } =>
+            eprintln!("status: depth={selective_depth}, score={score}, best_move={best_move}, total_nodes_in_1k={total_nodes_in_1k}, pv={pv:?}"),
+        Response::Telemetry(_) => {},
+    }
+}
+
+fn gtp_protocol<const R: RuleKind>() -> Result<(), impl Error> {
+    let aborted = Arc::new(AtomicBool::new(false));
+
+    let mut config = Config::default();
+
+    // stdout is reserved for replies, so a broken file is only reported on stderr
+    #[cfg(feature = "config-file")]
+    match ConfigFile::discover().map(Option::unwrap_or_default).map(|file| file.apply(config)) {
+        Ok(Ok(file_config)) => config = file_config,
+        Ok(Err(err)) => eprintln!("{err}"),
+        Err(err) => eprintln!("{err}"),
+    }
+
+    let mut game_agent = GameAgent::<R>::new(config);
+
+    let mut timer = config.initial_timer;
+
+    // the opponent's clock runs from our last move until the next genmove, unless time_left reports it
+    let mut opponent_turn_started: Option<Instant> = None;
+
+    let (message_sender, message_receiver) = {
+        let (tx, rx) = mpsc::channel();
+        (MessageSender::new(tx), rx)
+    };
+
+    let (reply_sender, reply_receiver) = mpsc::channel::<GtpReply>();
+
+    spawn_command_listener(message_sender, reply_receiver);
+
+    for message in message_receiver {
+        let reply = match message {
+            Message::Command(command) => {
+                game_agent.command(command.into_command(&config, game_agent.state.board.hash_key))
+                    .map(|_| String::new())
+                    .map_err(|err| err.to_string())
+            }
+            Message::Launch { objective, .. } => {
+                if let Some(turn_started) = opponent_turn_started.take() {
+                    timer.opponent_consume(turn_started.elapsed());
+                }
+
+                let best_move = game_agent.launch::<Instant>(
+                    config,
+                    timer,
+                    objective,
+                    CallBackResponseSender::new(print_response),
+                    Arc::new(AtomicU32::new(0)),
+                    aborted.clone(),
+                );
+
+                // genmove always plays the generated move
+                game_agent.command(Command::Play {
+                    hash: game_agent.state.board.hash_key,
+                    pos: best_move.best_move,
+                    draw_condition: config.draw_condition,
+                })
+                    .map(|_| {
+                        timer.consume(best_move.time_elapsed);
+                        timer.apply_increment();
+                        opponent_turn_started = Some(Instant::now());
+
+                        format_vertex(best_move.best_move)
+                    })
+                    .map_err(|err| err.to_string())
+            }
+            Message::Config(ConfigCommand::TotalTime(total)) => {
+                timer.total_remaining = Some(total);
+                opponent_turn_started = None;
+                Ok(String::new())
+            }
+            Message::Config(ConfigCommand::IncrementTime(increment)) => {
+                config.initial_timer.increment = increment;
+                timer.increment = increment;
+                Ok(String::new())
+            }
+            Message::Config(ConfigCommand::TurnTime(turn)) => {
+                config.initial_timer.turn = Some(turn);
+                timer.turn = Some(turn);
+                Ok(String::new())
+            }
+            Message::Config(ConfigCommand::TimeControl(update)) => {
+                config.initial_timer.control = update.apply(config.initial_timer.control);
+                timer.control = update.apply(timer.control);
+                Ok(String::new())
+            }
+            Message::Config(_) => unreachable!(),
+            Message::Status(StatusCommand::Board { show_last_moves }) => {
+                let board = if show_last_moves {
+                    game_agent.state.board.to_string_with_last_moves(game_agent.state.history.last_action_pair())
+                } else {
+                    game_agent.state.board.to_string()
+                };
+
+                Ok(format!("\n{board}"))
+            }
+            Message::Status(StatusCommand::Result) => {
+                let winner = game_agent.state.history.last_action_or_none().ok()
+                    .and_then(|pos| game_agent.state.board.find_winner(pos));
+
+                match winner {
+                    Some(Color::Black) => Ok("B+".to_string()),
+                    Some(Color::White) => Ok("W+".to_string()),
+                    None if game_agent.state.board.stones == pos::U8_BOARD_SIZE => Ok("0".to_string()),
+                    None => Err("cannot score".to_string()),
+                }
+            }
+            _ => unreachable!(),
+        };
+
+        reply_sender.send(reply).expect(CHANNEL_CLOSED_MESSAGE);
+    }
+
+    Ok::<(), GameError>(())
+}
+
+// mirrors the side to move, gtp lets either color play at any time
+struct Session {
+    player_color: Color,
+    // engine actions behind each gtp move, two when a pass was inserted
+    actions: Vec<u8>,
+    // controllers may report both clocks, only the one of the color asked to genmove reaches the engine
+    time_left: ColorContainer<Option<(Duration, u32)>>,
+    time_control: TimeControl,
+}
+
+impl Session {
+    const EMPTY: Self = Self {
+        player_color: Color::Black,
+        actions: vec![],
+        time_left: ColorContainer::new(None, None),
+        time_control: TimeControl::Fischer,
+    };
+}
+
+struct Engine<'a> {
+    message_sender: &'a MessageSender,
+    replies: &'a mpsc::Receiver<GtpReply>,
+}
+
+impl Engine<'_> {
+    fn reply(&self) -> GtpReply {
+        self.replies.recv().expect(CHANNEL_CLOSED_MESSAGE)
+    }
+
+    fn command(&self, command: MessageCommand) -> GtpReply {
+        self.message_sender.command(command);
+        self.reply()
+    }
+
+    fn config(&self, command: ConfigCommand) -> GtpReply {
+        self.message_sender.config(command);
+        self.reply()
+    }
+
+    fn status(&self, command: StatusCommand) -> GtpReply {
+        self.message_sender.status(command);
+        self.reply()
+    }
+
+    fn launch(&self) -> GtpReply {
+        self.message_sender.launch(SearchObjective::Best, true, false);
+        self.reply()
+    }
+
+    // passes first when the other color is asked to move
+    fn align_turn(&self, session: &Session, color: Color) -> Result<u8, String> {
+        if session.player_color == color {
+            return Ok(0);
+        }
+
+        self.command(MessageCommand::Play { pos: MaybePos::NONE })?;
+
+        Ok(1)
+    }
+
+    fn revert(&self, actions: u8) {
+        for _ in 0 .. actions {
+            let _ = self.command(MessageCommand::Undo);
+        }
+    }
+}
+
+// https://www.lysator.liu.se/~gunnar/gtp/gtp2-spec-draft2/gtp2-spec.html
+fn match_command(
+    engine: &Engine,
+    session: &mut Session,
+    command: &str,
+    args: &[&str],
+) -> GtpReply {
+    match command {
+        "protocol_version" => Ok("2".to_string()),
+        "name" => Ok("mintaka".to_string()),
+        "version" => Ok(mintaka::VERSION.to_string()),
+        "known_command" => {
+            let command = args.first().ok_or("syntax error")?;
+
+            Ok(KNOWN_COMMANDS.contains(command).to_string())
+        }
+        "list_commands" => Ok(KNOWN_COMMANDS.join("\n")),
+        "boardsize" => {
+            let size = args.first()
+                .and_then(|size| size.parse::<usize>().ok())
+                .ok_or("syntax error")?;
+
+            if size != pos::U_BOARD_WIDTH {
+                return Err("unacceptable size".to_string());
+            }
+
+            Ok(String::new())
+        }
+        "clear_board" => {
+            engine.command(MessageCommand::Command(Command::Clear))?;
+            *session = Session { time_control: session.time_control, ..Session::EMPTY };
+
+            Ok(String::new())
+        }
+        "komi" => {
+            args.first()
+                .and_then(|komi| komi.parse::<f32>().ok())
+                .ok_or("syntax error")?;
+
+            Ok(String::new())
+        }
+        "play" => {
+            let [color, vertex] = args else {
+                return Err("syntax error".to_string());
+            };
+
+            let color = color.parse::<Color>().map_err(|_| "syntax error")?;
+            let pos = parse_vertex(vertex)?;
+
+            let passed = engine.align_turn(session, color)?;
+
+            if let Err(err) = engine.command(MessageCommand::Play { pos }) {
+                engine.revert(passed);
+                return Err(format!("illegal move: {err}"));
+            }
+
+            session.actions.push(passed + 1);
+            session.player_color = !color;
+
+            Ok(String::new())
+        }
+        "genmove" => {
+            let color = args.first()
+                .and_then(|color| color.parse::<Color>().ok())
+                .ok_or("syntax error")?;
+
+            let passed = engine.align_turn(session, color)?;
+
+            if let Some((time, stones)) = session.time_left[color].take() {
+                apply_time_left(engine, session.time_control, time, stones).inspect_err(|_| engine.revert(passed))?;
+            }
+
+            let vertex = engine.launch().inspect_err(|_| engine.revert(passed))?;
+
+            session.actions.push(passed + 1);
+            session.player_color = !color;
+
+            Ok(vertex)
+        }
+        "undo" => {
+            let actions = session.actions.pop().ok_or("cannot undo")?;
+
+            engine.revert(actions);
+
+            // an inserted pass already handed the turn back
+            if actions == 1 {
+                session.player_color = !session.player_color;
+            }
+
+            Ok(String::new())
+        }
+        "time_settings" => {
+            let [main_time, byo_yomi_time, byo_yomi_stones] = parse_numbers(args)?;
+
+            if byo_yomi_time > 0 && byo_yomi_stones == 0 {
+                return set_time(engine, session, UNLIMITED_TIME, None, TimeControl::Fischer);
+            }
+
+            let control = match byo_yomi_stones {
+                0 => TimeControl::Fischer,
+                stones => TimeControl::MovesToGo {
+                    moves_to_go: stones as u32,
+                    moves_per_period: stones as u32,
+                    period_time: Duration::from_secs(byo_yomi_time),
+                },
+            };
+
+            set_time(engine, session, Duration::from_secs(main_time), None, control)
+        }
+        "kgs-time_settings" => {
+            let (kind, args) = args.split_first().ok_or("syntax error")?;
+
+            match *kind {
+                "none" => set_time(engine, session, UNLIMITED_TIME, None, TimeControl::Fischer),
+                "absolute" => {
+                    let [main_time] = parse_numbers(args)?;
+
+                    set_time(engine, session, Duration::from_secs(main_time), None, TimeControl::Fischer)
+                }
+                "byoyomi" => {
+                    let [main_time, period_time, periods] = parse_numbers(args)?;
+
+                    set_time(engine, session, Duration::from_secs(main_time), Some(Duration::from_secs(period_time)), TimeControl::ByoYomi {
+                        periods: periods as u32,
+                        period_time: Duration::from_secs(period_time),
+                    })
+                }
+                "canadian" => {
+                    let [main_time, period_time, stones] = parse_numbers(args)?;
+
+                    set_time(engine, session, Duration::from_secs(main_time), None, TimeControl::MovesToGo {
+                        moves_to_go: stones as u32,
+                        moves_per_period: stones as u32,
+                        period_time: Duration::from_secs(period_time),
+                    })
+                }
+                _ => Err("syntax error".to_string()),
+            }
+        }
+        "time_left" => {
+            let (color, args) = args.split_first().ok_or("syntax error")?;
+            let color = color.parse::<Color>().map_err(|_| "syntax error")?;
+
+            let [time, stones] = parse_numbers(args)?;
+
+            session.time_left[color] = Some((Duration::from_secs(time), stones as u32));
+
+            Ok(String::new())
+        }
+        "final_score" => engine.status(StatusCommand::Result),
+        "showboard" => engine.status(StatusCommand::Board { show_last_moves: true }),
+        _ => Err("unknown command".to_string()),
+    }
+}
+
+fn set_time(engine: &Engine, session: &mut Session, total: Duration, turn: Option<Duration>, control: TimeControl) -> GtpReply {
+    session.time_control = control;
+
+    engine.config(ConfigCommand::TotalTime(total))?;
+    engine.config(ConfigCommand::TimeControl(TimeControlUpdate::Replace(control)))?;
+
+    if let Some(turn) = turn {
+        engine.config(ConfigCommand::TurnTime(turn))?;
+    }
+
+    Ok(String::new())
+}
+
+// stones is zero during the main time, otherwise the byo-yomi periods or the canadian moves left
+fn apply_time_left(engine: &Engine, control: TimeControl, time: Duration, stones: u32) -> GtpReply {
+    match control {
+        TimeControl::ByoYomi { .. } if stones > 0 => {
+            // the time left only covers the running period, which resets after the move anyway
+            engine.config(ConfigCommand::TotalTime(Duration::ZERO))?;
+            engine.config(ConfigCommand::TimeControl(TimeControlUpdate::ByoYomiPeriods(stones)))?;
+        },
+        _ if stones > 0 => {
+            // a canadian period hands out its time for the remaining moves, the period time refills it after
+            engine.config(ConfigCommand::TotalTime(time))?;
+            engine.config(ConfigCommand::TimeControl(TimeControlUpdate::MovesToGo(stones)))?;
+        },
+        _ => {
+            engine.config(ConfigCommand::TotalTime(time))?;
+        },
+    }
+
+    Ok(String::new())
+}
+
+fn spawn_command_listener(message_sender: MessageSender, replies: mpsc::Receiver<GtpReply>) {
+    std::thread::spawn(move || {
+        let engine = Engine { message_sender: &message_sender, replies: &replies };
+        let mut session = Session::EMPTY;
+
+        let stdin = std::io::stdin();
+
+        for line in stdin.lock().lines().map(Result::unwrap) {
+            let line = line.split('#').next().unwrap_or_default().to_lowercase();
+            let mut tokens = line.split_whitespace().peekable();
+
+            let id = tokens.next_if(|token| token.parse::<u32>().is_ok())
+                .and_then(|token| token.parse::<u32>().ok());
+
+            let Some(command) = tokens.next() else {
+                continue;
+            };
+
+            let args = tokens.collect::<Vec<&str>>();
+
+            if command == "quit" {
+                stdio_out(id, Ok(String::new()));
+                std::process::exit(0);
+            }
+
+            let reply = match_command(&engine, &mut session, command, &args);
+
+            stdio_out(id, reply);
+        }
+    });
+}
+
+fn parse_numbers<const N: usize>(args: &[&str]) -> Result<[u64; N], String> {
+    let numbers = args.iter()
+        .map(|token| token.parse::<u64>().map_err(|_| "syntax error".to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    numbers.try_into().map_err(|_| "syntax error".to_string())
+}
+
+// renju boards keep the column i, unlike go
+fn parse_vertex(vertex: &str) -> Result<MaybePos, String> {
+    if vertex == "pass" {
+        return Ok(MaybePos::NONE);
+    }
+
+    let mut chars = vertex.chars();
+
+    let col = chars.next()
+        .filter(char::is_ascii_lowercase)
+        .map(|col| col as u8 - b'a')
+        .ok_or("syntax error")?;
+
+    let row = chars.as_str().parse::<u8>().map_err(|_| "syntax error")?;
+
+    if col >= pos::BOARD_WIDTH || row == 0 || row > pos::BOARD_WIDTH {
+        return Err("syntax error".to_string());
+    }
+
+    Ok(Pos::from_cartesian(row - 1, col).into())
+}
+
+fn format_vertex(pos: MaybePos) -> String {
+    pos.ok().map_or_else(|| "pass".to_string(), |pos| pos.to_string().to_uppercase())
+}
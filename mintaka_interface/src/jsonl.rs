@@ -0,0 +1,161 @@
+use mintaka::config::{Config, SearchObjective};
+use mintaka::game_agent::{GameAgent, GameError};
+use mintaka::protocol::command::Command;
+use mintaka::protocol::response::{CallBackResponseSender, Response};
+use mintaka::protocol::results::{BestMove, CommandResult};
+use mintaka::protocol::timer::Timer;
+use mintaka_interface::config_file::ConfigFile;
+use mintaka_interface::message::CHANNEL_CLOSED_MESSAGE;
+use rusty_renju::notation::rule::RuleKind;
+use std::io::{BufRead, Write};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Instant;
+
+pub fn entry<const R: RuleKind>() -> Result<(), GameError> {
+    jsonl_protocol::<R>()
+}
+
+// one object per line, e.g. {"id":1,"type":"launch","content":{"objective":"Best"}}
+#[derive(serde::Deserialize)]
+struct Request {
+    #[serde(default)]
+    id: Option<u64>,
+    #[serde(flatten)]
+    body: RequestBody,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "type", content = "content", rename_all = "snake_case")]
+enum RequestBody {
+    Command(Command),
+    Launch {
+        #[serde(default)]
+        objective: SearchObjective,
+        // falls back to the initial timer of the config
+        #[serde(default)]
+        timer: Option<Timer>,
+    },
+    Config(Config),
+    Abort,
+    Quit,
+}
+
+#[derive(serde::Serialize)]
+struct Reply {
+    id: Option<u64>,
+    #[serde(flatten)]
+    body: ReplyBody,
+}
+
+#[derive(serde::Serialize)]
+#[serde(tag = "type", content = "content", rename_all = "snake_case")]
+enum ReplyBody {
+    CommandResult(CommandResult),
+    Response(Response),
+    BestMove(BestMove),
+    Ack,
+    Error(String),
+}
+
+fn stdio_out(id: Option<u64>, body: ReplyBody) {
+    let line = serde_json::to_string(&Reply { id, body })
+        .expect("failed to serialize reply");
+
+    let mut stdout = std::io::stdout().lock();
+
+    writeln!(stdout, "{line}")
+        .and_then(|_| stdout.flush())
+        .expect("failed to flush stdout");
+}
+
+fn jsonl_protocol<const R: RuleKind>() -> Result<(), GameError> {
+    let aborted = Arc::new(AtomicBool::new(false));
+
+    let mut config = Config::default();
+
+    match ConfigFile::discover().map(Option::unwrap_or_default).map(|file| file.apply(config)) {
+        Ok(Ok(file_config)) => config = file_config,
+        Ok(Err(err)) => stdio_out(None, ReplyBody::Error(err.to_string())),
+        Err(err) => stdio_out(None, ReplyBody::Error(err.to_string())),
+    }
+
+    let mut game_agent = GameAgent::<R>::new(config);
+
+    let (request_sender, request_receiver) = mpsc::channel::<Request>();
+
+    spawn_request_listener(aborted.clone(), request_sender);
+
+    for Request { id, body } in request_receiver {
+        match body {
+            RequestBody::Command(command) => {
+                match game_agent.command(command) {
+                    Ok(result) => stdio_out(id, ReplyBody::CommandResult(result)),
+                    Err(err) => stdio_out(id, ReplyBody::Error(err.to_string())),
+                }
+            }
+            RequestBody::Launch { objective, timer } => {
+                let best_move = game_agent.launch::<Instant>(
+                    config,
+                    timer.unwrap_or(config.initial_timer),
+                    objective,
+                    CallBackResponseSender::new(|response| stdio_out(id, ReplyBody::Response(response))),
+                    Arc::new(AtomicU32::new(0)),
+                    aborted.clone(),
+                );
+
+                stdio_out(id, ReplyBody::BestMove(best_move));
+            }
+            RequestBody::Config(update) => {
+                if update.tt_size != config.tt_size {
+                    let _ = game_agent.command(Command::RebuildTT(update.tt_size));
+                }
+
+                config = update;
+
+                stdio_out(id, ReplyBody::Ack);
+            }
+            RequestBody::Quit => {
+                stdio_out(id, ReplyBody::Ack);
+                std::process::exit(0);
+            }
+            RequestBody::Abort => unreachable!(),
+        }
+    }
+
+    Ok(())
+}
+
+fn spawn_request_listener(aborted: Arc<AtomicBool>, request_sender: mpsc::Sender<Request>) {
+    std::thread::spawn(move || {
+        let stdin = std::io::stdin();
+
+        for line in stdin.lock().lines().map(Result::unwrap) {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let request = match serde_json::from_str::<Request>(&line) {
+                Ok(request) => request,
+                Err(err) => {
+                    stdio_out(None, ReplyBody::Error(err.to_string()));
+                    continue;
+                }
+            };
+
+            // answered here, the engine thread may be busy searching
+            match request.body {
+                RequestBody::Abort => {
+                    aborted.store(true, Ordering::Relaxed);
+                    stdio_out(request.id, ReplyBody::Ack);
+                }
+                // earlier requests are still answered, only a running search is cut short
+                RequestBody::Quit => {
+                    aborted.store(true, Ordering::Relaxed);
+                    request_sender.send(request).expect(CHANNEL_CLOSED_MESSAGE);
+                }
+                _ => request_sender.send(request).expect(CHANNEL_CLOSED_MESSAGE),
+            }
+        }
+    });
+}
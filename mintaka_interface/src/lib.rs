@@ -2,3 +2,5 @@
 #[cfg(feature = "clap")]
 pub mod preference;
 pub mod message;
+#[cfg(feature = "config-file")]
+pub mod config_file;
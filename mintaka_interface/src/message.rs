@@ -1,8 +1,15 @@
 use mintaka::config::{Config, SearchObjective};
 use mintaka::protocol::command::Command;
+use mintaka::protocol::timer::TimeControl;
+use mintaka::test_suite::TestSuite;
+use mintaka::value::Depth;
 use rusty_renju::hash_key::HashKey;
+use rusty_renju::history_format::HistoryFormat;
 use rusty_renju::notation::color::Color;
 use rusty_renju::notation::pos::{MaybePos, Pos};
+use rusty_renju::notation::rule::RuleKind;
+use rusty_renju::notation::score::Score;
+use std::path::PathBuf;
 use std::sync::mpsc;
 use std::time::Duration;
 use rusty_renju::utils::byte_size::ByteSize;
@@ -16,16 +23,60 @@ pub enum Message {
         print: bool,
     },
     Status(StatusCommand),
+    Analyze { nodes_in_1k: u32 },
+    Prove { max_nodes: usize },
+    Defenses,
+    RankMoves { count: usize },
+    BalanceOne { target: Score },
+    BalanceTwo { target: Score },
+    Swap2,
+    Suite { suite: TestSuite, nodes_in_1k: u32 },
+    Bench { depth: Depth },
 }
 
 pub enum ConfigCommand {
     TotalTime(Duration),
     IncrementTime(Duration),
     TurnTime(Duration),
+    TimeControl(TimeControlUpdate),
     MaxNodes { in_1k: u32 },
     MaxDepth(u32),
     Workers(u32),
+    Deterministic(bool),
     ResizeTT(ByteSize),
+    ClearTT,
+    Realtime(bool),
+    Folder(PathBuf),
+    Rule(RuleKind),
+}
+
+#[derive(Copy, Clone)]
+pub enum TimeControlUpdate {
+    Replace(TimeControl),
+    ByoYomiPeriods(u32),
+    ByoYomiPeriodTime(Duration),
+    MovesToGo(u32),
+}
+
+impl TimeControlUpdate {
+    // protocols announce byo-yomi and tournament controls one value at a time
+    pub fn apply(self, control: TimeControl) -> TimeControl {
+        match (self, control) {
+            (Self::Replace(control), _) => control,
+            (Self::ByoYomiPeriods(periods), TimeControl::ByoYomi { period_time, .. }) =>
+                TimeControl::ByoYomi { periods, period_time },
+            (Self::ByoYomiPeriods(periods), _) =>
+                TimeControl::ByoYomi { periods, period_time: Duration::ZERO },
+            (Self::ByoYomiPeriodTime(period_time), TimeControl::ByoYomi { periods, .. }) =>
+                TimeControl::ByoYomi { periods, period_time },
+            (Self::ByoYomiPeriodTime(period_time), _) =>
+                TimeControl::ByoYomi { periods: 1, period_time },
+            (Self::MovesToGo(moves_to_go), TimeControl::MovesToGo { moves_per_period, period_time, .. }) =>
+                TimeControl::MovesToGo { moves_to_go, moves_per_period, period_time },
+            (Self::MovesToGo(moves_to_go), _) =>
+                TimeControl::MovesToGo { moves_to_go, moves_per_period: moves_to_go, period_time: Duration::ZERO },
+        }
+    }
 }
 
 pub enum MessageCommand {
@@ -50,12 +101,42 @@ impl MessageCommand {
 
 pub const CHANNEL_CLOSED_MESSAGE: &str = "sender channel closed.";
 
+// the rule listeners parse positions with, only multi-rule binaries may change it at runtime
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct RuleSelection {
+    pub rule: RuleKind,
+    pub switchable: bool,
+}
+
+impl RuleSelection {
+    pub fn fixed(rule: RuleKind) -> Self {
+        Self { rule, switchable: false }
+    }
+
+    pub fn switchable(rule: RuleKind) -> Self {
+        Self { rule, switchable: true }
+    }
+
+    pub fn switch(&mut self, rule: RuleKind) -> Result<(), &'static str> {
+        if rule != self.rule && !self.switchable {
+            return Err("unsupported rule");
+        }
+
+        self.rule = rule;
+
+        Ok(())
+    }
+}
+
 pub enum StatusCommand {
     Version,
     Board { show_last_moves: bool },
     Forbid,
-    History,
+    History { format: HistoryFormat },
     Time,
+    Info,
+    Result,
+    Database,
 }
 
 #[derive(Clone)]
@@ -91,4 +172,58 @@ impl MessageSender {
             .send(Message::Launch { objective, apply, print })
             .expect(CHANNEL_CLOSED_MESSAGE);
     }
+
+    pub fn analyze(&self, nodes_in_1k: u32) {
+        self.sender
+            .send(Message::Analyze { nodes_in_1k })
+            .expect(CHANNEL_CLOSED_MESSAGE);
+    }
+
+    pub fn prove(&self, max_nodes: usize) {
+        self.sender
+            .send(Message::Prove { max_nodes })
+            .expect(CHANNEL_CLOSED_MESSAGE);
+    }
+
+    pub fn suite(&self, suite: TestSuite, nodes_in_1k: u32) {
+        self.sender
+            .send(Message::Suite { suite, nodes_in_1k })
+            .expect(CHANNEL_CLOSED_MESSAGE);
+    }
+
+    pub fn bench(&self, depth: Depth) {
+        self.sender
+            .send(Message::Bench { depth })
+            .expect(CHANNEL_CLOSED_MESSAGE);
+    }
+
+    pub fn defenses(&self) {
+        self.sender
+            .send(Message::Defenses)
+            .expect(CHANNEL_CLOSED_MESSAGE);
+    }
+
+    pub fn rank_moves(&self, count: usize) {
+        self.sender
+            .send(Message::RankMoves { count })
+            .expect(CHANNEL_CLOSED_MESSAGE);
+    }
+
+    pub fn balance_one(&self, target: Score) {
+        self.sender
+            .send(Message::BalanceOne { target })
+            .expect(CHANNEL_CLOSED_MESSAGE);
+    }
+
+    pub fn balance_two(&self, target: Score) {
+        self.sender
+            .send(Message::BalanceTwo { target })
+            .expect(CHANNEL_CLOSED_MESSAGE);
+    }
+
+    pub fn swap2(&self) {
+        self.sender
+            .send(Message::Swap2)
+            .expect(CHANNEL_CLOSED_MESSAGE);
+    }
 }
@@ -1,11 +1,16 @@
+use mintaka::bench::BENCH_DEPTH;
 use mintaka::config::{Config, SearchObjective};
+use mintaka::balance::Swap2Decision;
 use mintaka::game_agent::{ComputingResource, GameAgent, GameError};
 use mintaka::protocol::command::Command;
 use mintaka::protocol::response::{CallBackResponseSender, Response};
-use mintaka_interface::message::{ConfigCommand, Message, MessageCommand, MessageSender, StatusCommand};
+#[cfg(feature = "config-file")]
+use mintaka_interface::config_file::ConfigFile;
+use mintaka_interface::message::{ConfigCommand, Message, MessageCommand, MessageSender, RuleSelection, StatusCommand, TimeControlUpdate};
 use rusty_renju::notation::pos;
 use rusty_renju::notation::pos::Pos;
 use rusty_renju::notation::rule::RuleKind;
+use rusty_renju::notation::score::{Score, Scores};
 use rusty_renju::utils::byte_size::ByteSize;
 use std::error::Error;
 use std::io::{BufRead, Write};
@@ -13,12 +18,36 @@ use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::{mpsc, Arc};
 use std::time::{Duration, Instant};
 use mintaka::game_state::GameState;
-use mintaka::protocol::timer::Timer;
+use mintaka::protocol::timer::{TimeControl, Timer};
+use mintaka::value::Depth;
+use rusty_renju::history::History;
 use rusty_renju::notation::color::Color;
 use rusty_renju::utils::empty::Empty;
 
-pub fn entry<const R: RuleKind>() -> Result<(), impl Error> {
-    piskvork_protocol::<R>()
+pub fn entry(rule: RuleSelection) -> Result<(), impl Error> {
+    // `pbrain-mintaka_renju-15 bench [depth]` runs the fixed benchmark instead of the protocol
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    if args.first().is_some_and(|arg| arg == "bench") {
+        let depth = match args.get(1).map(|depth| depth.parse::<Depth>()).transpose() {
+            Ok(depth) => depth.unwrap_or(BENCH_DEPTH),
+            Err(_) => {
+                stdio_out(Err("invalid depth".to_string()));
+                return Ok(());
+            }
+        };
+
+        let report = mintaka::bench::bench::<Instant>(depth, |result| println!(
+            "{} {}: best={} score={} nodes={} elapsed={:?}",
+            result.rule, result.history, result.best_move, result.score, result.nodes, result.time_elapsed,
+        ));
+
+        println!("{report}");
+
+        return Ok(());
+    }
+
+    piskvork_protocol(rule)
 }
 
 const PROTOCOL_MARGIN: Duration = Duration::from_millis(30);
@@ -30,6 +59,8 @@ enum PiskvorkResponse {
     About(String),
     Pos(Pos),
     Forbid(Vec<Pos>),
+    Positions(Vec<Pos>),
+    Swap,
     Ok,
 }
 
@@ -50,6 +81,9 @@ fn stdio_out(piskvork_response: Result<PiskvorkResponse, String>) {
         Ok(PiskvorkResponse::Ok) => {
             println!("OK");
         }
+        Ok(PiskvorkResponse::Swap) => {
+            println!("SWAP");
+        }
         Ok(PiskvorkResponse::Pos(pos)) => {
             println!("{},{}", pos.col(), pos.row());
         }
@@ -61,6 +95,14 @@ fn stdio_out(piskvork_response: Result<PiskvorkResponse, String>) {
                      .join("")
             );
         }
+        Ok(PiskvorkResponse::Positions(positions)) => {
+            println!("{}",
+                 positions.iter()
+                     .map(|pos| format!("{},{}", pos.col(), pos.row()))
+                     .collect::<Vec<_>>()
+                     .join(" ")
+            );
+        }
         Err(message) => {
             println!("ERROR {}", message);
         }
@@ -69,7 +111,17 @@ fn stdio_out(piskvork_response: Result<PiskvorkResponse, String>) {
     std::io::stdout().flush().expect("failed to flush stdout");
 }
 
-fn print_response(response: Response) {
+fn print_response(response: Response, realtime: bool) {
+    // yixin-style clients follow the search through the realtime channel
+    if realtime
+        && let Response::Status { best_move, score, .. } = &response
+        && let Some(best_move) = best_move.ok()
+    {
+        let kind = if Score::is_losing(*score) { "LOSE" } else { "BEST" };
+
+        stdio_out(Ok(PiskvorkResponse::Message(format!("REALTIME {kind} {},{}", best_move.col(), best_move.row()))));
+    }
+
     let response = match response {
         Response::Begins(ComputingResource { workers, time_limit, nodes_in_1k }) =>
             format!(
@@ -83,22 +135,50 @@ fn print_response(response: Response) {
                 total_nodes_in_1k={total_nodes_in_1k}, \
                 pv={pv:?}"
             ),
+        // too verbose for the piskvork message channel
+        Response::Telemetry(_) => return,
     };
 
     stdio_out(Ok(PiskvorkResponse::Debug(response)));
 }
 
-fn piskvork_protocol<const R: RuleKind>() -> Result<(), impl Error> {
+// carried over when the manager switches the rule
+#[derive(Copy, Clone)]
+struct Session {
+    config: Config,
+    timer: Timer,
+    realtime: bool,
+    history: History,
+}
+
+fn piskvork_protocol(rule: RuleSelection) -> Result<(), impl Error> {
     let aborted = Arc::new(AtomicBool::new(false));
 
-    let mut config = Presets::FASTGAME_PRESET;
+    let config = Presets::FASTGAME_PRESET;
 
-    let mut game_agent = GameAgent::<R>::new(config);
+    let realtime = false;
 
-    let mut timer = Timer {
+    let timer = Timer {
         total_remaining: Some(Duration::from_secs(180)),
         increment: Duration::ZERO,
         turn: Some(Duration::from_secs(30)),
+        control: TimeControl::Fischer,
+    };
+
+    // managers start brains without arguments, so the file is the only way to configure them up front
+    #[cfg(feature = "config-file")]
+    let (config, timer, realtime) = match ConfigFile::discover().map(Option::unwrap_or_default) {
+        Ok(file) => match file.apply(config) {
+            Ok(file_config) => (file_config, file.apply_timer(timer), file.protocol.realtime.unwrap_or(realtime)),
+            Err(err) => {
+                stdio_out(Err(err.to_string()));
+                (config, timer, realtime)
+            },
+        },
+        Err(err) => {
+            stdio_out(Err(err.to_string()));
+            (config, timer, realtime)
+        },
     };
 
     let (message_sender, message_receiver) = {
@@ -106,9 +186,41 @@ fn piskvork_protocol<const R: RuleKind>() -> Result<(), impl Error> {
         (MessageSender::new(tx), rx)
     };
 
-    spawn_command_listener::<R>(aborted.clone(), message_sender);
+    spawn_command_listener(aborted.clone(), message_sender, rule);
+
+    let mut session = Session { config, timer, realtime, history: History::empty() };
+
+    let mut rule = rule.rule;
+
+    loop {
+        let next_rule = match rule {
+            RuleKind::Renju => engine_loop::<{ RuleKind::Renju }>(&mut session, &message_receiver, &aborted),
+            RuleKind::Gomoku => engine_loop::<{ RuleKind::Gomoku }>(&mut session, &message_receiver, &aborted),
+            RuleKind::Freestyle => engine_loop::<{ RuleKind::Freestyle }>(&mut session, &message_receiver, &aborted),
+        };
 
-    for message in message_receiver {
+        match next_rule {
+            Some(next_rule) => rule = next_rule,
+            None => break,
+        }
+    }
+
+    Ok::<(), GameError>(())
+}
+
+fn engine_loop<const R: RuleKind>(
+    session: &mut Session,
+    message_receiver: &mpsc::Receiver<Message>,
+    aborted: &Arc<AtomicBool>,
+) -> Option<RuleKind> {
+    let Session { mut config, mut timer, mut realtime, history } = *session;
+
+    let mut game_agent = GameAgent::<R>::from_state(config, history.into());
+
+    // the opponent's clock runs from our last move until the next turn, unless the manager reports it
+    let mut opponent_turn_started: Option<Instant> = None;
+
+    for message in message_receiver.iter() {
         match message {
             Message::Command(command) => {
                 let result = game_agent.command(command.into_command(&config, game_agent.state.board.hash_key));
@@ -118,11 +230,15 @@ fn piskvork_protocol<const R: RuleKind>() -> Result<(), impl Error> {
                 }
             }
             Message::Launch { objective, apply, .. } => {
+                if let Some(turn_started) = opponent_turn_started.take() {
+                    timer.opponent_consume(turn_started.elapsed());
+                }
+
                 let best_move = game_agent.launch::<Instant>(
                     config,
                     timer,
                     objective,
-                    CallBackResponseSender::new(print_response),
+                    CallBackResponseSender::new(move |response| print_response(response, realtime)),
                     Arc::new(AtomicU32::new(0)),
                     aborted.clone(),
                 );
@@ -138,12 +254,17 @@ fn piskvork_protocol<const R: RuleKind>() -> Result<(), impl Error> {
                         stdio_out(Err(err.to_string()));
                         continue;
                     }
+
+                    timer.consume(best_move.time_elapsed);
+                    timer.apply_increment();
+                    opponent_turn_started = Some(Instant::now());
                 }
 
                 stdio_out(Ok(PiskvorkResponse::Pos(best_move.best_move.unwrap_or(Pos::from_cartesian(7, 7)))));
             }
             Message::Config(ConfigCommand::TotalTime(total)) => {
                 timer.total_remaining = Some(total);
+                opponent_turn_started = None;
             }
             Message::Config(ConfigCommand::IncrementTime(increment)) => {
                 config.initial_timer.increment = increment;
@@ -153,6 +274,10 @@ fn piskvork_protocol<const R: RuleKind>() -> Result<(), impl Error> {
                 config.initial_timer.turn = Some(turn);
                 timer.turn = Some(turn)
             }
+            Message::Config(ConfigCommand::TimeControl(update)) => {
+                config.initial_timer.control = update.apply(config.initial_timer.control);
+                timer.control = update.apply(timer.control);
+            }
             Message::Config(ConfigCommand::MaxNodes { in_1k }) => {
                 config.max_nodes_in_1k = Some(in_1k);
             }
@@ -163,6 +288,41 @@ fn piskvork_protocol<const R: RuleKind>() -> Result<(), impl Error> {
                 config.tt_size = size;
                 let _ = game_agent.command(Command::RebuildTT(config.tt_size));
             }
+            Message::Config(ConfigCommand::ClearTT) => {
+                let _ = game_agent.command(Command::RebuildTT(config.tt_size));
+            }
+            Message::Config(ConfigCommand::Realtime(enabled)) => {
+                realtime = enabled;
+            }
+            Message::Config(ConfigCommand::Rule(rule)) => {
+                if rule != R {
+                    *session = Session { config, timer, realtime, history: game_agent.state.history };
+
+                    return Some(rule);
+                }
+            }
+            // time limits always come through INFO, so only the engine and protocol sections apply
+            Message::Config(ConfigCommand::Folder(folder)) => {
+                #[cfg(feature = "config-file")]
+                match ConfigFile::load_from_folder(&folder) {
+                    Ok(Some(file)) => match file.apply_engine(config) {
+                        Ok(file_config) => {
+                            if file_config.tt_size != config.tt_size {
+                                let _ = game_agent.command(Command::RebuildTT(file_config.tt_size));
+                            }
+
+                            config = file_config;
+                            realtime = file.protocol.realtime.unwrap_or(realtime);
+                        },
+                        Err(err) => stdio_out(Err(err.to_string())),
+                    },
+                    Ok(None) => {},
+                    Err(err) => stdio_out(Err(err.to_string())),
+                }
+
+                #[cfg(not(feature = "config-file"))]
+                let _ = folder;
+            }
             Message::Config(_) => unreachable!(),
             Message::Status(StatusCommand::Forbid) => {
                 stdio_out(Ok(PiskvorkResponse::Forbid(
@@ -177,11 +337,77 @@ fn piskvork_protocol<const R: RuleKind>() -> Result<(), impl Error> {
                     )
                 )));
             }
-            Message::Status(_) => unreachable!()
+            Message::Status(StatusCommand::Info) => {
+                let info = [
+                    format!("MAX_THREAD_NUM {}", std::thread::available_parallelism().map_or(1, usize::from)),
+                    format!("THREAD_NUM {}", config.workers),
+                    format!("MAX_MEMORY {}", config.tt_size.bytes()),
+                    format!("RULE {}", R),
+                ];
+
+                for line in info {
+                    stdio_out(Ok(PiskvorkResponse::Message(format!("INFO {line}"))));
+                }
+            }
+            // no opening database is bundled, every query comes back empty
+            Message::Status(StatusCommand::Database) => {
+                stdio_out(Ok(PiskvorkResponse::About("DATABASE DONE".to_string())));
+            }
+            Message::Status(_) => unreachable!(),
+            Message::Analyze { .. } => unreachable!(),
+            Message::Prove { .. } => unreachable!(),
+            Message::Suite { .. } => unreachable!(),
+            Message::Bench { .. } => unreachable!(),
+            Message::Defenses => {
+                let defenses = game_agent.vcf_defenses::<Instant>(config)
+                    .map(|result| result.defenses.iter().map(|defense| defense.defense).collect())
+                    .unwrap_or_default();
+
+                stdio_out(Ok(PiskvorkResponse::Positions(defenses)));
+            }
+            Message::RankMoves { count } => {
+                let move_scores = game_agent.rank_moves::<Instant>(config, count, aborted.clone());
+
+                for move_score in move_scores.iter() {
+                    stdio_out(Ok(PiskvorkResponse::Message(format!(
+                        "REALTIME BEST {},{} {}", move_score.pos.col(), move_score.pos.row(), move_score.score
+                    ))));
+                }
+
+                match move_scores.first() {
+                    Some(move_score) => stdio_out(Ok(PiskvorkResponse::Pos(move_score.pos))),
+                    None => stdio_out(Err("no legal move".to_string())),
+                }
+            }
+            Message::BalanceOne { target } => {
+                match game_agent.balance_one::<Instant>(config, target, aborted.clone()) {
+                    Some(move_score) => stdio_out(Ok(PiskvorkResponse::Pos(move_score.pos))),
+                    None => stdio_out(Err("no balancing move".to_string())),
+                }
+            }
+            Message::BalanceTwo { target } => {
+                match game_agent.balance_two::<Instant>(config, target, aborted.clone()) {
+                    Some(move_scores) => stdio_out(Ok(PiskvorkResponse::Positions(
+                        move_scores.iter().map(|move_score| move_score.pos).collect()
+                    ))),
+                    None => stdio_out(Err("no balancing moves".to_string())),
+                }
+            }
+            Message::Swap2 => {
+                let response = match game_agent.swap2_decision::<Instant>(config, aborted.clone()) {
+                    Some(Swap2Decision::Place(stones)) => Ok(PiskvorkResponse::Positions(stones.to_vec())),
+                    Some(Swap2Decision::Swap) => Ok(PiskvorkResponse::Swap),
+                    Some(Swap2Decision::Play(pos)) => Ok(PiskvorkResponse::Pos(pos)),
+                    Some(Swap2Decision::Add(stones)) => Ok(PiskvorkResponse::Positions(stones.to_vec())),
+                    None => Err("no swap2 decision".to_string()),
+                };
+
+                stdio_out(response);
+            }
         }
     }
 
-    Ok::<(), GameError>(())
+    None
 }
 
 // https://plastovicka.github.io/protocl2en.htm
@@ -190,6 +416,7 @@ fn match_command<const R: RuleKind>(
     aborted: &Arc<AtomicBool>,
     message_sender: &MessageSender,
     args: Vec<&str>,
+    rule: &mut RuleSelection,
 ) -> Result<(), &'static str> {
     let command_kind = args[0].to_uppercase();
 
@@ -227,6 +454,44 @@ fn match_command<const R: RuleKind>(
                         return Err("invalid time value");
                     }
                 }
+                // mintaka extensions for time controls the piskvork protocol has no keys for
+                Some("time_increment") => {
+                    message_sender.config(ConfigCommand::IncrementTime(parse_time(&args)?));
+                }
+                Some("byoyomi_periods") => {
+                    let periods = args.get(2).and_then(|value| value.parse::<u32>().ok())
+                        .ok_or("invalid periods value")?;
+
+                    message_sender.config(ConfigCommand::TimeControl(TimeControlUpdate::ByoYomiPeriods(periods)));
+                }
+                Some("byoyomi_time") => {
+                    message_sender.config(ConfigCommand::TimeControl(TimeControlUpdate::ByoYomiPeriodTime(parse_time(&args)?)));
+                }
+                Some("time_delay") => {
+                    let delay = parse_time(&args)?;
+
+                    message_sender.config(ConfigCommand::TimeControl(TimeControlUpdate::Replace(TimeControl::SimpleDelay { delay })));
+                }
+                Some("time_bronstein") => {
+                    let delay = parse_time(&args)?;
+
+                    message_sender.config(ConfigCommand::TimeControl(TimeControlUpdate::Replace(TimeControl::Bronstein { delay })));
+                }
+                Some("moves_to_go") => {
+                    let moves_to_go = args.get(2).and_then(|value| value.parse::<u32>().ok())
+                        .ok_or("invalid moves value")?;
+
+                    message_sender.config(ConfigCommand::TimeControl(TimeControlUpdate::MovesToGo(moves_to_go)));
+                }
+                Some("hourglass") => {
+                    let control = match args.get(2).copied() {
+                        Some("1") => TimeControl::Hourglass,
+                        Some("0") => TimeControl::Fischer,
+                        _ => return Err("invalid hourglass value"),
+                    };
+
+                    message_sender.config(ConfigCommand::TimeControl(TimeControlUpdate::Replace(control)));
+                }
                 Some("max_memory") => {
                     if let Some(max_memory_in_bytes) = args.get(2)
                         && let Some(max_memory_in_bytes) = max_memory_in_bytes.parse::<u64>().ok()
@@ -245,22 +510,29 @@ fn match_command<const R: RuleKind>(
                     }
                 }
                 Some("game_type") => {
-                    let _ = args.get(2);
+                    // human, brain, tournament and network tournament opponents are all played the same
+                    match args.get(2).and_then(|value| value.parse::<u32>().ok()) {
+                        Some(0 ..= 3) => {},
+                        _ => return Err("invalid game type value"),
+                    }
+                }
+                Some("folder") => {
+                    let folder = args.get(2 ..)
+                        .filter(|tokens| !tokens.is_empty())
+                        .ok_or("folder not provided")?
+                        .join(" ");
+
+                    message_sender.config(ConfigCommand::Folder(folder.into()));
                 }
                 Some("rule") => {
-                    if let Some(rule) = args.get(2).and_then(|value| value.parse::<usize>().ok()) {
-                        let rule_kind = match rule {
-                            1 => RuleKind::Gomoku,
-                            2 | 4 => RuleKind::Renju,
-                            _ => return Err("unsupported rule"),
-                        };
-
-                        if rule_kind != R {
-                            return Err("unsupported rule");
-                        }
-                    } else {
-                        return Err("invalid rule value");
-                    }
+                    let rule_flags = args.get(2).and_then(|value| value.parse::<u32>().ok())
+                        .ok_or("invalid rule value")?;
+
+                    let rule_kind = parse_rule_flags(rule_flags)?;
+
+                    rule.switch(rule_kind)?;
+
+                    message_sender.config(ConfigCommand::Rule(rule_kind));
                 }
                 _ => return Err("unknown info token"),
             }
@@ -322,6 +594,38 @@ fn match_command<const R: RuleKind>(
                 message_sender.launch(SearchObjective::Best, true, false);
             }
         }
+        "SWAP2BOARD" => {
+            const DONE_TOKEN: &str = "DONE";
+
+            let mut game_state = GameState::<R>::empty();
+
+            let mut buf = String::new();
+            loop {
+                buf.clear();
+                std::io::stdin()
+                    .read_line(&mut buf)
+                    .map_err(|_| "failed to stdio")?;
+
+                if buf.trim() == DONE_TOKEN {
+                    break;
+                }
+
+                let pos = parse_pos_token(buf.trim())?;
+
+                if !game_state.board.is_legal_move(pos) {
+                    return Err("illegal move");
+                }
+
+                game_state.play_mut(pos);
+            }
+
+            if !matches!(game_state.board.stones, 0 | 3 | 5) {
+                return Err("unsupported swap2 stage");
+            }
+
+            message_sender.command(MessageCommand::Command(Command::Init(Box::new((&game_state).into()))));
+            message_sender.swap2();
+        }
         "TURN" => {
             let pos = parse_command_pos(&args)?;
 
@@ -350,15 +654,48 @@ fn match_command<const R: RuleKind>(
         "YXSHOWFORBID" => {
             message_sender.status(StatusCommand::Forbid);
         }
+        "YXSHOWINFO" => {
+            message_sender.config(ConfigCommand::Realtime(true));
+            message_sender.status(StatusCommand::Info);
+        }
+        "YXHASHCLEAR" => {
+            message_sender.config(ConfigCommand::ClearTT);
+        }
+        "YXNBEST" => {
+            let count = args.get(1)
+                .ok_or("missing count token.")?
+                .parse::<usize>()
+                .map_err(|_| "count parsing failed.")?;
+
+            message_sender.rank_moves(count);
+        }
+        "YXBALANCEONE" | "YXBALANCETWO" => {
+            let target = args.get(1)
+                .map_or(Ok(0), |token| token.parse::<Score>())
+                .map_err(|_| "score parsing failed.")?;
+
+            if command_kind.as_str() == "YXBALANCEONE" {
+                message_sender.balance_one(target);
+            } else {
+                message_sender.balance_two(target);
+            }
+        }
+        "YXSEARCHDEFEND" => {
+            message_sender.defenses();
+        }
+        "YXQUERYDATABASEALL" | "YXQUERYDATABASEONE" | "YXQUERYDATABASETEXT" => {
+            message_sender.status(StatusCommand::Database);
+        }
         &_ => return Err("unknown command."),
     }
 
     Ok(())
 }
 
-fn spawn_command_listener<const R: RuleKind>(
+fn spawn_command_listener(
     aborted: Arc<AtomicBool>,
     message_sender: MessageSender,
+    mut rule: RuleSelection,
 ) {
     std::thread::spawn(move || {
         let stdin = std::io::stdin();
@@ -371,7 +708,11 @@ fn spawn_command_listener<const R: RuleKind>(
                 continue;
             }
 
-            let result = match_command::<R>(&aborted, &message_sender, args);
+            let result = match rule.rule {
+                RuleKind::Renju => match_command::<{ RuleKind::Renju }>(&aborted, &message_sender, args, &mut rule),
+                RuleKind::Gomoku => match_command::<{ RuleKind::Gomoku }>(&aborted, &message_sender, args, &mut rule),
+                RuleKind::Freestyle => match_command::<{ RuleKind::Freestyle }>(&aborted, &message_sender, args, &mut rule),
+            };
 
             if let Err(error) = result {
                 stdio_out(Err(error.to_string()));
@@ -410,6 +751,23 @@ fn parse_pos(x: &str, y: &str) -> Result<Pos, &'static str> {
     }
 }
 
+// bit 0 exact five, bit 1 continuous game, bit 2 renju, bit 3 caro
+fn parse_rule_flags(rule: u32) -> Result<RuleKind, &'static str> {
+    const EXACT_FIVE: u32 = 0b0001;
+    const RENJU: u32 = 0b0100;
+    const CARO: u32 = 0b1000;
+
+    if rule & CARO != 0 {
+        Err("caro rule is not supported")
+    } else if rule & RENJU != 0 {
+        Ok(RuleKind::Renju)
+    } else if rule & EXACT_FIVE != 0 {
+        Ok(RuleKind::Gomoku)
+    } else {
+        Ok(RuleKind::Freestyle)
+    }
+}
+
 fn parse_time(parameters: &Vec<&str>) -> Result<Duration, &'static str> {
     parameters
         .get(2)
@@ -434,8 +792,11 @@ impl Presets {
             total_remaining: Some(Duration::from_secs(120)),
             increment: Duration::ZERO,
             turn: Some(Duration::from_secs(5)),
+            control: TimeControl::Fischer,
         },
         spawn_depth_specialist: false,
+        skill_level: None,
+        deterministic: false,
     };
 
     const STANDARD_PRESET: Config = Config {
@@ -451,8 +812,11 @@ impl Presets {
             total_remaining: Some(Duration::from_secs(180)),
             increment: Duration::ZERO,
             turn: Some(Duration::from_secs(30)),
+            control: TimeControl::Fischer,
         },
         spawn_depth_specialist: false,
+        skill_level: None,
+        deterministic: false,
     };
 
     const FINAL_PRESET: Config = Config {
@@ -468,7 +832,10 @@ impl Presets {
             total_remaining: Some(Duration::from_secs(1000)),
             increment: Duration::ZERO,
             turn: Some(Duration::from_secs(300)),
+            control: TimeControl::Fischer,
         },
         spawn_depth_specialist: false,
+        skill_level: None,
+        deterministic: false,
     };
 }
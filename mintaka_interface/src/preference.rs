@@ -1,4 +1,6 @@
 use clap::Parser;
+#[cfg(feature = "config-file")]
+use crate::config_file::ConfigFile;
 use mintaka::config::Config;
 use mintaka::game_state::{GameState, GameStateData};
 use rusty_renju::board::Board;
@@ -28,6 +30,15 @@ pub struct Preference<const R: RuleKind> {
     pub pondering: bool,
     #[arg(long)]
     pub command_sequence: Option<String>,
+    #[cfg(feature = "config-file")]
+    #[arg(short, long)]
+    pub config_file: Option<std::path::PathBuf>,
+    // read ahead of the other flags by multi-rule binaries, see `rule_arg`
+    #[arg(long)]
+    pub rule: Option<RuleKind>,
+    #[cfg(feature = "tui")]
+    #[arg(long)]
+    pub tui: bool,
     #[clap(skip)]
     pub game_state: Option<GameState<R>>,
     #[clap(skip)]
@@ -51,26 +62,66 @@ impl<const R: RuleKind> Preference<R> {
             self.game_state = Some(GameStateData { board_data: (&board).into(), history }.into());
         }
 
+        self.config.workers = std::thread::available_parallelism()
+            .map_or_else(|_| 1, |n| n.get()) as u32;
+
+        // flags override the config file, which overrides the defaults
+        #[cfg(feature = "config-file")]
+        self.load_config_file();
+
         if let Some(&[total_time_in_ms, increment_time_in_ms, turn_time_in_ms]) = self.time.as_deref() {
             self.config.initial_timer.total_remaining = (total_time_in_ms != 0).then_some(Duration::from_millis(total_time_in_ms as u64));
             self.config.initial_timer.turn = (turn_time_in_ms != 0).then_some(Duration::from_millis(turn_time_in_ms as u64));
             self.config.initial_timer.increment = Duration::from_millis(increment_time_in_ms as u64);
         }
 
-        self.config.pondering = self.pondering;
+        self.config.pondering |= self.pondering;
 
         if let Some(memory_in_mib) = self.memory_in_mib {
             self.config.tt_size = ByteSize::from_mib(memory_in_mib as u64);
         }
 
-        match self.workers {
-            Some(workers) => self.config.workers = workers,
-            None => self.config.workers = std::thread::available_parallelism()
-                .map_or_else(|_| 1, |n| n.get()) as u32,
+        if let Some(workers) = self.workers {
+            self.config.workers = workers;
         }
 
-        self.config.max_nodes_in_1k = self.nodes_in_1k;
+        self.config.max_nodes_in_1k = self.nodes_in_1k.or(self.config.max_nodes_in_1k);
 
         self.config = self.config.validate().unwrap();
     }
+
+    #[cfg(feature = "config-file")]
+    fn load_config_file(&mut self) {
+        let file = match &self.config_file {
+            Some(path) => ConfigFile::load(path).map(Some),
+            None => ConfigFile::discover(),
+        };
+
+        let file = file
+            .unwrap_or_else(|err| <Self as clap::CommandFactory>::command().error(clap::error::ErrorKind::Io, err).exit())
+            .unwrap_or_default();
+
+        self.config = file.apply(self.config)
+            .unwrap_or_else(|err| <Self as clap::CommandFactory>::command().error(clap::error::ErrorKind::ValueValidation, err).exit());
+
+        if self.command_sequence.is_none() {
+            self.command_sequence = file.protocol.command_sequence;
+        }
+    }
+}
+
+pub fn rule_arg() -> Option<String> {
+    let mut args = std::env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        if arg == "--rule" {
+            return args.next();
+        }
+
+        if let Some(rule) = arg.strip_prefix("--rule=") {
+            return Some(rule.to_string());
+        }
+    }
+
+    None
 }
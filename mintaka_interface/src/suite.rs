@@ -0,0 +1,81 @@
+use clap::Parser;
+use mintaka::config::Config;
+use mintaka::game_agent::GameAgent;
+use mintaka::test_suite::{SuiteParseError, TestSuite};
+use rusty_renju::notation::rule::RuleKind;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[derive(Parser)]
+struct SuiteArgs {
+    file: String,
+    #[arg(short, long, default_value_t = 100)]
+    nodes_in_1k: u32,
+    #[arg(short, long)]
+    time_in_ms: Option<u64>,
+    #[arg(short, long, default_value_t = 1)]
+    workers: u32,
+}
+
+#[derive(Debug)]
+pub enum SuiteError {
+    Io(std::io::Error),
+    Parse(SuiteParseError),
+    Failed { passed: usize, total: usize },
+}
+
+impl Display for SuiteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SuiteError::Io(err) => write!(f, "{err}"),
+            SuiteError::Parse(err) => write!(f, "{err}"),
+            SuiteError::Failed { passed, total } => write!(f, "{} of {total} cases failed", total - passed),
+        }
+    }
+}
+
+impl std::error::Error for SuiteError {}
+
+pub fn entry<const R: RuleKind>() -> Result<(), SuiteError> {
+    let args = SuiteArgs::parse();
+
+    let suite: TestSuite = std::fs::read_to_string(&args.file)
+        .map_err(SuiteError::Io)?
+        .parse()
+        .map_err(SuiteError::Parse)?;
+
+    let mut config = Config::default();
+    config.workers = args.workers;
+
+    // a time budget replaces the node budget
+    match args.time_in_ms {
+        Some(time_in_ms) => config.initial_timer.turn = Some(Duration::from_millis(time_in_ms)),
+        None => config.max_nodes_in_1k = Some(args.nodes_in_1k),
+    }
+
+    let report = GameAgent::<R>::run_suite::<Instant>(
+        config,
+        &suite,
+        |result| println!(
+            "{} {}: best={} score={} nodes={}k elapsed={:?}{}",
+            if result.passed { "pass" } else { "fail" },
+            result.id,
+            result.best_move,
+            result.score.map_or("-".to_string(), |score| score.to_string()),
+            result.total_nodes_in_1k,
+            result.time_elapsed,
+            result.error.as_ref().map_or(String::new(), |err| format!(" error={err}")),
+        ),
+        Arc::new(AtomicBool::new(false)),
+    );
+
+    println!("{report}");
+
+    if report.passed() != report.results.len() {
+        return Err(SuiteError::Failed { passed: report.passed(), total: report.results.len() });
+    }
+
+    Ok(())
+}
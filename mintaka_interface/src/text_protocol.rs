@@ -1,15 +1,21 @@
+use mintaka::bench::BENCH_DEPTH;
 use mintaka::config::{Config, SearchObjective};
 use mintaka::game_agent::{ComputingResource, GameAgent, GameError};
 use mintaka::game_state::{GameState, GameStateData};
 use mintaka::protocol::command::Command;
-use mintaka::protocol::response::{CallBackResponseSender, Response};
-use mintaka_interface::message::{ConfigCommand, Message, MessageCommand, MessageSender, StatusCommand};
+use mintaka::protocol::response::{CallBackResponseSender, Response, SearchTelemetry};
+use mintaka::protocol::timer::{TimeControl, Timer};
+use mintaka::test_suite::{SuiteParseError, TestSuite};
+use mintaka_interface::message::{ConfigCommand, Message, MessageCommand, MessageSender, RuleSelection, StatusCommand, TimeControlUpdate};
+use mintaka_interface::preference;
 use mintaka_interface::preference::Preference;
 use rusty_renju::board::Board;
 use rusty_renju::history::History;
+use rusty_renju::history_format::HistoryFormat;
 use rusty_renju::notation::color::UnknownColorError;
-use rusty_renju::notation::pos::{MaybePos, PosError};
+use rusty_renju::notation::pos::{MaybePos, Pos, PosError};
 use rusty_renju::notation::rule::RuleKind;
+use rusty_renju::notation::score::Score;
 use rusty_renju::utils::byte_size::ByteSize;
 use rusty_renju::utils::empty::Empty;
 use std::io::{BufRead, Write};
@@ -18,9 +24,36 @@ use std::sync::{mpsc, Arc};
 use std::time::{Duration, Instant};
 use mintaka::value::Depth;
 
-pub fn entry<const R: RuleKind>() -> Result<(), GameError> {
+const ANALYSIS_NODES_IN_1K: u32 = 200;
+const PROOF_MAX_NODES: usize = 10_000_000;
+const SUITE_NODES_IN_1K: u32 = 100;
+
+pub fn entry(rule: RuleSelection) -> Result<(), GameError> {
+    let mut rule = rule;
+
+    // the board and history flags are parsed with the rule, so it has to be known first
+    if let Some(arg_rule) = preference::rule_arg().and_then(|arg_rule| arg_rule.parse().ok())
+        && let Err(err) = rule.switch(arg_rule)
+    {
+        stdio_out(Err(err.to_string()));
+        return Ok(());
+    }
+
+    match rule.rule {
+        RuleKind::Renju => entry_with::<{ RuleKind::Renju }>(rule),
+        RuleKind::Gomoku => entry_with::<{ RuleKind::Gomoku }>(rule),
+        RuleKind::Freestyle => entry_with::<{ RuleKind::Freestyle }>(rule),
+    }
+}
+
+fn entry_with<const R: RuleKind>(rule: RuleSelection) -> Result<(), GameError> {
     let pref = Preference::<R>::parse();
 
+    #[cfg(feature = "tui")]
+    if pref.tui {
+        return crate::tui::run(pref.config, pref.game_state.unwrap_or_else(|| GameState::empty()));
+    }
+
     let command_sequence: Vec<String> = pref
         .command_sequence
         .map(|sequence| {
@@ -34,8 +67,9 @@ pub fn entry<const R: RuleKind>() -> Result<(), GameError> {
 
     text_protocol(
         pref.config,
-        pref.game_state.unwrap_or_else(|| GameState::empty()),
+        pref.game_state.map_or_else(History::empty, |state| state.history),
         command_sequence,
+        rule,
     )
 }
 
@@ -74,6 +108,20 @@ fn print_response(response: Response) {
             format!("begins: workers={workers}, running-time={time_limit:?}, nodes={nodes_in_1k:?}"),
         Response::Status { best_move, score, pv, total_nodes_in_1k, selective_depth, .. } =>
             format!("status: depth={selective_depth}, score={score}, best_move={best_move}, total_nodes_in_1k={total_nodes_in_1k}, pv={pv:?}"),
+        Response::Telemetry(SearchTelemetry { nps_in_1k, hash_full_permille, thread_depths, statics, .. }) => {
+            let mut log = format!("telemetry: nps_in_1k={nps_in_1k}, hashfull={hash_full_permille}, depths={thread_depths:?}");
+
+            if let Some(statics) = statics {
+                log.push_str(&format!(
+                    ", search_nodes_in_1k={}, vcf_nodes_in_1k={}, tt_hit_rate={:.3}, tt_cutoff_rate={:.3}, beta_cutoff_rate={:.3}, avg_cutoff_distance={:.2}",
+                    statics.search_nodes_in_1k, statics.vcf_nodes_in_1k,
+                    statics.tt_hit_rate, statics.tt_cutoff_rate,
+                    statics.beta_cutoff_rate, statics.avg_cutoff_distance,
+                ));
+            }
+
+            log
+        }
     };
 
     stdio_out(Ok(TextProtocolResponse::Log(log)));
@@ -89,25 +137,62 @@ fn print_board<const R: RuleKind>(state: &GameState<R>, show_last_moves: bool) {
     stdio_out(Ok(TextProtocolResponse::Multiline(string)));
 }
 
-fn text_protocol<const R: RuleKind>(
-    mut config: Config,
-    state: GameState<R>,
+// carried over when the rule is switched
+#[derive(Copy, Clone)]
+struct Session {
+    config: Config,
+    timer: Timer,
+    history: History,
+}
+
+fn text_protocol(
+    config: Config,
+    history: History,
     command_sequence: Vec<String>,
+    rule: RuleSelection,
 ) -> Result<(), GameError> {
     let aborted = Arc::new(AtomicBool::new(false));
 
-    let mut game_agent = GameAgent::from_state(config, state);
-
-    let mut timer = config.initial_timer;
-
     let (message_sender, message_receiver) = {
         let (tx, rx) = mpsc::channel();
         (MessageSender::new(tx), rx)
     };
 
-    spawn_command_listener::<R>(aborted.clone(), message_sender, command_sequence);
+    spawn_command_listener(aborted.clone(), message_sender, command_sequence, rule);
+
+    let mut session = Session { config, timer: config.initial_timer, history };
+
+    let mut rule = rule.rule;
+
+    loop {
+        let next_rule = match rule {
+            RuleKind::Renju => engine_loop::<{ RuleKind::Renju }>(&mut session, &message_receiver, &aborted),
+            RuleKind::Gomoku => engine_loop::<{ RuleKind::Gomoku }>(&mut session, &message_receiver, &aborted),
+            RuleKind::Freestyle => engine_loop::<{ RuleKind::Freestyle }>(&mut session, &message_receiver, &aborted),
+        };
+
+        match next_rule {
+            Some(next_rule) => rule = next_rule,
+            None => break,
+        }
+    }
+
+    Ok(())
+}
+
+fn engine_loop<const R: RuleKind>(
+    session: &mut Session,
+    message_receiver: &mpsc::Receiver<Message>,
+    aborted: &Arc<AtomicBool>,
+) -> Option<RuleKind> {
+    let Session { mut config, mut timer, history } = *session;
+
+    let mut game_agent = GameAgent::<R>::from_state(config, history.into());
+
+    // the opponent's clock runs from our last move until the next search, unless the clock is reported
+    let mut opponent_turn_started: Option<Instant> = None;
 
-    for message in message_receiver {
+    for message in message_receiver.iter() {
         match message {
             Message::Command(command) => {
                 let command = command.into_command(&config, game_agent.state.board.hash_key);
@@ -117,6 +202,10 @@ fn text_protocol<const R: RuleKind>(
                 stdio_out(response);
             }
             Message::Launch { objective, apply, print: interactive } => {
+                if let Some(turn_started) = opponent_turn_started.take() {
+                    timer.opponent_consume(turn_started.elapsed());
+                }
+
                 let best_move = game_agent.launch::<Instant>(
                     config,
                     timer,
@@ -148,6 +237,12 @@ fn text_protocol<const R: RuleKind>(
 
                     let response = execute_command(&mut game_agent, command);
 
+                    if response.is_ok() {
+                        timer.consume(best_move.time_elapsed);
+                        timer.apply_increment();
+                        opponent_turn_started = Some(Instant::now());
+                    }
+
                     stdio_out(response);
                 }
 
@@ -157,6 +252,7 @@ fn text_protocol<const R: RuleKind>(
             }
             Message::Config(ConfigCommand::TotalTime(total)) => {
                 timer.total_remaining = Some(total);
+                opponent_turn_started = None;
 
                 stdio_out(Ok(TextProtocolResponse::Ack));
             }
@@ -172,6 +268,12 @@ fn text_protocol<const R: RuleKind>(
 
                 stdio_out(Ok(TextProtocolResponse::Ack));
             }
+            Message::Config(ConfigCommand::TimeControl(update)) => {
+                config.initial_timer.control = update.apply(config.initial_timer.control);
+                timer.control = update.apply(timer.control);
+
+                stdio_out(Ok(TextProtocolResponse::Ack));
+            }
             Message::Config(ConfigCommand::MaxNodes { in_1k }) => {
                 config.max_nodes_in_1k = Some(in_1k);
 
@@ -187,6 +289,11 @@ fn text_protocol<const R: RuleKind>(
 
                 stdio_out(Ok(TextProtocolResponse::Ack));
             }
+            Message::Config(ConfigCommand::Deterministic(deterministic)) => {
+                config.deterministic = deterministic;
+
+                stdio_out(Ok(TextProtocolResponse::Ack));
+            }
             Message::Config(ConfigCommand::ResizeTT(size)) => {
                 config.tt_size = size;
 
@@ -205,17 +312,148 @@ fn text_protocol<const R: RuleKind>(
             Message::Status(StatusCommand::Board { show_last_moves }) => {
                 print_board::<R>(&game_agent.state, show_last_moves);
             }
-            Message::Status(StatusCommand::History) => {
-                stdio_out(Ok(TextProtocolResponse::Response(
-                    game_agent.state.history.to_string()
-                )));
+            Message::Status(StatusCommand::History { format }) => {
+                stdio_out(
+                    game_agent.state.history.to_string_with_format(format)
+                        .map(TextProtocolResponse::Response)
+                        .map_err(str::to_string)
+                );
             }
             Message::Status(StatusCommand::Forbid) => unreachable!(),
             Message::Status(StatusCommand::Time) => unreachable!(),
+            Message::Status(StatusCommand::Info | StatusCommand::Result | StatusCommand::Database) => unreachable!(),
+            Message::Analyze { nodes_in_1k } => {
+                // the analysis keeps a pending stop request, so one left from an earlier command is cleared here
+                aborted.store(false, Ordering::Relaxed);
+
+                let result = GameAgent::<R>::analyze_game::<Instant>(
+                    config,
+                    &game_agent.state.history,
+                    nodes_in_1k,
+                    None,
+                    |progress| if progress.checkpoint.is_none() {
+                        stdio_out(Ok(TextProtocolResponse::Log(format!(
+                            "analyzing: {}/{}", progress.analyzed_plies(), progress.total_plies()
+                        ))))
+                    },
+                    aborted.clone(),
+                );
+
+                match result {
+                    Ok(analysis) => {
+                        let report = analysis.moves.iter()
+                            .map(|analysis| format!(
+                                "{}. {:?} {} best={} score={}->{} win-rate={:+.3} {:?}",
+                                analysis.ply,
+                                analysis.color,
+                                analysis.played,
+                                analysis.best_move,
+                                analysis.score_before,
+                                analysis.score_after,
+                                analysis.win_rate_delta,
+                                analysis.labels,
+                            ))
+                            .collect::<Vec<_>>()
+                            .join("\n");
+
+                        stdio_out(Ok(TextProtocolResponse::Multiline(report)));
+                    },
+                    Err(err) => stdio_out(Err(err.to_string())),
+                }
+            }
+            Message::Prove { max_nodes } => {
+                match game_agent.prove_win::<Instant>(config, max_nodes, aborted.clone()) {
+                    Ok(tree) => {
+                        stdio_out(Ok(TextProtocolResponse::Log(format!(
+                            "proof: attacker={:?}, nodes={}, depth={}", tree.attacker, tree.nodes, tree.depth()
+                        ))));
+                        stdio_out(Ok(TextProtocolResponse::Response(tree.to_sgf())));
+                    },
+                    Err(err) => stdio_out(Err(err.to_string())),
+                }
+            }
+            Message::Defenses => {
+                match game_agent.vcf_defenses::<Instant>(config) {
+                    Some(result) => {
+                        let threat = result.threat.iter()
+                            .map(Pos::to_string)
+                            .collect::<Vec<_>>()
+                            .join(",");
+
+                        let defenses = result.defenses.iter()
+                            .map(|defense| format!("{} {:?}", defense.defense, defense.kind))
+                            .collect::<Vec<_>>()
+                            .join("\n");
+
+                        stdio_out(Ok(TextProtocolResponse::Log(format!("threat: {threat}"))));
+                        stdio_out(Ok(TextProtocolResponse::Multiline(defenses)));
+                    },
+                    None => stdio_out(Ok(TextProtocolResponse::Response("no vcf threat".to_string()))),
+                }
+            }
+            Message::Suite { suite, nodes_in_1k } => {
+                let mut suite_config = config;
+                suite_config.max_nodes_in_1k = Some(nodes_in_1k);
+
+                let report = GameAgent::<R>::run_suite::<Instant>(
+                    suite_config,
+                    &suite,
+                    |result| stdio_out(Ok(TextProtocolResponse::Log(format!(
+                        "{} {}: best={} score={} nodes={}k elapsed={:?}{}",
+                        if result.passed { "pass" } else { "fail" },
+                        result.id,
+                        result.best_move,
+                        result.score.map_or("-".to_string(), |score| score.to_string()),
+                        result.total_nodes_in_1k,
+                        result.time_elapsed,
+                        result.error.as_ref().map_or(String::new(), |err| format!(" error={err}")),
+                    )))),
+                    aborted.clone(),
+                );
+
+                stdio_out(Ok(TextProtocolResponse::Response(report.to_string())));
+            }
+            Message::Bench { depth } => {
+                let report = mintaka::bench::bench::<Instant>(depth, |result| stdio_out(Ok(TextProtocolResponse::Log(format!(
+                    "{} {}: best={} score={} nodes={} elapsed={:?}",
+                    result.rule, result.history, result.best_move, result.score, result.nodes, result.time_elapsed,
+                )))));
+
+                stdio_out(Ok(TextProtocolResponse::Response(report.to_string())));
+            }
+            Message::Config(ConfigCommand::Rule(rule)) => {
+                stdio_out(Ok(TextProtocolResponse::Ack));
+
+                if rule != R {
+                    *session = Session { config, timer, history: game_agent.state.history };
+
+                    return Some(rule);
+                }
+            }
+            Message::Config(ConfigCommand::ClearTT | ConfigCommand::Realtime(_) | ConfigCommand::Folder(_)) => unreachable!(),
+            Message::BalanceOne { target } => {
+                match game_agent.balance_one::<Instant>(config, target, aborted.clone()) {
+                    Some(move_score) => {
+                        stdio_out(Ok(TextProtocolResponse::Log(format!("balance: {} score={}", move_score.pos, move_score.score))));
+                        stdio_out(Ok(TextProtocolResponse::Response(move_score.pos.to_string())));
+                    },
+                    None => stdio_out(Err("no balancing move".to_string())),
+                }
+            }
+            Message::BalanceTwo { target } => {
+                match game_agent.balance_two::<Instant>(config, target, aborted.clone()) {
+                    Some([first, second]) => {
+                        stdio_out(Ok(TextProtocolResponse::Log(format!("balance: {} {} score={}", first.pos, second.pos, first.score))));
+                        stdio_out(Ok(TextProtocolResponse::Response(format!("{},{}", first.pos, second.pos))));
+                    },
+                    None => stdio_out(Err("no balancing moves".to_string())),
+                }
+            }
+            Message::RankMoves { .. } | Message::Swap2 => unreachable!(),
         }
     }
 
-    Ok(())
+    None
 }
 
 fn match_command<const R: RuleKind>(
@@ -223,6 +461,7 @@ fn match_command<const R: RuleKind>(
     message_sender: &MessageSender,
     args: Vec<&str>,
     buf: &str,
+    rule: &mut RuleSelection,
 ) -> Result<(), String> {
     match args[0] {
         "abort" => {
@@ -256,6 +495,24 @@ fn match_command<const R: RuleKind>(
 
                 message_sender.config(ConfigCommand::ResizeTT(ByteSize::from_kib(memory_size_in_kib)));
             }
+            "rule" => {
+                let rule_kind = args.get(2).ok_or("rule not provided.")?
+                    .parse::<RuleKind>()
+                    .map_err(|e| e.to_string())?;
+
+                rule.switch(rule_kind)?;
+
+                message_sender.config(ConfigCommand::Rule(rule_kind));
+            }
+            "deterministic" => {
+                let deterministic = match *args.get(2).ok_or("mode not provided.")? {
+                    "on" => true,
+                    "off" => false,
+                    &_ => return Err("invalid mode.".to_string()),
+                };
+
+                message_sender.config(ConfigCommand::Deterministic(deterministic));
+            }
             &_ => return Err("data type not provided.".to_string()),
         },
         "limit" => match *args.get(1).ok_or("data type not provided.")? {
@@ -278,6 +535,49 @@ fn match_command<const R: RuleKind>(
                     "increment" => {
                         message_sender.config(ConfigCommand::IncrementTime(parse_time_in_milliseconds(&args)?));
                     }
+                    "fischer" => {
+                        message_sender.config(ConfigCommand::TimeControl(TimeControlUpdate::Replace(TimeControl::Fischer)));
+                    }
+                    "byoyomi" => {
+                        let periods = args.get(3).ok_or("periods not provided.")?
+                            .parse::<u32>()
+                            .map_err(|_| "invalid periods.")?;
+
+                        let period_time = args.get(4).ok_or("period time not provided.")?
+                            .parse::<u64>()
+                            .map_err(|_| "invalid time.")?;
+
+                        message_sender.config(ConfigCommand::TimeControl(TimeControlUpdate::Replace(
+                            TimeControl::ByoYomi { periods, period_time: Duration::from_millis(period_time) }
+                        )));
+                    }
+                    "delay" => {
+                        let delay = parse_time_in_milliseconds(&args)?;
+
+                        message_sender.config(ConfigCommand::TimeControl(TimeControlUpdate::Replace(TimeControl::SimpleDelay { delay })));
+                    }
+                    "bronstein" => {
+                        let delay = parse_time_in_milliseconds(&args)?;
+
+                        message_sender.config(ConfigCommand::TimeControl(TimeControlUpdate::Replace(TimeControl::Bronstein { delay })));
+                    }
+                    "moves-to-go" => {
+                        let moves_to_go = args.get(3).ok_or("moves not provided.")?
+                            .parse::<u32>()
+                            .map_err(|_| "invalid moves.")?;
+
+                        let period_time = args.get(4)
+                            .map(|time| time.parse::<u64>().map_err(|_| "invalid time."))
+                            .transpose()?
+                            .map_or(Duration::ZERO, Duration::from_millis);
+
+                        message_sender.config(ConfigCommand::TimeControl(TimeControlUpdate::Replace(
+                            TimeControl::MovesToGo { moves_to_go, moves_per_period: moves_to_go, period_time }
+                        )));
+                    }
+                    "hourglass" => {
+                        message_sender.config(ConfigCommand::TimeControl(TimeControlUpdate::Replace(TimeControl::Hourglass)));
+                    }
                     &_ => return Err("unknown time type.".to_string()),
                 }
             }
@@ -299,7 +599,11 @@ fn match_command<const R: RuleKind>(
                 message_sender.command(MessageCommand::Command(Command::Init(Box::new(GameStateData { board_data: (&board).into(), history }))));
             }
             "history" => {
-                let history: History = args.get(2).ok_or("history not provided.")?.parse()?;
+                let history: History = args.get(2 ..)
+                    .filter(|tokens| !tokens.is_empty())
+                    .ok_or("history not provided.")?
+                    .join(" ")
+                    .parse()?;
 
                 let board: Board<R> = (&history).into();
 
@@ -316,7 +620,12 @@ fn match_command<const R: RuleKind>(
             });
         }
         "history" => {
-            message_sender.status(StatusCommand::History);
+            let format = args.get(1)
+                .map(|format| format.parse::<HistoryFormat>())
+                .transpose()?
+                .unwrap_or(HistoryFormat::Coordinates);
+
+            message_sender.status(StatusCommand::History { format });
         }
         "time" => {
             message_sender.status(StatusCommand::Time);
@@ -356,6 +665,60 @@ fn match_command<const R: RuleKind>(
         "undo" => {
             message_sender.command(MessageCommand::Undo);
         }
+        "analyze" => {
+            let nodes_in_1k = args.get(1)
+                .map(|nodes| nodes.parse::<u32>().map_err(|_| "invalid nodes number."))
+                .transpose()?
+                .unwrap_or(ANALYSIS_NODES_IN_1K);
+
+            message_sender.analyze(nodes_in_1k);
+        }
+        "prove" => {
+            let max_nodes = args.get(1)
+                .map(|nodes| nodes.parse::<usize>().map_err(|_| "invalid nodes number."))
+                .transpose()?
+                .unwrap_or(PROOF_MAX_NODES);
+
+            message_sender.prove(max_nodes);
+        }
+        "suite" => {
+            let path = args.get(1).ok_or("suite file not provided.")?;
+
+            let suite: TestSuite = std::fs::read_to_string(path)
+                .map_err(|err| err.to_string())?
+                .parse()
+                .map_err(|err: SuiteParseError| err.to_string())?;
+
+            let nodes_in_1k = args.get(2)
+                .map(|nodes| nodes.parse::<u32>().map_err(|_| "invalid nodes number."))
+                .transpose()?
+                .unwrap_or(SUITE_NODES_IN_1K);
+
+            message_sender.suite(suite, nodes_in_1k);
+        }
+        "bench" => {
+            let depth = args.get(1)
+                .map(|depth| depth.parse::<Depth>().map_err(|_| "invalid depth."))
+                .transpose()?
+                .unwrap_or(BENCH_DEPTH);
+
+            message_sender.bench(depth);
+        }
+        "defenses" => {
+            message_sender.defenses();
+        }
+        "balance" => {
+            let target = args.get(2)
+                .map(|score| score.parse::<Score>().map_err(|_| "invalid score."))
+                .transpose()?
+                .unwrap_or(0);
+
+            match *args.get(1).ok_or("balance kind not provided.")? {
+                "one" => message_sender.balance_one(target),
+                "two" => message_sender.balance_two(target),
+                &_ => return Err("unknown balance kind.".to_string()),
+            }
+        }
         "gen" => {
             message_sender.launch(SearchObjective::Best, false, false);
         }
@@ -385,10 +748,11 @@ fn execute_command<const R: RuleKind>(game_agent: &mut GameAgent<R>, command: Co
         .map_err(|err| err.to_string())
 }
 
-fn spawn_command_listener<const R: RuleKind>(
+fn spawn_command_listener(
     aborted: Arc<AtomicBool>,
     message_sender: MessageSender,
     initial_sequence: Vec<String>,
+    mut rule: RuleSelection,
 ) {
     std::thread::spawn(move || {
         let stdin = std::io::stdin();
@@ -404,7 +768,11 @@ fn spawn_command_listener<const R: RuleKind>(
                 continue;
             }
 
-            let result = match_command::<R>(&aborted, &message_sender, args, &line);
+            let result = match rule.rule {
+                RuleKind::Renju => match_command::<{ RuleKind::Renju }>(&aborted, &message_sender, args, &line, &mut rule),
+                RuleKind::Gomoku => match_command::<{ RuleKind::Gomoku }>(&aborted, &message_sender, args, &line, &mut rule),
+                RuleKind::Freestyle => match_command::<{ RuleKind::Freestyle }>(&aborted, &message_sender, args, &line, &mut rule),
+            };
 
             if let Err(error) = result {
                 stdio_out(Err(error));
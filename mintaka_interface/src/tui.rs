@@ -0,0 +1,323 @@
+use crossterm::cursor;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::style::Print;
+use crossterm::terminal::{self, ClearType};
+use crossterm::{execute, queue};
+use mintaka::config::{Config, SearchObjective};
+use mintaka::game_agent::{GameAgent, GameError};
+use mintaka::game_state::GameState;
+use mintaka::principal_variation::PrincipalVariation;
+use mintaka::protocol::command::Command;
+use mintaka::protocol::response::{CallBackResponseSender, Response};
+use mintaka::protocol::results::BestMove;
+use mintaka::value::Depth;
+use mintaka_interface::message::{Message, MessageCommand, MessageSender, CHANNEL_CLOSED_MESSAGE};
+use rusty_renju::board_io::SYMBOL_EMPTY;
+use rusty_renju::board_iter::BoardIterItem;
+use rusty_renju::notation::pos;
+use rusty_renju::notation::pos::{MaybePos, Pos};
+use rusty_renju::notation::rule::RuleKind;
+use rusty_renju::notation::score::{Score, Scores};
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+
+const INPUT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+const KEY_HELP: &str = "arrows/hjkl move, enter play, g engine move, a analyze, esc stop, u undo, r redo, m heatmap, q quit";
+
+enum EngineEvent<const R: RuleKind> {
+    State(GameState<R>),
+    Status {
+        best_move: MaybePos,
+        score: Score,
+        depth: Depth,
+        total_nodes_in_1k: u32,
+        pv: PrincipalVariation,
+    },
+    Finished {
+        best_move: BestMove,
+        heatmap: [f32; pos::BOARD_SIZE],
+    },
+    Error(String),
+}
+
+struct View<const R: RuleKind> {
+    state: GameState<R>,
+    cursor: Pos,
+    redo: Vec<Pos>,
+    searching: bool,
+    status: String,
+    pv: PrincipalVariation,
+    heatmap: Option<[f32; pos::BOARD_SIZE]>,
+    show_heatmap: bool,
+    notice: String,
+}
+
+pub fn run<const R: RuleKind>(config: Config, state: GameState<R>) -> Result<(), GameError> {
+    let aborted = Arc::new(AtomicBool::new(false));
+
+    let (message_sender, message_receiver) = {
+        let (tx, rx) = mpsc::channel();
+        (MessageSender::new(tx), rx)
+    };
+
+    let (event_sender, event_receiver) = mpsc::channel::<EngineEvent<R>>();
+
+    spawn_engine::<R>(config, state, aborted.clone(), message_receiver, event_sender);
+
+    let mut view = View {
+        state,
+        cursor: pos::CENTER,
+        redo: vec![],
+        searching: false,
+        status: String::new(),
+        pv: PrincipalVariation::EMPTY,
+        heatmap: None,
+        show_heatmap: false,
+        notice: String::new(),
+    };
+
+    let mut stdout = std::io::stdout();
+
+    terminal::enable_raw_mode().expect("failed to enable raw mode");
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide).expect("failed to enter alternate screen");
+
+    let mut dirty = true;
+
+    loop {
+        while let Ok(event) = event_receiver.try_recv() {
+            apply_event(&mut view, event);
+            dirty = true;
+        }
+
+        if dirty {
+            draw(&mut stdout, &view);
+            dirty = false;
+        }
+
+        if !event::poll(INPUT_POLL_INTERVAL).expect("failed to poll terminal events") {
+            continue;
+        }
+
+        let Event::Key(key) = event::read().expect("failed to read terminal event") else {
+            dirty = true;
+            continue;
+        };
+
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        dirty = true;
+        view.notice.clear();
+
+        match key.code {
+            KeyCode::Char('q') => break,
+            KeyCode::Esc => aborted.store(true, Ordering::Relaxed),
+            KeyCode::Char('m') => view.show_heatmap = !view.show_heatmap,
+            KeyCode::Left | KeyCode::Char('h') => view.cursor = step_cursor(view.cursor, 0, -1),
+            KeyCode::Right | KeyCode::Char('l') => view.cursor = step_cursor(view.cursor, 0, 1),
+            KeyCode::Up | KeyCode::Char('k') => view.cursor = step_cursor(view.cursor, 1, 0),
+            KeyCode::Down | KeyCode::Char('j') => view.cursor = step_cursor(view.cursor, -1, 0),
+            // the board only changes while the engine is idle
+            _ if view.searching => view.notice = "searching, press esc to stop".to_string(),
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                if view.state.board.is_legal_move(view.cursor) {
+                    view.redo.clear();
+                    message_sender.command(MessageCommand::Play { pos: view.cursor.into() });
+                } else {
+                    view.notice = format!("{} is not a legal move", view.cursor);
+                }
+            }
+            KeyCode::Char('g') | KeyCode::Char('a') => {
+                view.searching = true;
+                view.status.clear();
+                view.pv = PrincipalVariation::EMPTY;
+
+                if key.code == KeyCode::Char('g') {
+                    view.redo.clear();
+                }
+
+                message_sender.launch(SearchObjective::Best, key.code == KeyCode::Char('g'), false);
+            }
+            KeyCode::Char('u') => match view.state.history.last_action_or_none().ok() {
+                Some(pos) => {
+                    view.redo.push(pos);
+                    message_sender.command(MessageCommand::Undo);
+                }
+                None => view.notice = "no history to undo".to_string(),
+            },
+            KeyCode::Char('r') => match view.redo.pop() {
+                Some(pos) => message_sender.command(MessageCommand::Play { pos: pos.into() }),
+                None => view.notice = "no move to redo".to_string(),
+            },
+            _ => {},
+        }
+    }
+
+    aborted.store(true, Ordering::Relaxed);
+
+    execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen).expect("failed to leave alternate screen");
+    terminal::disable_raw_mode().expect("failed to disable raw mode");
+
+    Ok(())
+}
+
+fn spawn_engine<const R: RuleKind>(
+    config: Config,
+    state: GameState<R>,
+    aborted: Arc<AtomicBool>,
+    message_receiver: mpsc::Receiver<Message>,
+    event_sender: mpsc::Sender<EngineEvent<R>>,
+) {
+    std::thread::spawn(move || {
+        let mut game_agent = GameAgent::from_state(config, state);
+
+        let send = |event: EngineEvent<R>| event_sender.send(event).expect(CHANNEL_CLOSED_MESSAGE);
+
+        for message in message_receiver {
+            match message {
+                Message::Command(command) => {
+                    match game_agent.command(command.into_command(&config, game_agent.state.board.hash_key)) {
+                        Ok(_) => send(EngineEvent::State(game_agent.state)),
+                        Err(err) => send(EngineEvent::Error(err.to_string())),
+                    }
+                }
+                Message::Launch { objective, apply, .. } => {
+                    let status_sender = event_sender.clone();
+
+                    let best_move = game_agent.launch::<Instant>(
+                        config,
+                        config.initial_timer,
+                        objective,
+                        CallBackResponseSender::new(move |response| {
+                            if let Response::Status { best_move, score, selective_depth, total_nodes_in_1k, pv, .. } = response {
+                                let _ = status_sender.send(EngineEvent::Status {
+                                    best_move, score, depth: selective_depth, total_nodes_in_1k, pv,
+                                });
+                            }
+                        }),
+                        Arc::new(AtomicU32::new(0)),
+                        aborted.clone(),
+                    );
+
+                    let heatmap = game_agent.checkpoint()
+                        .filter(|checkpoint| checkpoint.position_hash == game_agent.state.board.hash_key)
+                        .map_or([f32::NAN; pos::BOARD_SIZE], |checkpoint| checkpoint.root_scores_array().map(|score|
+                            if score == Score::NAN { f32::NAN } else { score as f32 }
+                        ));
+
+                    send(EngineEvent::Finished { best_move, heatmap });
+
+                    if apply {
+                        let result = game_agent.command(Command::Play {
+                            hash: game_agent.state.board.hash_key,
+                            pos: best_move.best_move,
+                            draw_condition: config.draw_condition,
+                        });
+
+                        match result {
+                            Ok(_) => send(EngineEvent::State(game_agent.state)),
+                            Err(err) => send(EngineEvent::Error(err.to_string())),
+                        }
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+    });
+}
+
+fn apply_event<const R: RuleKind>(view: &mut View<R>, event: EngineEvent<R>) {
+    match event {
+        EngineEvent::State(state) => {
+            if state.board.hash_key != view.state.board.hash_key {
+                view.heatmap = None;
+            }
+
+            view.state = state;
+        }
+        EngineEvent::Status { best_move, score, depth, total_nodes_in_1k, pv } => {
+            view.status = format!("best={best_move} score={score} depth={depth} nodes={total_nodes_in_1k}k");
+            view.pv = pv;
+        }
+        EngineEvent::Finished { best_move, heatmap } => {
+            view.searching = false;
+            view.status = format!(
+                "best={} score={} depth={} nodes={}k elapsed={:?}",
+                best_move.best_move, best_move.score, best_move.selective_depth,
+                best_move.total_nodes_in_1k, best_move.time_elapsed,
+            );
+            view.pv = best_move.pv;
+            view.heatmap = Some(heatmap);
+        }
+        EngineEvent::Error(err) => {
+            view.notice = err;
+        }
+    }
+}
+
+fn step_cursor(cursor: Pos, row_offset: i8, col_offset: i8) -> Pos {
+    let max = pos::BOARD_WIDTH as i8 - 1;
+
+    Pos::from_cartesian(
+        (cursor.row() as i8 + row_offset).clamp(0, max) as u8,
+        (cursor.col() as i8 + col_offset).clamp(0, max) as u8,
+    )
+}
+
+fn render_board<const R: RuleKind>(view: &View<R>) -> String {
+    let board = &view.state.board;
+
+    if view.show_heatmap && let Some(heatmap) = view.heatmap {
+        return board.to_string_with_heatmap_and_last_moves(heatmap, false, view.state.history.last_action_pair());
+    }
+
+    let last_move = view.state.history.last_action_or_none();
+
+    board.render_with_attributes(
+        |pos, item| match item {
+            BoardIterItem::Stone(color) => char::from(*color).to_string(),
+            BoardIterItem::Pattern(_) => match board.patterns.forbidden_kind(pos) {
+                Some(kind) => format!("\x1b[31m{}\x1b[0m", char::from(kind)),
+                None => SYMBOL_EMPTY.to_string(),
+            },
+        },
+        |pos, _| {
+            if pos == view.cursor {
+                Some((true, ['[', ']']))
+            } else if MaybePos::from(pos) == last_move {
+                Some((false, ['(', ')']))
+            } else {
+                None
+            }
+        },
+    )
+}
+
+fn draw<const R: RuleKind>(stdout: &mut std::io::Stdout, view: &View<R>) {
+    let board = &view.state.board;
+
+    let cursor_info = match board.patterns.forbidden_kind(view.cursor) {
+        Some(kind) => format!("cursor={} forbidden={kind:?}", view.cursor),
+        None => format!("cursor={}", view.cursor),
+    };
+
+    let panel = [
+        format!("rule={R} player={:?} stones={} {cursor_info}", board.player_color, board.stones),
+        format!("{} {}", if view.searching { "searching" } else { "idle" }, view.status),
+        format!("pv={}", view.pv),
+        view.notice.clone(),
+        KEY_HELP.to_string(),
+    ];
+
+    queue!(stdout, cursor::MoveTo(0, 0), terminal::Clear(ClearType::All)).expect("failed to clear screen");
+
+    for line in render_board(view).lines().chain(panel.iter().map(String::as_str)) {
+        queue!(stdout, Print(line), Print("\r\n")).expect("failed to draw screen");
+    }
+
+    stdout.flush().expect("failed to flush stdout");
+}
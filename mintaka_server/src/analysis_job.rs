@@ -0,0 +1,171 @@
+use crate::app_state::AppError;
+use dashmap::DashMap;
+use mintaka::analysis::{AnalysisProgress, GameAnalysis};
+use mintaka::config::Config;
+use rusty_renju::history::History;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+const ANALYSIS_SAVE_INTERVAL: Duration = Duration::from_secs(10);
+const FINISHED_ANALYSIS_TTL: Duration = Duration::from_secs(60 * 60);
+const STALE_ANALYSIS_TTL: Duration = Duration::from_secs(60 * 60 * 24);
+
+pub const ANALYSIS_FILE_EXTENSION: &str = "analysis";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AnalysisKey(Uuid);
+
+impl Display for AnalysisKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for AnalysisKey {
+    type Err = AppError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Ok(Self(Uuid::from_str(source).map_err(|_| AppError::AnalysisNotFound)?))
+    }
+}
+
+impl AnalysisKey {
+    pub fn new_random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[typeshare::typeshare]
+#[serde(tag = "type", content = "content")]
+pub enum AnalysisStatus {
+    Queued,
+    Running {
+        analyzed_plies: u32,
+        total_plies: u32,
+    },
+    Finished(GameAnalysis),
+    Failed(String),
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AnalysisJobData {
+    pub config: Config,
+    pub history: History,
+    pub nodes_in_1k_per_ply: u32,
+    pub progress: AnalysisProgress,
+}
+
+pub struct AnalysisJob {
+    pub status: Mutex<AnalysisStatus>,
+    pub aborted: Arc<AtomicBool>,
+    data: Mutex<AnalysisJobData>,
+    saved_at: Mutex<Instant>,
+    touched_at: Mutex<Instant>,
+    path: PathBuf,
+}
+
+impl AnalysisJob {
+    pub fn new(data: AnalysisJobData, path: PathBuf) -> Self {
+        Self {
+            status: Mutex::new(AnalysisStatus::Queued),
+            aborted: Arc::new(AtomicBool::new(false)),
+            data: Mutex::new(data),
+            saved_at: Mutex::new(Instant::now()),
+            touched_at: Mutex::new(Instant::now()),
+            path,
+        }
+    }
+
+    pub fn status(&self) -> AnalysisStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    pub fn update(&self, status: AnalysisStatus) {
+        if matches!(status, AnalysisStatus::Finished(_) | AnalysisStatus::Failed(_)) {
+            self.touch();
+        }
+
+        *self.status.lock().unwrap() = status;
+    }
+
+    pub fn touch(&self) {
+        *self.touched_at.lock().unwrap() = Instant::now();
+    }
+
+    // results are kept for a while after they were finished or last read,
+    // jobs nobody asked about for a day are dropped even if still running
+    pub fn is_expired(&self, now: Instant) -> bool {
+        let ttl = if self.is_pending() {
+            STALE_ANALYSIS_TTL
+        } else {
+            FINISHED_ANALYSIS_TTL
+        };
+
+        now.saturating_duration_since(*self.touched_at.lock().unwrap()) > ttl
+    }
+
+    pub fn is_pending(&self) -> bool {
+        matches!(self.status(), AnalysisStatus::Queued | AnalysisStatus::Running { .. })
+    }
+
+    pub fn data(&self) -> AnalysisJobData {
+        self.data.lock().unwrap().clone()
+    }
+
+    // keeps the latest progress, and writes it out once in a while so a killed server loses little work
+    pub fn record(&self, progress: &AnalysisProgress) {
+        self.update(AnalysisStatus::Running {
+            analyzed_plies: progress.analyzed_plies() as u32,
+            total_plies: progress.total_plies() as u32,
+        });
+
+        self.data.lock().unwrap().progress = progress.clone();
+
+        let due = {
+            let mut saved_at = self.saved_at.lock().unwrap();
+            let due = saved_at.elapsed() >= ANALYSIS_SAVE_INTERVAL;
+
+            if due {
+                *saved_at = Instant::now();
+            }
+
+            due
+        };
+
+        if due && let Err(err) = self.save() {
+            tracing::warn!("failed to save analysis progress: path={}, err={err}", self.path.display());
+        }
+    }
+
+    pub fn save(&self) -> Result<(), AppError> {
+        let data = self.data.lock().unwrap();
+
+        let encoded = rmp_serde::to_vec(&*data)
+            .map_err(AppError::from_general_error)?;
+
+        let tmp_path = self.path.with_extension("tmp");
+
+        std::fs::write(&tmp_path, encoded)
+            .and_then(|_| std::fs::rename(&tmp_path, &self.path))
+            .map_err(|err| {
+                let _ = std::fs::remove_file(&tmp_path);
+
+                AppError::from_general_error(err)
+            })
+    }
+
+    pub fn remove_file(&self) {
+        let _data = self.data.lock().unwrap();
+
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+pub type AnalysisJobs = DashMap<AnalysisKey, Arc<AnalysisJob>>;
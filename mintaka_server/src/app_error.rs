@@ -25,6 +25,8 @@ pub enum AppError {
     MemoryAcquireTimeout,
     #[error("WORKER_ACQUIRE_TIMEOUT")]
     WorkerAcquireTimeout,
+    #[error("ANALYSIS_NOT_FOUND")]
+    AnalysisNotFound,
     #[error("{}", game_error_code(.0))]
     GameError(#[from] GameError),
     #[error("INTERNAL_ERROR")]
@@ -40,6 +42,7 @@ fn game_error_code(error: &GameError) -> &'static str {
         GameError::ForbiddenMove => "FORBIDDEN_MOVE",
         GameError::NoHistoryToUndo => "NO_HISTORY_TO_UNDO",
         GameError::NoTimeManagement => "NO_TIME_MANAGEMENT",
+        GameError::InvalidPosition(_) => "INVALID_POSITION",
     }
 }
 
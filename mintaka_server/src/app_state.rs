@@ -1,26 +1,34 @@
 pub(crate) use crate::app_error::AppError;
+use crate::analysis_job::{AnalysisJob, AnalysisJobData, AnalysisJobs, AnalysisKey, AnalysisStatus, ANALYSIS_FILE_EXTENSION};
 use crate::preference::Preference;
 use crate::session::{Session, SessionData, SessionKey, SessionResponse, SessionResponseReceiver, SessionResponseSender, SessionResultResponse, SessionStatus, SessionToken, Sessions};
 use crate::stream_response_sender::StreamSessionResponseSender;
+use mintaka::analysis::AnalysisProgress;
 use mintaka::config::Config;
 use mintaka::protocol::command::Command;
+use mintaka::protocol::timer::Timer;
 use mintaka::game_state::GameState;
 use rusty_renju::utils::byte_size::ByteSize;
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use serde::{Deserialize, Serialize};
 use tokio::io::AsyncWriteExt;
 use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
-use mintaka::game_agent::{GameError};
+use mintaka::game_agent::{GameAgent, GameError};
 use mintaka::protocol::results::{BestMove, CommandResult};
 use rusty_renju::hash_key::HashKey;
+use rusty_renju::history::History;
 use rusty_renju::notation::rule::RuleKind;
 use crate::app_error::AppError::SessionInComputing;
 
 const SESSION_RESPONSE_CHANNEL_CAPACITY: usize = 4;
 const RESOURCE_ACQUIRE_TIMEOUT: Duration = Duration::from_secs(3);
+const SESSION_PAUSE_TIMEOUT: Duration = Duration::from_secs(10);
+const ANALYSIS_NODES_IN_1K_PER_PLY: u32 = 200;
 
 struct HibernatedSessionFile {
     path: PathBuf,
@@ -96,6 +104,7 @@ impl SessionResource {
 
 pub struct AppState {
     pub sessions: Arc<Sessions>,
+    pub analyses: Arc<AnalysisJobs>,
     memory_acquire_lock: Arc<Mutex<()>>,
     worker_resource: Arc<Semaphore>,
     memory_resource: Arc<Semaphore>,
@@ -111,6 +120,7 @@ impl AppState {
     pub fn new(preference: Preference) -> Result<Self, AppError> {
         Ok(Self {
             sessions: Arc::new(Sessions::default()),
+            analyses: Arc::new(AnalysisJobs::default()),
             memory_acquire_lock: Arc::new(Mutex::new(())),
             worker_resource: Arc::new(Semaphore::new(preference.cores)),
             memory_resource: Arc::new(Semaphore::new(preference.memory_limit.mib() as usize)),
@@ -465,6 +475,7 @@ impl AppState {
 
     pub async fn clean_idle_sessions(&self) {
         enum CleanupAction {
+            ReleaseWorkers,
             Hibernate,
             Destroy,
         }
@@ -479,12 +490,18 @@ impl AppState {
                     Some(CleanupAction::Destroy)
                 } else if session.should_hibernate(now) {
                     Some(CleanupAction::Hibernate)
+                } else if session.should_release_workers(now) {
+                    Some(CleanupAction::ReleaseWorkers)
                 } else {
                     None
                 }
             }).flatten();
 
             match action {
+                Some(CleanupAction::ReleaseWorkers) => {
+                    // idle sessions keep no search threads parked, they respawn on the next launch
+                    self.sessions.with_mut(&session_key, Session::release_workers);
+                }
                 Some(CleanupAction::Hibernate) => {
                     if let Err(err) = self.hibernate_active_session(session_key).await {
                         tracing::warn!("failed to hibernate idle session: sid={session_key}, err={err}; skipping");
@@ -554,6 +571,7 @@ impl AppState {
         session_key: SessionKey,
         timeout: Duration,
         position_hash: HashKey,
+        timer: Option<Timer>,
         nodes_polling_interval_ms: Option<u32>,
     ) -> Result<(), AppError> {
         let workers = self.sessions.with_touch(&session_key, |session| {
@@ -588,6 +606,7 @@ impl AppState {
                 StreamSessionResponseSender::new(response_sender.clone()),
                 result_tx,
                 worker_permit,
+                timer,
                 nodes_polling_interval_ms,
             )?;
 
@@ -618,6 +637,176 @@ impl AppState {
         Ok(())
     }
 
+    pub async fn new_analysis(
+        &self,
+        config: Option<Config>,
+        history: History,
+        nodes_in_1k_per_ply: Option<u32>,
+    ) -> Result<AnalysisKey, AppError> {
+        if let Some(config) = config
+            && let Some(max_config) = self.preference.max_config
+            && config > max_config
+        {
+            return Err(AppError::InvalidConfig);
+        }
+
+        let analysis_key = AnalysisKey::new_random();
+
+        self.spawn_analysis(analysis_key, AnalysisJobData {
+            config: config.unwrap_or(self.preference.default_config),
+            history,
+            nodes_in_1k_per_ply: nodes_in_1k_per_ply.unwrap_or(ANALYSIS_NODES_IN_1K_PER_PLY),
+            progress: AnalysisProgress::default(),
+        }).await?;
+
+        tracing::info!("analysis created; aid={analysis_key}");
+
+        Ok(analysis_key)
+    }
+
+    async fn spawn_analysis(&self, analysis_key: AnalysisKey, data: AnalysisJobData) -> Result<(), AppError> {
+        let config = data.config;
+
+        let memory_permit = {
+            let _memory_acquire_guard = self.memory_acquire_lock.lock().await;
+            self.acquire_memory(config.tt_size, true, RESOURCE_ACQUIRE_TIMEOUT).await?
+        };
+
+        let worker_permit = self.acquire_workers(config.workers, RESOURCE_ACQUIRE_TIMEOUT).await?;
+
+        let job = Arc::new(AnalysisJob::new(data, self.analysis_file_path(analysis_key)));
+
+        self.analyses.insert(analysis_key, job.clone());
+
+        tokio::task::spawn_blocking(move || {
+            let AnalysisJobData { config, history, nodes_in_1k_per_ply, progress } = job.data();
+
+            let result = GameAgent::<{ RuleKind::Renju }>::analyze_game::<Instant>(
+                config,
+                &history,
+                nodes_in_1k_per_ply,
+                Some(progress),
+                |progress| job.record(progress),
+                job.aborted.clone(),
+            );
+
+            job.update(match result {
+                Ok(analysis) => AnalysisStatus::Finished(analysis),
+                Err(err) => AnalysisStatus::Failed(err.to_string()),
+            });
+
+            job.remove_file();
+
+            worker_permit.release();
+            memory_permit.release();
+        });
+
+        Ok(())
+    }
+
+    fn analysis_file_path(&self, analysis_key: AnalysisKey) -> PathBuf {
+        Path::new(&self.preference.analyses_directory)
+            .join(format!("{analysis_key}.{ANALYSIS_FILE_EXTENSION}"))
+    }
+
+    // unfinished jobs are saved with their last completed iteration, and picked up again on the next start
+    pub async fn suspend_analyses(&self) {
+        let jobs: Vec<_> = self.analyses.iter()
+            .filter(|entry| entry.value().is_pending())
+            .map(|entry| (*entry.key(), entry.value().clone()))
+            .collect();
+
+        for (analysis_key, job) in jobs {
+            let result = tokio::task::spawn_blocking(move || job.save())
+                .await
+                .map_err(AppError::from_general_error)
+                .flatten();
+
+            if let Err(err) = result {
+                tracing::warn!("failed to suspend analysis: aid={analysis_key}, err={err}; skipping");
+            }
+        }
+    }
+
+    pub async fn resume_analyses(&self) -> Result<(), AppError> {
+        let mut entries = tokio::fs::read_dir(&self.preference.analyses_directory)
+            .await
+            .map_err(AppError::from_general_error)?;
+
+        while let Some(entry) = entries.next_entry().await.map_err(AppError::from_general_error)? {
+            let path = entry.path();
+
+            if path.extension().and_then(|extension| extension.to_str()) != Some(ANALYSIS_FILE_EXTENSION) {
+                continue;
+            }
+
+            let Some(Ok(analysis_key)) = path.file_stem()
+                .and_then(|stem| stem.to_str())
+                .map(AnalysisKey::from_str)
+            else {
+                continue;
+            };
+
+            let data = match tokio::fs::read(&path).await
+                .map_err(AppError::from_general_error)
+                .and_then(|buf| rmp_serde::from_slice::<AnalysisJobData>(&buf).map_err(AppError::from_general_error))
+            {
+                Ok(data) => data,
+                Err(err) => {
+                    tracing::warn!("failed to load analysis: aid={analysis_key}, err={err}; removing");
+
+                    let _ = tokio::fs::remove_file(&path).await;
+
+                    continue;
+                }
+            };
+
+            // the file stays in place when resources are short, so the job is retried on the next start
+            match self.spawn_analysis(analysis_key, data).await {
+                Ok(()) => tracing::info!("analysis resumed; aid={analysis_key}"),
+                Err(err) => tracing::warn!("failed to resume analysis: aid={analysis_key}, err={err}; skipping"),
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get_analysis(&self, analysis_key: AnalysisKey) -> Result<AnalysisStatus, AppError> {
+        self.analyses.get(&analysis_key)
+            .map(|job| {
+                job.touch();
+                job.status()
+            })
+            .ok_or(AppError::AnalysisNotFound)
+    }
+
+    pub fn clean_expired_analyses(&self) {
+        let now = Instant::now();
+
+        let expired: Vec<_> = self.analyses.iter()
+            .filter(|entry| entry.value().is_expired(now))
+            .map(|entry| *entry.key())
+            .collect();
+
+        for analysis_key in expired {
+            if let Err(err) = self.destroy_analysis(analysis_key) {
+                tracing::warn!("failed to destroy expired analysis: aid={analysis_key}, err={err}; skipping");
+            }
+        }
+    }
+
+    pub fn destroy_analysis(&self, analysis_key: AnalysisKey) -> Result<(), AppError> {
+        let (_, job) = self.analyses.remove(&analysis_key)
+            .ok_or(AppError::AnalysisNotFound)?;
+
+        job.aborted.store(true, Ordering::Relaxed);
+        job.remove_file();
+
+        tracing::info!("analysis destroyed; aid={analysis_key}");
+
+        Ok(())
+    }
+
     pub fn abort_session(&self, session_key: SessionKey) -> Result<(), AppError> {
         self.sessions.with_touch(&session_key, |session| {
             if session.status() == SessionStatus::Idle {
@@ -677,6 +866,8 @@ impl AppState {
     }
 
     pub async fn hibernate_all_sessions(&self) -> Result<(), AppError> {
+        self.pause_computing_sessions().await;
+
         for session_key in self.sessions.keys() {
             if let Err(err) = self.hibernate_active_session(session_key).await {
                 tracing::warn!("failed to hibernate session: sid={session_key}, err={err}; skipping");
@@ -685,4 +876,30 @@ impl AppState {
 
         Ok(())
     }
+
+    // aborted searches hand back their agents with the last completed iteration checkpointed,
+    // so hibernated sessions continue from the same depth once launched again
+    async fn pause_computing_sessions(&self) {
+        for session_key in self.sessions.keys() {
+            let _ = self.sessions.with(&session_key, |session| {
+                if session.status() != SessionStatus::Idle {
+                    let _ = session.abort();
+                }
+            });
+        }
+
+        let deadline = tokio::time::Instant::now() + SESSION_PAUSE_TIMEOUT;
+
+        while self.sessions.keys().into_iter().any(|session_key|
+            self.sessions.with(&session_key, |session| session.status() != SessionStatus::Idle)
+                .unwrap_or(false)
+        ) {
+            if tokio::time::Instant::now() >= deadline {
+                tracing::warn!("timed out waiting for computing sessions to pause");
+                break;
+            }
+
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
 }
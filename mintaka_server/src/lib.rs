@@ -1,3 +1,4 @@
+pub mod analysis_job;
 pub mod preference;
 pub mod session;
 pub mod stream_response_sender;
@@ -96,12 +96,17 @@ async fn main() -> Result<(), Box<dyn Error>> {
         .with_target(false)
         .init();
 
-    check_session_directory(&pref.sessions_directory);
+    check_directory(&pref.sessions_directory);
+    check_directory(&pref.analyses_directory);
 
     let addr: SocketAddr = pref.address.parse()?;
 
     let state = Arc::new(AppState::new(pref.clone())?);
 
+    if let Err(err) = state.resume_analyses().await {
+        tracing::warn!("failed to resume analyses: {err}");
+    }
+
     let session_routes = Router::new()
         .route("/{sid}/configs", get(rest::get_session_configs))
         .route("/{sid}/commands", post(rest::command_session))
@@ -122,6 +127,8 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let mut api = Router::new()
         .route("/status", get(rest::status))
         .route("/sessions", post(rest::new_session))
+        .route("/analyses", post(rest::new_analysis))
+        .route("/analyses/{aid}", get(rest::get_analysis).delete(rest::destroy_analysis))
         .nest("/sessions", session_routes
             .merge(session_destroy_route)
             .merge(session_stream_routes)
@@ -154,6 +161,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     spawn_session_cleaner(&state);
     spawn_hibernated_session_cleaner(&state);
+    spawn_analysis_cleaner(&state);
     spawn_sigterm_watcher(&state);
 
     let url = if pref.tls_config.is_some() {
@@ -217,11 +225,11 @@ fn open_browser(pref: &Preference, url: &str) {
     }
 }
 
-fn check_session_directory(session_directory: &str) {
-    if !std::path::Path::new(session_directory).exists() {
-        std::fs::create_dir_all(session_directory).unwrap();
+fn check_directory(directory: &str) {
+    if !std::path::Path::new(directory).exists() {
+        std::fs::create_dir_all(directory).unwrap();
 
-        tracing::info!("created session directory: {}", session_directory);
+        tracing::info!("created directory: {}", directory);
     }
 }
 
@@ -249,7 +257,7 @@ fn spawn_tls_watcher(shared_rustls_config: RustlsConfig, tls_config: TlsConfig)
 }
 
 fn spawn_sigterm_watcher(state: &Arc<AppState>) {
-    tracing::info!("watching SIGTERM for hibernate all sessions and exit");
+    tracing::info!("watching SIGTERM for hibernate all sessions, suspend analyses and exit");
 
     let state = state.clone();
 
@@ -257,7 +265,9 @@ fn spawn_sigterm_watcher(state: &Arc<AppState>) {
         let mut sigterm = tokio::signal::unix::signal(SignalKind::terminate()).unwrap();
 
         sigterm.recv().await;
-        tracing::info!("received SIGTERM signal; hibernate all sessions and exit");
+        tracing::info!("received SIGTERM signal; hibernate all sessions, suspend analyses and exit");
+
+        state.suspend_analyses().await;
 
         if let Err(err) = state.hibernate_all_sessions().await {
             tracing::warn!("failed to hibernate all sessions: {err}");
@@ -296,3 +306,17 @@ fn spawn_hibernated_session_cleaner(state: &Arc<AppState>) {
         }
     });
 }
+
+fn spawn_analysis_cleaner(state: &Arc<AppState>) {
+    let state = state.clone();
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60));
+
+        loop {
+            interval.tick().await;
+
+            state.clean_expired_analyses();
+        }
+    });
+}
@@ -45,6 +45,8 @@ pub struct Preference {
     tls_renew: bool,
     #[arg(short, default_value = "sessions", help = "Session storage directory")]
     pub sessions_directory: String,
+    #[arg(long, default_value = "analyses", help = "Unfinished analysis storage directory")]
+    pub analyses_directory: String,
     #[arg(long, env = "API_PASSWORD", default_value = None)]
     pub api_password: Option<String>,
     #[clap(skip)]
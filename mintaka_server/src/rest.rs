@@ -1,3 +1,4 @@
+use crate::analysis_job::AnalysisKey;
 use crate::app_state::{AppError, AppState};
 use crate::session::{SessionKey, SessionResponse};
 use async_stream::stream;
@@ -8,6 +9,7 @@ use axum::Json;
 use futures_util::Stream;
 use mintaka::config::Config;
 use mintaka::protocol::command::Command;
+use mintaka::protocol::timer::Timer;
 use mintaka::game_state::GameState;
 use serde::{Deserialize, Serialize};
 use std::convert::Infallible;
@@ -15,6 +17,7 @@ use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 use rusty_renju::hash_key::HashKey;
+use rusty_renju::history::History;
 use rusty_renju::notation::rule::RuleKind;
 
 impl From<&AppError> for StatusCode {
@@ -31,6 +34,7 @@ impl From<&AppError> for StatusCode {
             AppError::SessionFileNotFound => StatusCode::NOT_FOUND,
             AppError::MemoryAcquireTimeout => StatusCode::SERVICE_UNAVAILABLE,
             AppError::WorkerAcquireTimeout => StatusCode::SERVICE_UNAVAILABLE,
+            AppError::AnalysisNotFound => StatusCode::NOT_FOUND,
             AppError::GameError(_) => StatusCode::CONFLICT,
             AppError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
@@ -139,6 +143,7 @@ pub async fn command_session(
 pub struct LaunchSessionRequest {
     position_hash: HashKey,
     nodes_polling_interval_in_ms: Option<u32>,
+    timer: Option<Timer>,
 }
 
 pub async fn launch_session(
@@ -153,7 +158,7 @@ pub async fn launch_session(
         .map(Duration::from_secs)
         .unwrap_or(Duration::from_secs(3));
 
-    state.launch_session(sid, timeout, payload.position_hash, payload.nodes_polling_interval_in_ms)
+    state.launch_session(sid, timeout, payload.position_hash, payload.timer, payload.nodes_polling_interval_in_ms)
         .await
         .map(|computing_resource| (StatusCode::OK, Json(computing_resource)))
 }
@@ -222,3 +227,54 @@ pub async fn hibernate_session(
         .await
         .map(|_| StatusCode::NO_CONTENT)
 }
+
+#[derive(Deserialize)]
+#[typeshare::typeshare]
+pub struct CreateAnalysisRequest {
+    api_password: Option<String>,
+    config: Option<Config>,
+    history: History,
+    nodes_in_1k_per_ply: Option<u32>,
+}
+
+#[derive(Serialize)]
+#[typeshare::typeshare]
+pub struct CreateAnalysisResponse {
+    aid: String,
+}
+
+pub async fn new_analysis(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CreateAnalysisRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Some(expected_password) = &state.preference.api_password
+        && payload.api_password.as_deref() != Some(expected_password.as_str())
+    {
+        return Err(AppError::Unauthorized);
+    }
+
+    let analysis_key = state.new_analysis(payload.config, payload.history, payload.nodes_in_1k_per_ply).await?;
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(CreateAnalysisResponse {
+            aid: analysis_key.to_string(),
+        })
+    ))
+}
+
+pub async fn get_analysis(
+    Path(aid): Path<AnalysisKey>,
+    State(state): State<Arc<AppState>>,
+) -> impl IntoResponse {
+    state.get_analysis(aid)
+        .map(|status| (StatusCode::OK, Json(status)))
+}
+
+pub async fn destroy_analysis(
+    Path(aid): Path<AnalysisKey>,
+    State(state): State<Arc<AppState>>,
+) -> impl IntoResponse {
+    state.destroy_analysis(aid)
+        .map(|_| StatusCode::NO_CONTENT)
+}
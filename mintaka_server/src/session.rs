@@ -1,14 +1,16 @@
 use crate::app_state::{AppError, MemoryPermit, WorkerPermit};
 use crate::stream_response_sender::StreamSessionResponseSender;
 use dashmap::DashMap;
+use futures_util::StreamExt;
 use mintaka::config::{Config, SearchObjective};
 use mintaka::game_agent::{GameAgent, GameAgentData};
 use mintaka::game_state::GameState;
 use mintaka::memo::transposition_table::TTImportError;
 use mintaka::protocol::command::Command;
-use mintaka::protocol::response::Response;
+use mintaka::protocol::response::{Response, ResponseSender};
 use mintaka::protocol::results::{BestMove, CommandResult};
 use mintaka::protocol::timer::Timer;
+use mintaka::search_handle::SearchAbortHandle;
 use rusty_renju::hash_key::HashKey;
 use rusty_renju::notation::rule::RuleKind;
 use rusty_renju::utils::byte_size::ByteSize;
@@ -18,12 +20,13 @@ use std::collections::BinaryHeap;
 use std::fmt::{Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::str::FromStr;
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::Mutex;
 use std::time::{Duration, Instant, SystemTime};
 use tokio::sync::broadcast;
 use uuid::Uuid;
 
+const WORKER_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SessionKey(Uuid);
 
@@ -130,10 +133,11 @@ pub struct Session {
     pub config: Config,
     pub token: SessionToken,
     timer: Timer,
+    opponent_turn_started: Option<Instant>,
     state: AgentState,
     pub response_sender: SessionResponseSender,
     best_move: Option<BestMove>,
-    abort_handle: Arc<AtomicBool>,
+    abort_handle: SearchAbortHandle,
     time_to_hibernate: Option<Duration>,
     time_to_live: Option<Duration>,
     pub last_active: Instant,
@@ -165,10 +169,11 @@ impl Session {
             config,
             token,
             timer: config.initial_timer,
+            opponent_turn_started: None,
             state: AgentState::Agent(GameAgent::from_state(config, game_state)),
             response_sender,
             best_move: None,
-            abort_handle: Arc::new(AtomicBool::new(false)),
+            abort_handle: SearchAbortHandle::default(),
             time_to_hibernate,
             time_to_live,
             last_active: Instant::now(),
@@ -189,11 +194,12 @@ impl Session {
             config: data.config,
             token,
             timer: data.timer,
+            opponent_turn_started: None,
             state: AgentState::Agent(agent),
             response_sender,
             memory_permit,
             best_move: data.best_move,
-            abort_handle: Arc::new(AtomicBool::new(false)),
+            abort_handle: SearchAbortHandle::default(),
             time_to_hibernate: data.time_to_hibernate,
             time_to_live: data.time_to_live,
             last_active: Instant::now(),
@@ -230,33 +236,44 @@ impl Session {
         response_sender: StreamSessionResponseSender,
         result_sender: tokio::sync::oneshot::Sender<SessionResultResponse>,
         worker_permit: WorkerPermit,
+        timer: Option<Timer>,
         _nodes_polling_interval_ms: Option<u32>,
     ) -> Result<(), AppError> {
         if !matches!(self.state, AgentState::Agent(_)) {
             return Err(AppError::SessionInComputing);
         }
 
-        let AgentState::Agent(mut game_agent)
+        let AgentState::Agent(game_agent)
             = std::mem::replace(&mut self.state, AgentState::Permit(worker_permit))
                 else { unreachable!("agent state checked before launch") };
 
-        self.abort_handle.store(false, Ordering::Relaxed);
-        let abort_flag = self.abort_handle.clone();
+        // clients running the game clock send its current state with each launch,
+        // otherwise the opponent's turn is measured from the last best move
+        let opponent_turn_started = self.opponent_turn_started.take();
+
+        if let Some(timer) = timer {
+            self.timer = timer;
+        } else if let Some(turn_started) = opponent_turn_started {
+            self.timer.opponent_consume(turn_started.elapsed());
+        }
 
         let config = self.config;
         let timer = self.timer;
 
-        tokio::task::spawn_blocking(move || {
-            let best_move = game_agent.launch::<Instant>(
-                config,
-                timer,
-                SearchObjective::Best,
-                response_sender,
-                Arc::new(AtomicU32::new(0)),
-                abort_flag
-            );
-
-            let _ = result_sender.send(SessionResultResponse { game_agent, best_move });
+        let mut search_handle = game_agent.spawn_search::<Instant>(config, timer, SearchObjective::Best);
+        self.abort_handle = search_handle.abort_handle();
+
+        tokio::spawn(async move {
+            while let Some(response) = search_handle.next().await {
+                response_sender.response(response);
+            }
+
+            let outcome = search_handle.await;
+
+            let _ = result_sender.send(SessionResultResponse {
+                game_agent: outcome.game_agent,
+                best_move: outcome.best_move,
+            });
         });
 
         Ok(())
@@ -273,13 +290,23 @@ impl Session {
         match &self.state {
             AgentState::Agent(_) => Err(AppError::SessionIdle),
             AgentState::Permit(_) => {
-                self.abort_handle.store(true, Ordering::Relaxed);
+                self.abort_handle.abort();
                 Ok(())
             }
         }
     }
 
+    pub fn release_workers(&mut self) {
+        if let AgentState::Agent(agent) = &mut self.state {
+            agent.release_workers();
+        }
+    }
+
     pub fn store_best_move(&mut self, best_move: BestMove) {
+        self.timer.consume(best_move.time_elapsed);
+        self.timer.apply_increment();
+        self.opponent_turn_started = Some(Instant::now());
+
         self.best_move = Some(best_move);
     }
 
@@ -318,6 +345,10 @@ impl Session {
         }
     }
 
+    pub fn should_release_workers(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.last_active) >= WORKER_IDLE_TIMEOUT
+    }
+
     pub fn should_hibernate(&self, now: Instant) -> bool {
         match self.time_to_hibernate {
             Some(time_to_hibernate) => now.saturating_duration_since(self.last_active) >= time_to_hibernate,
@@ -340,7 +371,7 @@ impl Session {
 impl Drop for Session {
     fn drop(&mut self) {
         if let AgentState::Permit(_) = &self.state {
-            self.abort_handle.store(true, Ordering::Relaxed);
+            self.abort_handle.abort();
         }
     }
 }
@@ -453,6 +484,11 @@ impl Sessions {
             .map(|session| f(&session))
     }
 
+    pub fn with_mut<R>(&self, key: &SessionKey, f: impl FnOnce(&mut Session) -> R) -> Option<R> {
+        self.map.get_mut(key)
+            .map(|mut session| f(&mut session))
+    }
+
     pub fn with_touch<R>(&self, key: &SessionKey, f: impl FnOnce(&Session) -> R) -> Option<R> {
         let (result, entry) = {
             let mut session = self.map.get_mut(key)?;
@@ -33,6 +33,18 @@ extern "C" {
 
     #[wasm_bindgen(typescript_type = "HashKey")]
     pub type HashKey;
+
+    #[wasm_bindgen(typescript_type = "MoveScore[]")]
+    pub type MoveScores;
+
+    #[wasm_bindgen(typescript_type = "MoveScore | undefined")]
+    pub type MaybeMoveScore;
+
+    #[wasm_bindgen(typescript_type = "[MoveScore, MoveScore] | undefined")]
+    pub type MaybeMoveScorePair;
+
+    #[wasm_bindgen(typescript_type = "Swap2Decision | undefined")]
+    pub type MaybeSwap2Decision;
 }
 
 #[wasm_bindgen]
@@ -64,6 +76,11 @@ impl GameAgent {
         self.config = config;
     }
 
+    #[wasm_bindgen(js_name = "skillLevel")]
+    pub fn skill_level(&mut self, level: Option<u8>) {
+        self.config.skill_level = level;
+    }
+
     pub fn command(&mut self, command: Command) -> Result<CommandResult, JsError> {
         let command: mintaka::protocol::command::Command = try_from_js_value(command)?;
 
@@ -97,12 +114,49 @@ impl GameAgent {
         Ok(to_js_value(&best_move))
     }
 
+    #[wasm_bindgen(js_name = "rankMoves")]
+    pub fn rank_moves(&mut self, count: usize, abort_handle: &JsAbortHandle) -> MoveScores {
+        let move_scores = self.inner.borrow_mut()
+            .rank_moves::<WebClock>(self.config, count, abort_handle.inner.clone());
+
+        to_js_value(&move_scores)
+    }
+
+    #[wasm_bindgen(js_name = "balanceOne")]
+    pub fn balance_one(&mut self, target: i32, abort_handle: &JsAbortHandle) -> MaybeMoveScore {
+        let move_score = self.inner.borrow_mut()
+            .balance_one::<WebClock>(self.config, target, abort_handle.inner.clone());
+
+        to_js_value(&move_score)
+    }
+
+    #[wasm_bindgen(js_name = "balanceTwo")]
+    pub fn balance_two(&mut self, target: i32, abort_handle: &JsAbortHandle) -> MaybeMoveScorePair {
+        let move_scores = self.inner.borrow_mut()
+            .balance_two::<WebClock>(self.config, target, abort_handle.inner.clone());
+
+        to_js_value(&move_scores)
+    }
+
+    #[wasm_bindgen(js_name = "swap2Decision")]
+    pub fn swap2_decision(&mut self, abort_handle: &JsAbortHandle) -> MaybeSwap2Decision {
+        let decision = self.inner.borrow_mut()
+            .swap2_decision::<WebClock>(self.config, abort_handle.inner.clone());
+
+        to_js_value(&decision)
+    }
+
     #[wasm_bindgen(js_name = "hashKey")]
     pub fn hash_key(&self) -> HashKey {
         to_js_value(&self.inner.borrow().state.board.hash_key)
     }
 }
 
+#[wasm_bindgen(js_name = "skillLevelFromElo")]
+pub fn skill_level_from_elo(elo: u32) -> u8 {
+    mintaka::skill::Skill::from_elo(elo).level()
+}
+
 pub struct JsResponseSender;
 
 impl ResponseSender for JsResponseSender {
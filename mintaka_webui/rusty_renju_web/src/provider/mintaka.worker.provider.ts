@@ -49,8 +49,10 @@ export const DefaultWorkerConfig: Config = {
         total_remaining: undefined,
         increment: duration(0),
         turn: duration(5),
+        control: { type: "Fischer" },
     },
     spawn_depth_specialist: false,
+    deterministic: false,
 }
 
 export const MaxWorkerConfig: Config = {
@@ -63,6 +65,7 @@ export const MaxWorkerConfig: Config = {
         total_remaining: undefined,
         increment: InfiniteDuration,
         turn: undefined,
+        control: { type: "Fischer" },
     },
     spawn_depth_specialist: true,
 }
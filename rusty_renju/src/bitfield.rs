@@ -3,9 +3,10 @@ use crate::notation::pos::Pos;
 use crate::{assert_struct_sizes, impl_debug_from_display};
 #[cfg(feature = "serde")]
 use base64::engine::{general_purpose, Engine as _};
-use std::fmt::{Display, Formatter};
-use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
-use std::simd::Simd;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+use core::simd::Simd;
 #[cfg(feature = "typeshare")]
 use typeshare::typeshare;
 use crate::utils::empty::Empty;
@@ -259,7 +260,7 @@ impl Iterator for BitfieldHotBitsIterator {
 }
 
 impl Display for Bitfield {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         let content = self.iter()
             .map(|is_hot|
                 if is_hot { "X" } else { "." }
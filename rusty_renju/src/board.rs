@@ -7,7 +7,9 @@ use crate::notation::rule::RuleKind;
 use crate::pattern::Patterns;
 use crate::slice::Slices;
 use crate::utils::empty::Empty;
-use std::hash::{Hash, Hasher};
+use alloc::vec::Vec;
+use alloc::boxed::Box;
+use core::hash::{Hash, Hasher};
 #[cfg(feature = "typeshare")]
 use typeshare::typeshare;
 use crate::slice_pattern;
@@ -52,7 +54,7 @@ impl<const R: RuleKind> Empty for Board<R> {
     }
 }
 
-#[derive(std::marker::ConstParamTy, Eq, PartialEq,)]
+#[derive(core::marker::ConstParamTy, Eq, PartialEq,)]
 pub enum MoveType {
     Set, Unset
 }
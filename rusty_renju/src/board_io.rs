@@ -1,4 +1,8 @@
-use std::error::Error;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::error::Error;
 use crate::bitfield::Bitfield;
 use crate::board::Board;
 use crate::board_iter::{BoardExportItem, BoardIterItem};
@@ -13,8 +17,8 @@ use crate::pattern::Pattern;
 use crate::slice::Slice;
 use crate::utils::empty::Empty;
 use crate::utils::str_utils::join_str_horizontally;
-use std::fmt::{Display, Formatter};
-use std::str::FromStr;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
 #[cfg(feature = "typeshare")]
 use typeshare::typeshare;
 
@@ -137,10 +141,12 @@ impl<const R: RuleKind> Board<R> {
         )
     }
 
+    #[cfg(feature = "std")]
     pub fn to_string_with_heatmap(&self, heatmap: [f32; pos::BOARD_SIZE], log_scale: bool) -> String {
         self.to_string_with_heatmap_and_last_moves(heatmap, log_scale, [MaybePos::NONE; 2])
     }
 
+    #[cfg(feature = "std")]
     pub fn to_string_with_heatmap_and_last_moves(&self, heatmap: [f32; pos::BOARD_SIZE], log_scale: bool, last_moves: [MaybePos; 2]) -> String {
         let min = heatmap.into_iter()
             .fold(f32::NAN, f32::min);
@@ -287,7 +293,7 @@ impl<const R: RuleKind> From<&History> for Board<R> {
 }
 
 impl<const R: RuleKind> Display for Board<R> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.render_with_attributes(
             |pos, &item| board_iter_item_to_symbol(self, pos, item),
             |_, _| None
@@ -343,7 +349,7 @@ impl FromStr for Slice {
 }
 
 impl Display for Slice {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         let content = (0..self.length)
             .map(|idx| match self.stone_kind(idx) {
                 Some(color) => char::from(color),
@@ -359,7 +365,7 @@ impl Display for Slice {
 
 #[macro_export] macro_rules! board {
     ($board_str:expr) => {{
-        use std::str::FromStr;
+        use core::str::FromStr;
 
         $crate::board::Board::<{ rusty_renju::notation::rule::RuleKind::Renju }>::from_str($board_str).unwrap()
     }};
@@ -403,7 +409,7 @@ pub enum BoardDeserializeError {
 }
 
 impl Display for BoardDeserializeError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             BoardDeserializeError::RuleKindMismatch => write!(f, "Invalid rule kind."),
             BoardDeserializeError::HashMismatch => write!(f, "Hash mismatch."),
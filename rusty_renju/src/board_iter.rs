@@ -5,7 +5,7 @@ use crate::notation::pos::Pos;
 use crate::notation::rule::{ForbiddenKind, RuleKind};
 use crate::pattern::Pattern;
 use crate::{index_to_col, index_to_row};
-use std::array;
+use core::array;
 
 #[repr(u64)]
 #[derive(Copy, Clone)]
@@ -45,7 +45,7 @@ impl<const R: RuleKind> Board<R> {
                     .or(slice.winner_idx::<{ Color::White }>().map(|idx| (Color::White, idx)))
                     .map(|(color, idx)| BoardWinner {
                         color,
-                        moves: std::array::from_fn(|sequence|
+                        moves: core::array::from_fn(|sequence|
                             slice.start_pos.directional_offset_unchecked(direction, idx as isize)
                                 .directional_offset_unchecked(direction, sequence as isize)
                         )
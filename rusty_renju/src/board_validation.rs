@@ -0,0 +1,210 @@
+use crate::board::Board;
+use crate::history::History;
+use crate::notation::color::Color;
+use crate::notation::direction::Direction;
+use crate::notation::pos;
+use crate::notation::pos::{MaybePos, Pos};
+use crate::notation::rule::RuleKind;
+use crate::slice::Slice;
+use crate::slice_pattern;
+use alloc::collections::BTreeSet;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+pub const RECONSTRUCTION_NODE_LIMIT: usize = 1 << 16;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum PositionError {
+    StoneCountMismatch { blacks: u8, whites: u8, player_color: Color },
+    BothSidesHaveFive,
+    PlayerAlreadyHasFive,
+    ForbiddenStone(Pos),
+    Unreachable,
+}
+
+impl Display for PositionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::StoneCountMismatch { blacks, whites, player_color } =>
+                write!(f, "{blacks} black and {whites} white stones are inconsistent with {player_color} to move"),
+            Self::BothSidesHaveFive => write!(f, "both sides have five in a row"),
+            Self::PlayerAlreadyHasFive => write!(f, "player to move already has five in a row"),
+            Self::ForbiddenStone(pos) => write!(f, "stone at {pos} could only have been placed on a forbidden point"),
+            Self::Unreachable => write!(f, "no legal move order leads to this position"),
+        }
+    }
+}
+
+impl core::error::Error for PositionError {}
+
+impl<const R: RuleKind> Board<R> {
+    pub fn has_five(&self, color: Color) -> bool {
+        let exact_five = matches!((R, color), (RuleKind::Renju, Color::Black) | (RuleKind::Gomoku, _));
+
+        self.iter_slices()
+            .any(|slice| {
+                let stones = slice.stones[color];
+
+                if exact_five {
+                    slice_pattern::contains_exact_five_in_a_row(stones)
+                } else {
+                    slice_pattern::contains_five_in_a_row(stones)
+                }
+            })
+    }
+
+    // stone counts, forbidden stones and fives, without searching for a move order
+    pub fn validate_stones(&self) -> Result<(), PositionError> {
+        let bitfield = self.slices.bitfield();
+
+        let blacks = bitfield[Color::Black].count_hots() as u8;
+        let whites = bitfield[Color::White].count_hots() as u8;
+
+        let parity_matches = match self.player_color {
+            Color::Black => blacks == whites,
+            Color::White => blacks == whites + 1,
+        };
+
+        if !parity_matches {
+            return Err(PositionError::StoneCountMismatch { blacks, whites, player_color: self.player_color });
+        }
+
+        if R == RuleKind::Renju
+            && let Some(pos) = self.find_overline_stone()
+        {
+            return Err(PositionError::ForbiddenStone(pos));
+        }
+
+        // a five of the last mover is a finished game, which stays valid for replay
+        match (self.has_five(self.player_color), self.has_five(!self.player_color)) {
+            (true, true) => Err(PositionError::BothSidesHaveFive),
+            (true, false) => Err(PositionError::PlayerAlreadyHasFive),
+            _ => Ok(()),
+        }
+    }
+
+    // returns a legal move order when one is found, none when the search budget runs out
+    pub fn validate(&self) -> Result<Option<History>, PositionError> {
+        self.validate_stones()?;
+
+        let mut reconstructor = Reconstructor {
+            dead_ends: BTreeSet::new(),
+            moves: Vec::with_capacity(self.stones as usize),
+            nodes: 0,
+        };
+
+        match reconstructor.search(*self) {
+            Some(true) => {
+                let actions = reconstructor.moves.into_iter()
+                    .rev()
+                    .map(MaybePos::from)
+                    .collect::<Vec<_>>();
+
+                Ok(History::try_from(actions).ok())
+            },
+            Some(false) => Err(PositionError::Unreachable),
+            None => Ok(None),
+        }
+    }
+
+    pub fn reconstruct_history(&self) -> Option<History> {
+        self.validate().ok().flatten()
+    }
+
+    fn iter_slices(&self) -> impl Iterator<Item = &Slice> {
+        self.slices.horizontal_slices.iter()
+            .chain(self.slices.vertical_slices.iter())
+            .chain(self.slices.ascending_slices.iter())
+            .chain(self.slices.descending_slices.iter())
+    }
+
+    fn find_overline_stone(&self) -> Option<Pos> {
+        self.slices.horizontal_slices.iter().map(|slice| (Direction::Horizontal, slice))
+            .chain(self.slices.vertical_slices.iter().map(|slice| (Direction::Vertical, slice)))
+            .chain(self.slices.ascending_slices.iter().map(|slice| (Direction::Ascending, slice)))
+            .chain(self.slices.descending_slices.iter().map(|slice| (Direction::Descending, slice)))
+            .find_map(|(direction, slice)|
+                slice_pattern::overline_idx(slice.stones[Color::Black])
+                    .map(|idx| slice.start_pos.directional_offset_unchecked(direction, idx as isize))
+            )
+    }
+}
+
+struct Reconstructor {
+    dead_ends: BTreeSet<u64>,
+    moves: Vec<Pos>,
+    nodes: usize,
+}
+
+struct ReconstructionFrame {
+    candidates: Vec<Pos>,
+    next: usize,
+}
+
+impl ReconstructionFrame {
+    fn new<const R: RuleKind>(board: &Board<R>) -> Self {
+        let mut candidates = board.slices.bitfield()[!board.player_color].iter_hot_pos()
+            .collect::<Vec<_>>();
+
+        // later moves tend to be further from the center
+        candidates.sort_by_key(|candidate| core::cmp::Reverse(candidate.distance(pos::CENTER)));
+
+        Self { candidates, next: 0 }
+    }
+}
+
+impl Reconstructor {
+    // backward search: remove the last mover's stones one by one, undoing them in place
+    // on a single board, since a frame per stone would overflow the stack on crowded boards
+    fn search<const R: RuleKind>(&mut self, mut board: Board<R>) -> Option<bool> {
+        if board.stones == 0 {
+            return Some(true);
+        }
+
+        let mut stack = vec![ReconstructionFrame::new(&board)];
+
+        while let Some(frame) = stack.last_mut() {
+            let Some(&pos) = frame.candidates.get(frame.next) else {
+                self.dead_ends.insert(board.hash_key.into());
+                stack.pop();
+
+                if let Some(pos) = self.moves.pop() {
+                    board.set_mut(pos);
+                }
+
+                continue;
+            };
+
+            frame.next += 1;
+            self.nodes += 1;
+
+            if self.nodes > RECONSTRUCTION_NODE_LIMIT {
+                return None;
+            }
+
+            let last_color = !board.player_color;
+
+            board.unset_mut(pos);
+
+            if board.has_five(Color::Black)
+                || board.has_five(Color::White)
+                || (R == RuleKind::Renju && last_color == Color::Black && board.patterns.is_forbidden(pos))
+                || self.dead_ends.contains(&u64::from(board.hash_key))
+            {
+                board.set_mut(pos);
+                continue;
+            }
+
+            self.moves.push(pos);
+
+            if board.stones == 0 {
+                return Some(true);
+            }
+
+            stack.push(ReconstructionFrame::new(&board));
+        }
+
+        Some(false)
+    }
+}
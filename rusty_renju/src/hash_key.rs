@@ -3,9 +3,9 @@ use crate::notation::color::Color;
 use crate::notation::pos::Pos;
 use crate::utils::empty::Empty;
 use crate::impl_debug_from_display;
-use std::fmt::{Display, Formatter};
-use std::hash::{Hash, Hasher};
-use std::str::FromStr;
+use core::fmt::{Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::str::FromStr;
 #[cfg(feature = "typeshare")]
 use typeshare::typeshare;
 
@@ -42,7 +42,7 @@ impl Hash for HashKey {
 }
 
 impl Display for HashKey {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "0x{:016x}", self.0)
     }
 }
@@ -54,7 +54,7 @@ impl From<HashKey> for u64 {
 }
 
 impl FromStr for HashKey {
-    type Err = std::num::ParseIntError;
+    type Err = core::num::ParseIntError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         u64::from_str_radix(&s[2 ..], 16).map(Self)
@@ -4,14 +4,18 @@ use crate::impl_debug_from_display;
 use crate::notation::color::Color;
 use crate::notation::pos;
 use crate::notation::pos::{MaybePos, Pos};
-use std::fmt::{Debug, Display, Formatter};
-use std::iter;
-use std::ops::{Index, IndexMut};
-use std::str::FromStr;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use alloc::vec;
+use core::fmt::{Debug, Display, Formatter};
+use core::iter;
+use core::ops::{Index, IndexMut};
+use core::str::FromStr;
 #[cfg(feature = "typeshare")]
 use typeshare::typeshare;
 use crate::notation::rule::RuleKind;
 use crate::utils::empty::Empty;
+use crate::history_format::HistoryFormat;
 
 pub const MAX_HISTORY_SIZE: usize = 248;
 
@@ -172,7 +176,7 @@ impl History {
 }
 
 impl Display for History {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         let history = self.iter()
             .map(|&action|
                 match action {
@@ -193,41 +197,9 @@ impl FromStr for History {
     type Err = &'static str;
 
     fn from_str(source: &str) -> Result<Self, Self::Err> {
-        let mut history = History::empty();
-        let source = source.to_lowercase();
-        let bytes = source.as_bytes();
-        let mut idx = 0;
-
-        fn detect_token(bytes: &[u8]) -> Option<(Pos, usize)> {
-            if bytes.len() < 2
-                || !(b'a' .. (b'a' + pos::BOARD_WIDTH)).contains(&bytes[0])
-            {
-                return None;
-            }
-
-            let len =
-                if bytes.len() > 2 && bytes[2].is_ascii_digit() {
-                    3
-                } else if bytes[1].is_ascii_digit() {
-                    2
-                } else {
-                    return None;
-                };
-
-            Pos::from_str(str::from_utf8(&bytes[.. len]).unwrap()).ok()
-                .map(|pos| (pos, len))
-        }
-
-        while idx < bytes.len() {
-            if let Some((pos, len)) = detect_token(&bytes[idx ..]) {
-                history.set_mut(pos);
-                idx += len;
-            } else {
-                idx += 1;
-            }
-        }
-
-        Ok(history)
+        HistoryFormat::detect(source)
+            .unwrap_or(HistoryFormat::Coordinates)
+            .parse(source)
     }
 }
 
@@ -238,7 +210,7 @@ pub enum HistoryError {
 }
 
 impl Display for HistoryError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             HistoryError::HistoryTooLong => write!(f, "history is too long"),
             HistoryError::WhiteIsLongerThanBlack => write!(f, "white's history is longer than black's history"),
@@ -246,7 +218,7 @@ impl Display for HistoryError {
     }
 }
 
-impl std::error::Error for HistoryError {}
+impl core::error::Error for HistoryError {}
 
 impl<const R: RuleKind> TryFrom<&Board<R>> for History {
     type Error = HistoryError;
@@ -0,0 +1,238 @@
+use crate::board_io::HISTORY_LITERAL_PASS;
+use crate::history::History;
+use crate::notation::pos;
+use crate::notation::pos::{MaybePos, Pos};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::format;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+pub const POSITION_CODE_PASS: &str = "--";
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum HistoryFormat {
+    // h8,h9,i10
+    Coordinates,
+    // 7,7 7,8 8,9 (0-indexed x,y pairs, as in gomocup)
+    Gomocup,
+    // 070707080809 (zero-padded 0-indexed xxyy quads, as in piskvork FORBID)
+    Numeric,
+    // hhhgih (one url-safe letter per axis, col first)
+    PositionCode,
+}
+
+impl HistoryFormat {
+    pub const ALL: [Self; 4] = [Self::Coordinates, Self::Gomocup, Self::Numeric, Self::PositionCode];
+
+    pub fn detect(source: &str) -> Option<Self> {
+        let source = source.trim();
+
+        if source.is_empty() {
+            return None;
+        }
+
+        let is_board_letter = |c: char| ('a' .. (b'a' + pos::BOARD_WIDTH) as char).contains(&c.to_ascii_lowercase());
+        let is_separator = |c: char| c.is_ascii_whitespace() || c == ';';
+
+        if source.chars().all(|c| c.is_ascii_digit() || c == ',' || is_separator(c)) {
+            return if source.contains(',') {
+                Some(Self::Gomocup)
+            } else if source.chars().filter(char::is_ascii_digit).count().is_multiple_of(4) {
+                Some(Self::Numeric)
+            } else {
+                None
+            };
+        }
+
+        if source.chars().any(|c| c.is_ascii_digit()) {
+            return Some(Self::Coordinates);
+        }
+
+        let code = source.chars()
+            .filter(|&c| !is_separator(c))
+            .collect::<String>();
+
+        (code.len().is_multiple_of(2)
+            && code.chars().all(|c| is_board_letter(c) || c == '-')
+        ).then_some(Self::PositionCode)
+    }
+
+    pub fn parse(&self, source: &str) -> Result<History, &'static str> {
+        let actions = match self {
+            Self::Coordinates => return parse_coordinates(source),
+            Self::Gomocup => parse_gomocup(source)?,
+            Self::Numeric => parse_numeric(source)?,
+            Self::PositionCode => parse_position_code(source)?,
+        };
+
+        History::try_from(actions).map_err(|_| "history is too long.")
+    }
+
+    pub fn format(&self, history: &History) -> Result<String, &'static str> {
+        match self {
+            Self::Coordinates => Ok(history.to_string()),
+            Self::Gomocup => history.iter()
+                .map(|action| action.ok()
+                    .map(|pos| format!("{},{}", pos.col(), pos.row()))
+                    .ok_or("pass is not representable.")
+                )
+                .collect::<Result<Vec<_>, _>>()
+                .map(|moves| moves.join(" ")),
+            Self::Numeric => history.iter()
+                .map(|action| action.ok()
+                    .map(|pos| format!("{:02}{:02}", pos.col(), pos.row()))
+                    .ok_or("pass is not representable.")
+                )
+                .collect(),
+            Self::PositionCode => Ok(history.iter()
+                .map(|action| match action.ok() {
+                    Some(pos) => format!("{}{}", (b'a' + pos.col()) as char, (b'a' + pos.row()) as char),
+                    None => POSITION_CODE_PASS.to_string(),
+                })
+                .collect()),
+        }
+    }
+}
+
+impl Display for HistoryFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Coordinates => write!(f, "coordinates"),
+            Self::Gomocup => write!(f, "gomocup"),
+            Self::Numeric => write!(f, "numeric"),
+            Self::PositionCode => write!(f, "position-code"),
+        }
+    }
+}
+
+impl FromStr for HistoryFormat {
+    type Err = &'static str;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        match source.to_ascii_lowercase().as_str() {
+            "coordinates" | "coord" => Ok(Self::Coordinates),
+            "gomocup" | "piskvork" => Ok(Self::Gomocup),
+            "numeric" | "num" => Ok(Self::Numeric),
+            "position-code" | "code" => Ok(Self::PositionCode),
+            _ => Err("unknown history format."),
+        }
+    }
+}
+
+fn pos_from_col_row(col: u8, row: u8) -> Result<Pos, &'static str> {
+    (col < pos::BOARD_WIDTH && row < pos::BOARD_WIDTH)
+        .then(|| Pos::from_cartesian(row, col))
+        .ok_or("position out of range.")
+}
+
+fn parse_coordinates(source: &str) -> Result<History, &'static str> {
+    let mut history = History::EMPTY;
+    let source = source.to_lowercase();
+    let bytes = source.as_bytes();
+    let mut idx = 0;
+
+    fn detect_token(bytes: &[u8]) -> Option<(MaybePos, usize)> {
+        if bytes.starts_with(HISTORY_LITERAL_PASS.as_bytes()) {
+            return Some((MaybePos::NONE, HISTORY_LITERAL_PASS.len()));
+        }
+
+        if bytes.len() < 2
+            || !(b'a' .. (b'a' + pos::BOARD_WIDTH)).contains(&bytes[0])
+        {
+            return None;
+        }
+
+        let len =
+            if bytes.len() > 2 && bytes[2].is_ascii_digit() {
+                3
+            } else if bytes[1].is_ascii_digit() {
+                2
+            } else {
+                return None;
+            };
+
+        Pos::from_str(str::from_utf8(&bytes[.. len]).unwrap()).ok()
+            .map(|pos| (pos.into(), len))
+    }
+
+    while idx < bytes.len() {
+        if let Some((action, len)) = detect_token(&bytes[idx ..]) {
+            if history.len() == pos::BOARD_SIZE {
+                return Err("history is too long.");
+            }
+
+            history.action_mut(action);
+            idx += len;
+        } else {
+            idx += 1;
+        }
+    }
+
+    Ok(history)
+}
+
+fn parse_gomocup(source: &str) -> Result<Vec<MaybePos>, &'static str> {
+    source.split(|c: char| c.is_ascii_whitespace() || c == ';')
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            let [x, y]: [&str; 2] = token.split(',')
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|_| "invalid gomocup token.")?;
+
+            let x = x.parse::<u8>().map_err(|_| "invalid x coordinate.")?;
+            let y = y.parse::<u8>().map_err(|_| "invalid y coordinate.")?;
+
+            pos_from_col_row(x, y).map(MaybePos::from)
+        })
+        .collect()
+}
+
+fn parse_numeric(source: &str) -> Result<Vec<MaybePos>, &'static str> {
+    let digits = source.bytes()
+        .filter(u8::is_ascii_digit)
+        .map(|digit| digit - b'0')
+        .collect::<Vec<_>>();
+
+    if !digits.len().is_multiple_of(4) {
+        return Err("numeric history length must be a multiple of four.");
+    }
+
+    digits.chunks(4)
+        .map(|chunk| pos_from_col_row(chunk[0] * 10 + chunk[1], chunk[2] * 10 + chunk[3]).map(MaybePos::from))
+        .collect()
+}
+
+fn parse_position_code(source: &str) -> Result<Vec<MaybePos>, &'static str> {
+    let code = source.bytes()
+        .filter(|byte| !byte.is_ascii_whitespace() && *byte != b';')
+        .map(|byte| byte.to_ascii_lowercase())
+        .collect::<Vec<_>>();
+
+    if !code.len().is_multiple_of(2) {
+        return Err("position code length must be even.");
+    }
+
+    code.chunks(2)
+        .map(|chunk| match chunk {
+            [b'-', b'-'] => Ok(MaybePos::NONE),
+            &[col, row] if col.is_ascii_lowercase() && row.is_ascii_lowercase() =>
+                pos_from_col_row(col - b'a', row - b'a').map(MaybePos::from),
+            _ => Err("invalid position code token."),
+        })
+        .collect()
+}
+
+impl History {
+    pub fn from_str_with_format(source: &str) -> Result<(Self, HistoryFormat), &'static str> {
+        let format = HistoryFormat::detect(source).ok_or("unknown history format.")?;
+
+        format.parse(source).map(|history| (history, format))
+    }
+
+    pub fn to_string_with_format(&self, format: HistoryFormat) -> Result<String, &'static str> {
+        format.format(self)
+    }
+}
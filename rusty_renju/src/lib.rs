@@ -2,6 +2,8 @@
 #![feature(adt_const_params)]
 #![feature(portable_simd)]
 #![cfg_attr(target_arch = "aarch64", feature(stdarch_aarch64_prefetch))]
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
 extern crate core;
 
 pub mod board;
@@ -15,8 +17,11 @@ pub mod slice_pattern;
 pub mod board_iter;
 pub mod bitfield;
 pub mod history;
+pub mod history_format;
 pub mod win_rate;
 pub mod board_utils;
+pub mod board_validation;
+pub mod threat;
 pub mod pattern_index;
 pub mod hash_key;
 mod hash_table;
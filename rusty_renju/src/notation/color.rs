@@ -1,12 +1,12 @@
 use crate::board_io::{SYMBOL_BLACK, SYMBOL_WHITE};
-use std::fmt::Formatter;
-use std::ops::Not;
-use std::str::FromStr;
+use core::fmt::Formatter;
+use core::ops::Not;
+use core::str::FromStr;
 #[cfg(feature = "typeshare")]
 use typeshare::typeshare;
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(std::marker::ConstParamTy, PartialEq, Eq, Clone, Copy, Debug)]
+#[derive(core::marker::ConstParamTy, PartialEq, Eq, Clone, Copy, Debug)]
 #[repr(u8)]
 pub enum Color {
     Black = 0,
@@ -79,8 +79,8 @@ impl TryFrom<u8> for Color {
     }
 }
 
-impl std::fmt::Display for Color {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Color {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "{self:?}")
     }
 }
@@ -88,13 +88,13 @@ impl std::fmt::Display for Color {
 #[derive(Debug)]
 pub struct UnknownColorError;
 
-impl std::fmt::Display for UnknownColorError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for UnknownColorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "unknown color")
     }
 }
 
-impl std::error::Error for UnknownColorError {}
+impl core::error::Error for UnknownColorError {}
 
 impl FromStr for Color {
     type Err = UnknownColorError;
@@ -123,7 +123,7 @@ macro_rules! impl_color_container {
             }
         }
 
-        impl<T> std::ops::Index<Color> for $name<T> {
+        impl<T> core::ops::Index<Color> for $name<T> {
             type Output = T;
 
             #[inline]
@@ -132,7 +132,7 @@ macro_rules! impl_color_container {
             }
         }
 
-        impl<T> std::ops::IndexMut<Color> for $name<T> {
+        impl<T> core::ops::IndexMut<Color> for $name<T> {
             #[inline]
             fn index_mut(&mut self, index: Color) -> &mut Self::Output {
                 &mut self.0[index as usize]
@@ -161,8 +161,8 @@ macro_rules! impl_color_container {
             }
         }
 
-        impl <T: std::fmt::Debug> std::fmt::Debug for $name<T> {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        impl <T: core::fmt::Debug> core::fmt::Debug for $name<T> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 f.debug_struct("ColorContainer")
                     .field("black", &self.0[0])
                     .field("white", &self.0[1])
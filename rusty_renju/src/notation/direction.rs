@@ -1,8 +1,9 @@
-use std::fmt::Debug;
-use std::ops::{Index, IndexMut};
+use core::fmt::Debug;
+use core::ops::{Index, IndexMut};
 use crate::utils::empty::Empty;
 
-#[derive(std::marker::ConstParamTy, PartialEq, Eq, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(core::marker::ConstParamTy, PartialEq, Eq, Copy, Clone, Debug)]
 #[repr(u8)]
 pub enum Direction {
     Horizontal = 0,
@@ -15,7 +16,7 @@ impl From<u8> for Direction {
     fn from(value: u8) -> Self {
         debug_assert!(value < 4);
 
-        unsafe { std::mem::transmute(value) }
+        unsafe { core::mem::transmute(value) }
     }
 }
 
@@ -77,7 +78,7 @@ impl <T: Default> Default for DirectionContainer<T> {
 }
 
 impl <T: Debug> Debug for DirectionContainer<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("DirectionContainer")
             .field("horizontal", &self.0[0])
             .field("vertical", &self.0[1])
@@ -1,9 +1,9 @@
 use crate::notation::direction::Direction;
 use crate::utils::str_utils::u8_from_str;
 use crate::{const_for, const_max, impl_debug_from_display};
-use std::fmt::{Debug, Display, Formatter};
-use std::ops::{Index, IndexMut};
-use std::str::FromStr;
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::{Index, IndexMut};
+use core::str::FromStr;
 #[cfg(feature = "typeshare")]
 use typeshare::typeshare;
 
@@ -164,7 +164,7 @@ pub enum PosError {
 }
 
 impl Display for PosError {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::InvalidRowCharter => write!(f, "Invalid row charter"),
             Self::OutOfRange => write!(f, "Out of range"),
@@ -172,7 +172,7 @@ impl Display for PosError {
     }
 }
 
-impl std::error::Error for PosError {}
+impl core::error::Error for PosError {}
 
 impl FromStr for Pos {
     type Err = PosError;
@@ -191,7 +191,7 @@ impl FromStr for Pos {
 }
 
 impl Display for Pos {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}{}", (self.col() + b'a') as char, self.row() + 1)
     }
 }
@@ -340,7 +340,7 @@ impl FromStr for MaybePos {
 }
 
 impl Display for MaybePos {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match *self {
             MaybePos::NONE => write!(f, "none"),
             _ => write!(f, "{}", self.unwrap())
@@ -1,9 +1,10 @@
-use std::fmt::Display;
+use core::fmt::Display;
+use core::str::FromStr;
 #[cfg(feature = "typeshare")]
 use typeshare::typeshare;
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(std::marker::ConstParamTy, Default, PartialEq, Eq, Copy, Clone, Debug)]
+#[derive(core::marker::ConstParamTy, Default, PartialEq, Eq, Copy, Clone, Debug)]
 pub enum RuleKind {
     #[default] Renju = 0,
     Gomoku = 1,
@@ -11,7 +12,7 @@ pub enum RuleKind {
 }
 
 impl Display for RuleKind {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::Renju => write!(f, "Renju"),
             Self::Gomoku => write!(f, "Gomoku"),
@@ -20,6 +21,30 @@ impl Display for RuleKind {
     }
 }
 
+#[derive(Debug)]
+pub struct UnknownRuleError;
+
+impl Display for UnknownRuleError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "unknown rule")
+    }
+}
+
+impl core::error::Error for UnknownRuleError {}
+
+impl FromStr for RuleKind {
+    type Err = UnknownRuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "renju" => Ok(Self::Renju),
+            "gomoku" => Ok(Self::Gomoku),
+            "freestyle" => Ok(Self::Freestyle),
+            &_ => Err(UnknownRuleError)
+        }
+    }
+}
+
 #[cfg_attr(feature = "typeshare", typeshare(serialized_as = "String"))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(PartialEq, Eq, Copy, Clone, Debug)]
@@ -1,2 +1,6 @@
+#[cfg(feature = "std")]
 pub mod opening_utils;
+#[cfg(feature = "std")]
 pub mod opening_agent;
+pub mod symmetry;
+pub mod standard_opening;
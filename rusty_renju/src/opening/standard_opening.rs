@@ -0,0 +1,312 @@
+use crate::board::Board;
+use crate::history::History;
+use crate::notation::color::Color;
+use crate::notation::pos;
+use crate::notation::pos::{MaybePos, Pos};
+use crate::notation::rule::RuleKind;
+use crate::opening::symmetry::Symmetry;
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::format;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+#[cfg(feature = "typeshare")]
+use typeshare::typeshare;
+
+#[cfg_attr(feature = "typeshare", typeshare)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Hash, Eq, PartialEq, Copy, Clone, Debug)]
+pub enum OpeningGroup {
+    Direct,
+    Indirect,
+}
+
+impl OpeningGroup {
+    // relative (row, col) of the second move, seen from the first move
+    const fn second_move_offset(&self) -> (isize, isize) {
+        match self {
+            Self::Direct => (1, 0),
+            Self::Indirect => (1, 1),
+        }
+    }
+}
+
+#[cfg_attr(feature = "typeshare", typeshare)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Hash, Eq, PartialEq, Copy, Clone, Debug)]
+pub enum StandardOpening {
+    Kansei,
+    Keigetsu,
+    Sosei,
+    Kagetsu,
+    Zangetsu,
+    Ugetsu,
+    Kinsei,
+    Shogetsu,
+    Kyugetsu,
+    Shingetsu,
+    Zuisei,
+    Sangetsu,
+    Yusei,
+    Chosei,
+    Kyogetsu,
+    Kosei,
+    Suigetsu,
+    Ryusei,
+    Ungetsu,
+    Hogetsu,
+    Rangetsu,
+    Gingetsu,
+    Myojo,
+    Shagetsu,
+    Meigetsu,
+    Suisei,
+}
+
+impl StandardOpening {
+    pub const ALL: [Self; 26] = [
+        Self::Kansei, Self::Keigetsu, Self::Sosei, Self::Kagetsu, Self::Zangetsu,
+        Self::Ugetsu, Self::Kinsei, Self::Shogetsu, Self::Kyugetsu, Self::Shingetsu,
+        Self::Zuisei, Self::Sangetsu, Self::Yusei,
+        Self::Chosei, Self::Kyogetsu, Self::Kosei, Self::Suigetsu, Self::Ryusei,
+        Self::Ungetsu, Self::Hogetsu, Self::Rangetsu, Self::Gingetsu, Self::Myojo,
+        Self::Shagetsu, Self::Meigetsu, Self::Suisei,
+    ];
+
+    pub const fn group(&self) -> OpeningGroup {
+        if (*self as usize) < 13 {
+            OpeningGroup::Direct
+        } else {
+            OpeningGroup::Indirect
+        }
+    }
+
+    // 1-based index inside the group, as in "D4" or "I7"
+    pub const fn number(&self) -> usize {
+        *self as usize % 13 + 1
+    }
+
+    pub fn code(&self) -> String {
+        match self.group() {
+            OpeningGroup::Direct => format!("D{}", self.number()),
+            OpeningGroup::Indirect => format!("I{}", self.number()),
+        }
+    }
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Kansei => "Kansei",
+            Self::Keigetsu => "Keigetsu",
+            Self::Sosei => "Sosei",
+            Self::Kagetsu => "Kagetsu",
+            Self::Zangetsu => "Zangetsu",
+            Self::Ugetsu => "Ugetsu",
+            Self::Kinsei => "Kinsei",
+            Self::Shogetsu => "Shogetsu",
+            Self::Kyugetsu => "Kyugetsu",
+            Self::Shingetsu => "Shingetsu",
+            Self::Zuisei => "Zuisei",
+            Self::Sangetsu => "Sangetsu",
+            Self::Yusei => "Yusei",
+            Self::Chosei => "Chosei",
+            Self::Kyogetsu => "Kyogetsu",
+            Self::Kosei => "Kosei",
+            Self::Suigetsu => "Suigetsu",
+            Self::Ryusei => "Ryusei",
+            Self::Ungetsu => "Ungetsu",
+            Self::Hogetsu => "Hogetsu",
+            Self::Rangetsu => "Rangetsu",
+            Self::Gingetsu => "Gingetsu",
+            Self::Myojo => "Myojo",
+            Self::Shagetsu => "Shagetsu",
+            Self::Meigetsu => "Meigetsu",
+            Self::Suisei => "Suisei",
+        }
+    }
+
+    // relative (row, col) of the third move in the canonical orientation, seen from the first move
+    const fn third_move_offset(&self) -> (isize, isize) {
+        match self {
+            Self::Kansei => (2, 0),
+            Self::Keigetsu => (2, 1),
+            Self::Sosei => (2, 2),
+            Self::Kagetsu => (1, 1),
+            Self::Zangetsu => (1, 2),
+            Self::Ugetsu => (0, 1),
+            Self::Kinsei => (0, 2),
+            Self::Shogetsu => (-1, 1),
+            Self::Kyugetsu => (-1, 2),
+            Self::Shingetsu => (-2, 1),
+            Self::Zuisei => (-2, 0),
+            Self::Sangetsu => (-2, 2),
+            Self::Yusei => (-1, 0),
+            Self::Chosei => (2, 2),
+            Self::Kyogetsu => (1, 2),
+            Self::Kosei => (0, 2),
+            Self::Suigetsu => (-1, 2),
+            Self::Ryusei => (-2, 2),
+            Self::Ungetsu => (0, 1),
+            Self::Hogetsu => (-1, 1),
+            Self::Rangetsu => (-2, 1),
+            Self::Gingetsu => (-1, 0),
+            Self::Myojo => (-2, 0),
+            Self::Shagetsu => (-2, -1),
+            Self::Meigetsu => (-1, -1),
+            Self::Suisei => (-2, -2),
+        }
+    }
+
+    pub fn moves(&self) -> [Pos; 3] {
+        self.transformed_moves(Symmetry::Identity)
+    }
+
+    pub fn transformed_moves(&self, symmetry: Symmetry) -> [Pos; 3] {
+        let (second_row, second_col) = self.group().second_move_offset();
+        let (third_row, third_col) = self.third_move_offset();
+
+        [
+            pos::CENTER,
+            pos::CENTER.offset_unchecked(second_row, second_col),
+            pos::CENTER.offset_unchecked(third_row, third_col),
+        ].map(|pos| symmetry.apply(pos))
+    }
+
+    pub fn history(&self) -> History {
+        self.transformed_history(Symmetry::Identity)
+    }
+
+    pub fn transformed_history(&self, symmetry: Symmetry) -> History {
+        self.transformed_moves(symmetry).into_iter()
+            .fold(History::EMPTY, History::set)
+    }
+
+    pub fn board<const R: RuleKind>(&self) -> Board<R> {
+        Board::from(&self.history())
+    }
+
+    pub fn classify(first: Pos, second: Pos, third: Pos) -> Option<OpeningMatch> {
+        let offset_from_first = |pos: Pos| (
+            pos.row() as isize - first.row() as isize,
+            pos.col() as isize - first.col() as isize,
+        );
+
+        let (second_row, second_col) = offset_from_first(second);
+        let (third_row, third_col) = offset_from_first(third);
+
+        Symmetry::ALL.into_iter()
+            .find_map(|symmetry| {
+                let second_offset = symmetry.apply_offset(second_row, second_col);
+                let third_offset = symmetry.apply_offset(third_row, third_col);
+
+                Self::ALL.into_iter()
+                    .find(|opening|
+                        opening.group().second_move_offset() == second_offset
+                            && opening.third_move_offset() == third_offset
+                    )
+                    .map(|opening| OpeningMatch {
+                        opening,
+                        symmetry: symmetry.inverse(),
+                        origin: first,
+                    })
+            })
+    }
+}
+
+impl Display for StandardOpening {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug)]
+pub struct UnknownOpeningError;
+
+impl Display for UnknownOpeningError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "unknown opening")
+    }
+}
+
+impl core::error::Error for UnknownOpeningError {}
+
+impl FromStr for StandardOpening {
+    type Err = UnknownOpeningError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter()
+            .find(|opening|
+                opening.name().eq_ignore_ascii_case(source)
+                    || opening.code().eq_ignore_ascii_case(source)
+            )
+            .ok_or(UnknownOpeningError)
+    }
+}
+
+#[cfg_attr(feature = "typeshare", typeshare)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct OpeningMatch {
+    pub opening: StandardOpening,
+    // maps the canonical opening onto the actual position, as transformed_history expects
+    pub symmetry: Symmetry,
+    pub origin: Pos,
+}
+
+impl OpeningMatch {
+    pub fn to_canonical(&self, pos: Pos) -> Option<Pos> {
+        let (row, col) = self.symmetry.inverse().apply_offset(
+            pos.row() as isize - self.origin.row() as isize,
+            pos.col() as isize - self.origin.col() as isize,
+        );
+
+        pos::CENTER.offset(row, col)
+    }
+
+    pub fn from_canonical(&self, pos: Pos) -> Option<Pos> {
+        let center = pos::CENTER_ROW_COL as isize;
+
+        let (row, col) = self.symmetry.apply_offset(
+            pos.row() as isize - center,
+            pos.col() as isize - center,
+        );
+
+        self.origin.offset(row, col)
+    }
+}
+
+impl History {
+    pub fn standard_opening(&self) -> Option<OpeningMatch> {
+        let [first, second, third] = self.actions().get(.. 3)?.try_into().ok()?;
+
+        StandardOpening::classify(
+            MaybePos::ok(first)?,
+            MaybePos::ok(second)?,
+            MaybePos::ok(third)?,
+        )
+    }
+}
+
+impl<const R: RuleKind> Board<R> {
+    // boards carry no move order; a black stone on the center is preferred as the first move
+    pub fn standard_opening(&self) -> Option<OpeningMatch> {
+        if self.stones != 3 {
+            return None;
+        }
+
+        let bitfield = self.slices.bitfield();
+
+        let mut blacks = bitfield[Color::Black].iter_hot_pos().collect::<Vec<_>>();
+        let white = bitfield[Color::White].first_pos()?;
+
+        if blacks.len() != 2 {
+            return None;
+        }
+
+        if blacks[1] == pos::CENTER {
+            blacks.swap(0, 1);
+        }
+
+        StandardOpening::classify(blacks[0], white, blacks[1])
+            .or_else(|| StandardOpening::classify(blacks[1], white, blacks[0]))
+    }
+}
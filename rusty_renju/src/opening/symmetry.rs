@@ -0,0 +1,77 @@
+use crate::notation::pos;
+use crate::notation::pos::Pos;
+use core::fmt::{Display, Formatter};
+#[cfg(feature = "typeshare")]
+use typeshare::typeshare;
+
+#[cfg_attr(feature = "typeshare", typeshare)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Hash, Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Symmetry {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+    FlipDiagonal,
+    FlipAntiDiagonal,
+}
+
+impl Symmetry {
+    pub const ALL: [Self; 8] = [
+        Self::Identity,
+        Self::Rotate90,
+        Self::Rotate180,
+        Self::Rotate270,
+        Self::FlipHorizontal,
+        Self::FlipVertical,
+        Self::FlipDiagonal,
+        Self::FlipAntiDiagonal,
+    ];
+
+    pub const fn inverse(&self) -> Self {
+        match self {
+            Self::Rotate90 => Self::Rotate270,
+            Self::Rotate270 => Self::Rotate90,
+            &symmetry => symmetry,
+        }
+    }
+
+    // counter-clockwise rotations, row grows upward
+    pub const fn apply_offset(&self, row: isize, col: isize) -> (isize, isize) {
+        match self {
+            Self::Identity => (row, col),
+            Self::Rotate90 => (col, -row),
+            Self::Rotate180 => (-row, -col),
+            Self::Rotate270 => (-col, row),
+            Self::FlipHorizontal => (row, -col),
+            Self::FlipVertical => (-row, col),
+            Self::FlipDiagonal => (col, row),
+            Self::FlipAntiDiagonal => (-col, -row),
+        }
+    }
+
+    pub const fn apply(&self, pos: Pos) -> Pos {
+        let center = pos::CENTER_ROW_COL as isize;
+
+        let (row, col) = self.apply_offset(pos.row() as isize - center, pos.col() as isize - center);
+
+        Pos::from_cartesian((row + center) as u8, (col + center) as u8)
+    }
+}
+
+impl Display for Symmetry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Identity => write!(f, "identity"),
+            Self::Rotate90 => write!(f, "rotate-90"),
+            Self::Rotate180 => write!(f, "rotate-180"),
+            Self::Rotate270 => write!(f, "rotate-270"),
+            Self::FlipHorizontal => write!(f, "flip-horizontal"),
+            Self::FlipVertical => write!(f, "flip-vertical"),
+            Self::FlipDiagonal => write!(f, "flip-diagonal"),
+            Self::FlipAntiDiagonal => write!(f, "flip-anti-diagonal"),
+        }
+    }
+}
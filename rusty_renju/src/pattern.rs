@@ -49,13 +49,13 @@ assert_struct_sizes!(Pattern, size=4, align=1);
 
 impl From<Pattern> for u32 {
     fn from(value: Pattern) -> Self {
-        unsafe { std::mem::transmute::<Pattern, u32>(value) }
+        unsafe { core::mem::transmute::<Pattern, u32>(value) }
     }
 }
 
 impl From<u32> for Pattern {
     fn from(value: u32) -> Self {
-        unsafe { std::mem::transmute::<u32, Pattern>(value) }
+        unsafe { core::mem::transmute::<u32, Pattern>(value) }
     }
 }
 
@@ -169,7 +169,7 @@ pub struct Patterns<const R: RuleKind> {
 impl<const R: RuleKind> Empty for Patterns<R> {
     fn empty() -> Self {
         Self {
-            field: unsafe { std::mem::zeroed() },
+            field: unsafe { core::mem::zeroed() },
             indexes: ColorContainer::new(PatternIndex::empty(), PatternIndex::empty()),
             five_pos: ColorContainer::new(MaybePos::NONE, MaybePos::NONE),
             candidate_overline_field: Bitfield::ZERO_FILLED,
@@ -6,8 +6,8 @@ use crate::{pattern, slice};
 use crate::slice_pattern::SlicePattern;
 use crate::step_idx;
 use crate::utils::empty::Empty;
-use std::simd::cmp::SimdPartialEq;
-use std::simd::{u8x16, Simd};
+use core::simd::cmp::SimdPartialEq;
+use core::simd::{u8x16, Simd};
 use crate::notation::pos;
 use crate::notation::rule::RuleKind;
 
@@ -45,7 +45,7 @@ impl<const R: RuleKind> PatternIndex<R> {
         slice_idx: u8,
         bitmap: SlicePattern,
     ) -> SlicePattern {
-        std::mem::replace(
+        core::mem::replace(
             &mut self.slice_bitmap[Self::local_slice_idx::<D>(slice_idx as usize)],
             bitmap,
         )
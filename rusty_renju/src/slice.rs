@@ -22,7 +22,7 @@ pub struct Slice {
 }
 
 impl Slice {
-    const PLACEHOLDER: Self = unsafe { std::mem::zeroed() };
+    const PLACEHOLDER: Self = unsafe { core::mem::zeroed() };
 
     pub const fn empty(idx: u8, length: u8, start_row: u8, start_col: u8) -> Self {
         Slice {
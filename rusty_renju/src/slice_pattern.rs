@@ -3,8 +3,8 @@ use crate::notation::rule::RuleKind;
 use crate::slice::Slice;
 use crate::slice_pattern::ExtendedMatch::{Left, Right};
 use crate::{assert_struct_sizes, const_for, pattern};
-use std::simd::cmp::SimdPartialEq;
-use std::simd::Simd;
+use core::simd::cmp::SimdPartialEq;
+use core::simd::Simd;
 
 #[derive(Eq, PartialEq, Copy, Clone, Debug)]
 #[repr(transparent)]
@@ -148,6 +148,16 @@ pub fn contains_five_in_a_row(stones: u16) -> bool {
     calculate_five_in_a_rows(stones) != 0
 }
 
+pub fn contains_exact_five_in_a_row(stones: u16) -> bool {
+    calculate_five_in_a_rows(stones) & !(stones << 1) & !(stones >> 5) != 0
+}
+
+pub fn overline_idx(stones: u16) -> Option<u32> {
+    let overline = calculate_five_in_a_rows(stones) & (stones >> 5);
+
+    (overline != 0).then_some(overline.trailing_zeros())
+}
+
 #[inline(always)]
 pub fn match_overline_positions(stones: u16, blocks: u16) -> u16 {
     let two = stones & (stones >> 1);
@@ -0,0 +1,196 @@
+use crate::board::Board;
+use crate::notation::color::Color;
+use crate::notation::direction::Direction;
+use crate::notation::pos::Pos;
+use crate::notation::rule::RuleKind;
+use crate::pattern;
+use crate::slice::Slice;
+use crate::slice_pattern::SlicePattern;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "typeshare")]
+use typeshare::typeshare;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[repr(u8)]
+pub enum ThreatKind {
+    Five = 0,
+    OpenFour = 1,
+    ClosedFour = 2,
+    Three = 3,
+    SplitThree = 4,
+}
+
+impl ThreatKind {
+    pub fn is_four(&self) -> bool {
+        matches!(self, Self::OpenFour | Self::ClosedFour)
+    }
+
+    pub fn is_three(&self) -> bool {
+        matches!(self, Self::Three | Self::SplitThree)
+    }
+}
+
+#[cfg_attr(feature = "typeshare", typeshare)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Threat {
+    pub kind: ThreatKind,
+    pub color: Color,
+    pub direction: Direction,
+    pub stones: Vec<Pos>,
+    // empty points that upgrade the line: five for fours, open four for threes
+    pub completion_points: Vec<Pos>,
+    // empty points where an opponent stone neutralizes the line
+    pub defense_points: Vec<Pos>,
+}
+
+impl<const R: RuleKind> Board<R> {
+    pub fn threats(&self) -> Vec<Threat> {
+        [Color::Black, Color::White].into_iter()
+            .flat_map(|color| self.threats_of(color))
+            .collect()
+    }
+
+    pub fn threats_of(&self, color: Color) -> Vec<Threat> {
+        let slices = &self.slices;
+
+        slices.horizontal_slices.iter().map(|slice| (Direction::Horizontal, slice))
+            .chain(slices.vertical_slices.iter().map(|slice| (Direction::Vertical, slice)))
+            .chain(slices.ascending_slices.iter().map(|slice| (Direction::Ascending, slice)))
+            .chain(slices.descending_slices.iter().map(|slice| (Direction::Descending, slice)))
+            .flat_map(|(direction, slice)| slice_threats::<R>(slice, direction, color))
+            .collect()
+    }
+}
+
+fn calculate_slice_pattern<const R: RuleKind>(slice: &Slice, color: Color) -> SlicePattern {
+    match color {
+        Color::Black => slice.calculate_slice_pattern::<R, { Color::Black }>(),
+        Color::White => slice.calculate_slice_pattern::<R, { Color::White }>(),
+    }
+}
+
+fn is_exact_five<const R: RuleKind>(color: Color) -> bool {
+    matches!((R, color), (RuleKind::Renju, Color::Black) | (RuleKind::Gomoku, _))
+}
+
+// maximal run of stones covering the given slice index
+fn run_mask(stones: u16, idx: usize) -> u16 {
+    let right = (!(stones >> idx)).trailing_zeros() as usize;
+    let left = (!(stones << (15 - idx))).leading_zeros() as usize;
+
+    let (begin, end) = (idx + 1 - left, idx + right);
+
+    (((1u32 << end) - 1) & !((1u32 << begin) - 1)) as u16
+}
+
+fn iter_idx(mask: u16) -> impl Iterator<Item = usize> {
+    (0 .. 16).filter(move |idx| mask & (1 << idx) != 0)
+}
+
+// (line stones, completion points) keyed by the stones, for every point carrying the flag
+fn collect_lines(stones: u16, slice_pattern: SlicePattern, flag: u8) -> Vec<(u16, u16)> {
+    let mut lines: Vec<(u16, u16)> = vec![];
+
+    for (idx, pattern) in slice_pattern.patterns.to_le_bytes().into_iter().enumerate() {
+        if pattern & flag == 0 {
+            continue;
+        }
+
+        let line = run_mask(stones | (1 << idx), idx) & !(1 << idx);
+
+        match lines.iter_mut().find(|(stones, _)| *stones == line) {
+            Some((_, completions)) => *completions |= 1 << idx,
+            None => lines.push((line, 1 << idx)),
+        }
+    }
+
+    lines
+}
+
+fn slice_threats<const R: RuleKind>(slice: &Slice, direction: Direction, color: Color) -> Vec<Threat> {
+    let stones = slice.stones[color];
+
+    if stones.count_ones() < 2 {
+        return vec![];
+    }
+
+    let to_pos = |mask: u16| iter_idx(mask)
+        .map(|idx| slice.start_pos.directional_offset_unchecked(direction, idx as isize))
+        .collect::<Vec<_>>();
+
+    let mut threats = vec![];
+
+    let mut visited = 0u16;
+    for idx in iter_idx(stones) {
+        if visited & (1 << idx) != 0 {
+            continue;
+        }
+
+        let run = run_mask(stones, idx);
+        visited |= run;
+
+        let is_five = match run.count_ones() {
+            5 => true,
+            6 .. => !is_exact_five::<R>(color),
+            _ => false,
+        };
+
+        if is_five {
+            threats.push(Threat {
+                kind: ThreatKind::Five,
+                color,
+                direction,
+                stones: to_pos(run),
+                completion_points: vec![],
+                defense_points: vec![],
+            });
+        }
+    }
+
+    let slice_pattern = calculate_slice_pattern::<R>(slice, color);
+
+    if slice_pattern.is_empty() {
+        return threats;
+    }
+
+    let empty_mask = !(slice.stones[Color::Black] | slice.stones[Color::White]) & ((1 << slice.length) - 1);
+
+    for (flag, is_four) in [(pattern::FIVE, true), (pattern::OPEN_FOUR, false)] {
+        for (line, completions) in collect_lines(stones, slice_pattern, flag) {
+            // an open four can't be neutralized, so the completions of a four are reported instead
+            let defenses = if is_four {
+                completions
+            } else {
+                iter_idx(empty_mask)
+                    .filter(|&idx| {
+                        let blocked = slice.set(!color, idx as u8);
+
+                        !collect_lines(stones, calculate_slice_pattern::<R>(&blocked, color), flag).iter()
+                            .any(|&(blocked_line, _)| blocked_line == line)
+                    })
+                    .fold(0, |acc, idx| acc | (1 << idx))
+            };
+
+            let kind = match (is_four, completions.count_ones() > 1, line >> line.trailing_zeros()) {
+                (true, true, _) => ThreatKind::OpenFour,
+                (true, false, _) => ThreatKind::ClosedFour,
+                (false, _, 0b111) => ThreatKind::Three,
+                (false, _, _) => ThreatKind::SplitThree,
+            };
+
+            threats.push(Threat {
+                kind,
+                color,
+                direction,
+                stones: to_pos(line),
+                completion_points: to_pos(completions),
+                defense_points: to_pos(defenses),
+            });
+        }
+    }
+
+    threats
+}
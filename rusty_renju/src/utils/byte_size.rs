@@ -1,6 +1,6 @@
 use crate::impl_debug_from_display;
-use std::fmt::Display;
-use std::ops::{Add, AddAssign, Sub, SubAssign};
+use core::fmt::Display;
+use core::ops::{Add, AddAssign, Sub, SubAssign};
 #[cfg(feature = "typeshare")]
 use typeshare::typeshare;
 
@@ -66,7 +66,7 @@ impl SubAssign for ByteSize {
 }
 
 impl Display for ByteSize {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let bytes = self.0;
         match bytes {
             b if b < 1024 => write!(f, "{b} B"),
@@ -15,8 +15,8 @@ use typeshare::typeshare;
 
 #[macro_export] macro_rules! assert_struct_sizes {
     ($t:ty, size=$size:expr, align=$align:expr) => {
-        const _: () = assert!(std::mem::size_of::<$t>() == $size);
-        const _: () = assert!(std::mem::align_of::<$t>() == $align);
+        const _: () = assert!(core::mem::size_of::<$t>() == $size);
+        const _: () = assert!(core::mem::align_of::<$t>() == $align);
     };
 }
 
@@ -30,9 +30,9 @@ use typeshare::typeshare;
 
 #[macro_export] macro_rules! impl_debug_from_display {
     ($name:ident) => {
-        impl std::fmt::Debug for $name {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                std::fmt::Display::fmt(self, f)
+        impl core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Display::fmt(self, f)
             }
         }
     };
@@ -11,7 +11,7 @@ pub fn serialize_array<S, T, const N: usize>(array: &[T; N], serializer: S) -> R
 
 #[cfg(feature = "serde")]
 pub fn deserialize_array<'de, D, T, const N: usize>(deserializer: D) -> Result<[T; N], D::Error> where
-    T: serde::Deserialize<'de> + std::fmt::Debug,
+    T: serde::Deserialize<'de> + core::fmt::Debug,
     D: serde::Deserializer<'de>
 {
     let array: Vec<T> = serde::Deserialize::deserialize(deserializer)?;
@@ -33,7 +33,7 @@ pub fn serialize_color_container_array<S, T, const N: usize>(array: &ColorContai
 
 #[cfg(feature = "serde")]
 pub fn deserialize_color_container_array<'de, D, T, const N: usize>(deserializer: D) -> Result<ColorContainer<[T; N]>, D::Error> where
-    T: serde::Deserialize<'de> + std::fmt::Debug,
+    T: serde::Deserialize<'de> + core::fmt::Debug,
     D: serde::Deserializer<'de>
 {
     let mut array: Vec<T> = serde::Deserialize::deserialize(deserializer)?;
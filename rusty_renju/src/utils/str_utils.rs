@@ -1,4 +1,7 @@
 use crate::const_for;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::boxed::Box;
 
 pub fn join_str_horizontally(sources: &[&str]) -> String {
     let split = sources.iter()
@@ -20,7 +23,7 @@ pub fn join_str_horizontally(sources: &[&str]) -> String {
             split.iter()
                 .map(|rows| {
                     let mut row = rows[row_idx].to_string();
-                    row.extend(std::iter::repeat_n(' ', max_len - row.len()));
+                    row.extend(core::iter::repeat_n(' ', max_len - row.len()));
                     row
                 })
                 .collect::<Vec<_>>()
@@ -7,5 +7,24 @@ pub fn calculate_win_rate(score: Score) -> f32 {
         return -1.0;
     }
 
-    score.signum() as f32 * ((score.abs() as f32) .ln_1p() / 10000.0_f32.ln_1p())
+    score.signum() as f32 * (ln_1p(score.abs() as f32) / ln_1p(10000.0))
+}
+
+#[cfg(feature = "std")]
+fn ln_1p(x: f32) -> f32 {
+    x.ln_1p()
+}
+
+// core has no logarithm, ln(m * 2^e) = e ln 2 + 2 atanh((m - 1) / (m + 1)) with the series cut at the 11th power
+#[cfg(not(feature = "std"))]
+fn ln_1p(x: f32) -> f32 {
+    let bits = (1.0 + x).to_bits();
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127;
+    let mantissa = f32::from_bits((bits & 0x007f_ffff) | 0x3f80_0000);
+
+    let s = (mantissa - 1.0) / (mantissa + 1.0);
+    let s2 = s * s;
+    let series = 2.0 * s * (1.0 + s2 * (1.0 / 3.0 + s2 * (1.0 / 5.0 + s2 * (1.0 / 7.0 + s2 * (1.0 / 9.0 + s2 / 11.0)))));
+
+    exponent as f32 * core::f32::consts::LN_2 + series
 }
@@ -0,0 +1,204 @@
+#[cfg(test)]
+mod test_board_validation {
+    use indoc::indoc;
+    use rusty_renju::board;
+    use rusty_renju::board::Board;
+    use rusty_renju::board_validation::PositionError;
+    use rusty_renju::history::History;
+    use rusty_renju::notation::color::Color;
+    use rusty_renju::notation::pos::{MaybePos, Pos};
+    use rusty_renju::notation::rule::RuleKind;
+    use rusty_renju::utils::empty::Empty;
+    use std::str::FromStr;
+
+    #[test]
+    fn reconstruct_history() {
+        let history = History::from_str("h8,h9,i10,g7,j11,k12,i9,g9,i8,i7").unwrap();
+        let board = Board::<{ RuleKind::Renju }>::from(&history);
+
+        let reconstructed = board.validate().unwrap().unwrap();
+
+        assert_eq!(reconstructed.len(), history.len());
+        assert_eq!(Board::<{ RuleKind::Renju }>::from(&reconstructed), board);
+
+        assert_eq!(board!().validate(), Ok(Some(History::EMPTY)));
+    }
+
+    #[test]
+    fn crowded_position() {
+        // stripes of two never line up three stones of a color in any direction
+        let (blacks, whites): (Vec<Pos>, Vec<Pos>) = (0 .. 15)
+            .flat_map(|row| (0 .. 15).map(move |col| Pos::from_cartesian(row, col)))
+            .partition(|pos| (pos.col() + 2 * pos.row()) % 4 < 2);
+
+        let actions = blacks.into_iter().zip(whites)
+            .take(105)
+            .flat_map(|(black, white)| [MaybePos::from(black), MaybePos::from(white)])
+            .collect::<Vec<_>>();
+
+        let board = Board::<{ RuleKind::Renju }>::from(&History::try_from(actions).unwrap());
+
+        assert_eq!(board.stones, 210);
+
+        let reconstructed = std::thread::Builder::new()
+            .stack_size(2 << 20)
+            .spawn(move || board.validate())
+            .unwrap()
+            .join()
+            .unwrap()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(Board::<{ RuleKind::Renju }>::from(&reconstructed), board);
+    }
+
+    #[test]
+    fn stone_count_mismatch() {
+        let case = board!(indoc! {"
+           A B C D E F G H I J K L M N O
+        15 . . . . . . . . . . . . . . . 15
+        14 . . . . . . . . . . . . . . . 14
+        13 . . . . . . . . . . . . . . . 13
+        12 . . . . . . . . . . . . . . . 12
+        11 . . . . . . . . . . . . . . . 11
+        10 . . . . . . . . . . . . . . . 10
+         9 . . . . . . . X . . . . . . . 9
+         8 . . . . . . . X X . . . . . . 8
+         7 . . . . . . . . . . . . . . . 7
+         6 . . . . . . . . . . . . . . . 6
+         5 . . . . . . . . . . . . . . . 5
+         4 . . . . . . . . . . . . . . . 4
+         3 . . . . . . . . . . . . . . . 3
+         2 . . . . . . . . . . . . . . . 2
+         1 . . . . . . . . . . . . . . . 1
+           A B C D E F G H I J K L M N O"});
+
+        assert!(matches!(case.validate(), Err(PositionError::StoneCountMismatch { blacks: 3, whites: 0, .. })));
+
+        let case = Board::<{ RuleKind::Renju }>::from(&History::from_str("h8,h9").unwrap()).pass();
+
+        assert_eq!(case.player_color, Color::White);
+        assert!(matches!(case.validate(), Err(PositionError::StoneCountMismatch { .. })));
+    }
+
+    #[test]
+    fn finished_positions() {
+        let case = board!(indoc! {"
+           A B C D E F G H I J K L M N O
+        15 . . . . . . . . . . . . . . . 15
+        14 . . . . . . . . . . . . . . . 14
+        13 . . . . . . . . . . . . . . . 13
+        12 . . . . . . . . . . . . . . . 12
+        11 . . . . . . . . . . . . . . . 11
+        10 . . . . . . . O O O O . . . . 10
+         9 . . . . . . . . . . . . . . . 9
+         8 . . . . . X X X X X . . . . . 8
+         7 . . . . . . . . . . . . . . . 7
+         6 . . . . . . . . . . . . . . . 6
+         5 . . . . . . . . . . . . . . . 5
+         4 . . . . . . . . . . . . . . . 4
+         3 . . . . . . . . . . . . . . . 3
+         2 . . . . . . . . . . . . . . . 2
+         1 . . . . . . . . . . . . . . . 1
+           A B C D E F G H I J K L M N O"});
+
+        let reconstructed = case.validate().unwrap().unwrap();
+
+        assert_eq!(reconstructed.len(), 9);
+        assert_eq!(Board::<{ RuleKind::Renju }>::from(&reconstructed), case);
+
+        let case = board!(indoc! {"
+           A B C D E F G H I J K L M N O
+        15 . . . . . . . . . . . . . . . 15
+        14 . . . . . . . . . . . . . . . 14
+        13 . . . . . . . . . . . . . . . 13
+        12 . . . . . . . . . . . . . . . 12
+        11 . . . . . . . . . . . . . . . 11
+        10 . . . . . . . O O O O O . . . 10
+         9 . . . . . . . . . . . . . . . 9
+         8 . . . . . X X X X X . . . . . 8
+         7 . . . . . . . . . . . . . . . 7
+         6 . . . . . . . . . . . . . . . 6
+         5 . . . . . . . . . . . . . . . 5
+         4 . . . . . . . . . . . . . . . 4
+         3 . . . . . . . . . . . . . . . 3
+         2 . . . . . . . . . . . . . . . 2
+         1 . . . . . . . . . . . . . . . 1
+           A B C D E F G H I J K L M N O"});
+
+        assert_eq!(case.validate(), Err(PositionError::BothSidesHaveFive));
+    }
+
+    #[test]
+    fn forbidden_stones() {
+        let case = board!(indoc! {"
+           A B C D E F G H I J K L M N O
+        15 . . . . . . . . . . . . . . . 15
+        14 . . . . . . . . . . . . . . . 14
+        13 . . . . . . . . . . . . . . . 13
+        12 . . . . . . . . . . . . . . . 12
+        11 . . . . . . . . . . . . . . . 11
+        10 . . . . . . O O O O O . . . . 10
+         9 . . . . . . . . . . . . . . . 9
+         8 . . . . X X X X X X . . . . . 8
+         7 . . . . . . . . . . . . . . . 7
+         6 . . . . . . . . . . . . . . . 6
+         5 . . . . . . . . . . . . . . . 5
+         4 . . . . . . . . . . . . . . . 4
+         3 . . . . . . . . . . . . . . . 3
+         2 . . . . . . . . . . . . . . . 2
+         1 . . . . . . . . . . . . . . . 1
+           A B C D E F G H I J K L M N O"});
+
+        assert_eq!(case.validate(), Err(PositionError::ForbiddenStone(Pos::from_str("e8").unwrap())));
+
+        let case = board!(indoc! {"
+           A B C D E F G H I J K L M N O
+        15 . . . . . . . . . . . . . . . 15
+        14 . . . . . . . . . . . . . . . 14
+        13 . . . . . . . . . . . . . . . 13
+        12 . . . . . . . . . . . . . . . 12
+        11 . . . . . . . . . . . . . . . 11
+        10 . . . . . . . . . . . . . . . 10
+         9 . . . . . . X X X . . . . . . 9
+         8 . . . . . . X X X . . . . . . 8
+         7 . . . . . . X X X . . . . . . 7
+         6 . . . . . . . . . . . . . . . 6
+         5 . . . . . . . . . . . . . . . 5
+         4 . . . . . . . . . . . . . . . 4
+         3 . . . . . . . . . . . . . . . 3
+         2 . . . . . . . . . . . . . . . 2
+         1 O . O . O . O . O . O . O . O 1
+           A B C D E F G H I J K L M N O"});
+
+        assert_eq!(case.validate(), Err(PositionError::Unreachable));
+    }
+
+    #[test]
+    fn rule_dependent_positions() {
+        let source = indoc! {"
+           A B C D E F G H I J K L M N O
+        15 . . . . . . . . . . . . . . . 15
+        14 . . . . . . . . . . . . . . . 14
+        13 . . . . . . . . . . . . . . . 13
+        12 . . . . . . . . . . . . . . . 12
+        11 . . . . . . . . . . . . . . . 11
+        10 . . . . . . O O O O O . . . . 10
+         9 . . . . . . . . . . . . . . . 9
+         8 . . . . X X X X X X . . . . . 8
+         7 . . . . . . . . . . . . . . . 7
+         6 . . . . . . . . . . . . . . . 6
+         5 . . . . . . . . . . . . . . . 5
+         4 . . . . . . . . . . . . . . . 4
+         3 . . . . . . . . . . . . . . . 3
+         2 . . . . . . . . . . . . . . . 2
+         1 . . . . . . . . . . . . . . . 1
+           A B C D E F G H I J K L M N O"};
+
+        let gomoku = Board::<{ RuleKind::Gomoku }>::from_str(source).unwrap();
+        let freestyle = Board::<{ RuleKind::Freestyle }>::from_str(source).unwrap();
+
+        assert_eq!(gomoku.validate(), Err(PositionError::PlayerAlreadyHasFive));
+        assert_eq!(freestyle.validate(), Err(PositionError::BothSidesHaveFive));
+    }
+}
@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod test_history_format {
+    use rusty_renju::history::History;
+    use rusty_renju::history_format::HistoryFormat;
+    use std::str::FromStr;
+
+    macro_rules! test_format {
+        ($format:expr, $source:expr, $expected:expr) => {{
+            assert_eq!(HistoryFormat::detect($source), Some($format));
+
+            let (history, format) = History::from_str_with_format($source).unwrap();
+
+            assert_eq!(format, $format);
+            assert_eq!(history.to_string(), $expected);
+            assert_eq!(History::from_str($source).unwrap(), history);
+        }};
+    }
+
+    #[test]
+    fn detect_and_parse() {
+        test_format!(HistoryFormat::Coordinates, "h8h9i10", "h8,h9,i10");
+        test_format!(HistoryFormat::Coordinates, "H8, h9, pass, i10", "h8,h9,pass,i10");
+        test_format!(HistoryFormat::Gomocup, "7,7 7,8 8,9", "h8,h9,i10");
+        test_format!(HistoryFormat::Gomocup, "7,7\n7,8\n8,9\n", "h8,h9,i10");
+        test_format!(HistoryFormat::Gomocup, "7,7;7,8;8,9", "h8,h9,i10");
+        test_format!(HistoryFormat::Numeric, "070707080809", "h8,h9,i10");
+        test_format!(HistoryFormat::Numeric, "0707 0708 0809", "h8,h9,i10");
+        test_format!(HistoryFormat::PositionCode, "hhhiij", "h8,h9,i10");
+        test_format!(HistoryFormat::PositionCode, "HH--ij", "h8,pass,i10");
+    }
+
+    #[test]
+    fn round_trip() {
+        let history = History::from_str("h8,h9,i10,a1,o15,g7,k12").unwrap();
+
+        for format in HistoryFormat::ALL {
+            let source = history.to_string_with_format(format).unwrap();
+
+            assert_eq!(HistoryFormat::detect(&source), Some(format), "{source}");
+            assert_eq!(format.parse(&source).unwrap(), history, "{source}");
+        }
+
+        let history = History::from_str("h8,pass,i10").unwrap();
+
+        for format in [HistoryFormat::Coordinates, HistoryFormat::PositionCode] {
+            let source = history.to_string_with_format(format).unwrap();
+
+            assert_eq!(History::from_str(&source).unwrap(), history, "{source}");
+        }
+
+        assert!(history.to_string_with_format(HistoryFormat::Gomocup).is_err());
+        assert!(history.to_string_with_format(HistoryFormat::Numeric).is_err());
+    }
+
+    #[test]
+    fn invalid_sources() {
+        assert!(History::from_str("7,7 15,3").is_err());
+        assert!(History::from_str("7,7,7").is_err());
+        assert!(History::from_str("0707 1515").is_err());
+        assert!(History::from_str("hh-h").is_err());
+        assert_eq!(HistoryFormat::detect("070"), None);
+        assert_eq!(HistoryFormat::detect(""), None);
+    }
+}
@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod test_opening {
+    use indoc::indoc;
+    use rusty_renju::board;
+    use rusty_renju::history::History;
+    use rusty_renju::opening::standard_opening::{OpeningGroup, StandardOpening};
+    use rusty_renju::opening::symmetry::Symmetry;
+    use std::collections::HashSet;
+    use std::str::FromStr;
+
+    #[test]
+    fn catalogue() {
+        assert_eq!(StandardOpening::ALL.len(), 26);
+
+        let distinct = StandardOpening::ALL.iter()
+            .map(|opening| opening.moves())
+            .collect::<HashSet<_>>();
+
+        assert_eq!(distinct.len(), 26);
+
+        assert_eq!(StandardOpening::Kagetsu.code(), "D4");
+        assert_eq!(StandardOpening::Hogetsu.code(), "I7");
+        assert_eq!(StandardOpening::Suisei.group(), OpeningGroup::Indirect);
+
+        assert_eq!(StandardOpening::Kansei.history().to_string(), "h8,h9,h10");
+        assert_eq!(StandardOpening::Ugetsu.history().to_string(), "h8,h9,i8");
+        assert_eq!(StandardOpening::Hogetsu.history().to_string(), "h8,i9,i7");
+
+        assert_eq!(StandardOpening::from_str("kagetsu").unwrap(), StandardOpening::Kagetsu);
+        assert_eq!(StandardOpening::from_str("i13").unwrap(), StandardOpening::Suisei);
+    }
+
+    #[test]
+    fn classify_under_symmetry() {
+        for opening in StandardOpening::ALL {
+            for symmetry in Symmetry::ALL {
+                let history = opening.transformed_history(symmetry);
+                let opening_match = history.standard_opening().unwrap();
+
+                assert_eq!(opening_match.opening, opening, "{history}");
+
+                for (actual, canonical) in history.iter().zip(opening.moves()) {
+                    assert_eq!(opening_match.to_canonical(actual.unwrap()), Some(canonical), "{history}");
+                    assert_eq!(opening_match.from_canonical(canonical), Some(actual.unwrap()), "{history}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn classify_translated() {
+        let history = History::from_str("c3d3b2e8").unwrap();
+
+        let opening_match = history.standard_opening().unwrap();
+
+        assert_eq!(opening_match.opening, StandardOpening::Shogetsu);
+        assert_eq!(opening_match.symmetry, Symmetry::Rotate270);
+    }
+
+    #[test]
+    fn reproduce_from_match() {
+        let history = StandardOpening::Kagetsu.transformed_history(Symmetry::Rotate90);
+
+        let opening_match = history.standard_opening().unwrap();
+
+        assert_eq!(opening_match.opening, StandardOpening::Kagetsu);
+        assert_eq!(opening_match.symmetry, Symmetry::Rotate90);
+        assert_eq!(opening_match.opening.transformed_history(opening_match.symmetry), history);
+
+        for opening in StandardOpening::ALL {
+            for symmetry in Symmetry::ALL {
+                let history = opening.transformed_history(symmetry);
+                let opening_match = history.standard_opening().unwrap();
+
+                assert_eq!(opening_match.opening.transformed_history(opening_match.symmetry), history, "{history}");
+            }
+        }
+    }
+
+    #[test]
+    fn non_standard_openings() {
+        assert!(History::from_str("h8h10i9").unwrap().standard_opening().is_none());
+        assert!(History::from_str("h8h9k8").unwrap().standard_opening().is_none());
+        assert!(History::from_str("h8h9").unwrap().standard_opening().is_none());
+    }
+
+    #[test]
+    fn classify_board() {
+        let case = board!(indoc! {"
+           A B C D E F G H I J K L M N O
+        15 . . . . . . . . . . . . . . . 15
+        14 . . . . . . . . . . . . . . . 14
+        13 . . . . . . . . . . . . . . . 13
+        12 . . . . . . . . . . . . . . . 12
+        11 . . . . . . . . . . . . . . . 11
+        10 . . . . . . . . . . . . . . . 10
+         9 . . . . . . . . . . . . . . . 9
+         8 . . . . . . O X . . . . . . . 8
+         7 . . . . . . . . . . . . . . . 7
+         6 . . . . . . . . . . . . . . . 6
+         5 . . . . . . X . . . . . . . . 5
+         4 . . . . . . . . . . . . . . . 4
+         3 . . . . . . . . . . . . . . . 3
+         2 . . . . . . . . . . . . . . . 2
+         1 . . . . . . . . . . . . . . . 1
+           A B C D E F G H I J K L M N O"});
+
+        assert!(case.standard_opening().is_none());
+
+        let case = board!(indoc! {"
+           A B C D E F G H I J K L M N O
+        15 . . . . . . . . . . . . . . . 15
+        14 . . . . . . . . . . . . . . . 14
+        13 . . . . . . . . . . . . . . . 13
+        12 . . . . . . . . . . . . . . . 12
+        11 . . . . . . . . . . . . . . . 11
+        10 . . . . . . . . . . . . . . . 10
+         9 . . . . . . . . . . . . . . . 9
+         8 . . . . . . O X . . . . . . . 8
+         7 . . . . . . . . . . . . . . . 7
+         6 . . . . . . X . . . . . . . . 6
+         5 . . . . . . . . . . . . . . . 5
+         4 . . . . . . . . . . . . . . . 4
+         3 . . . . . . . . . . . . . . . 3
+         2 . . . . . . . . . . . . . . . 2
+         1 . . . . . . . . . . . . . . . 1
+           A B C D E F G H I J K L M N O"});
+
+        assert_eq!(case.standard_opening().unwrap().opening, StandardOpening::Zangetsu);
+    }
+}
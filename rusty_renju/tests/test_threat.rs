@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod test_threat {
+    use indoc::indoc;
+    use rusty_renju::board;
+    use rusty_renju::board::Board;
+    use rusty_renju::notation::color::Color;
+    use rusty_renju::notation::direction::Direction;
+    use rusty_renju::notation::pos::Pos;
+    use rusty_renju::notation::rule::RuleKind;
+    use rusty_renju::threat::{Threat, ThreatKind};
+    use std::str::FromStr;
+
+    fn positions(source: &str) -> Vec<Pos> {
+        let mut positions = source.split(',')
+            .filter(|token| !token.is_empty())
+            .map(|token| Pos::from_str(token).unwrap())
+            .collect::<Vec<_>>();
+
+        positions.sort_by_key(|pos| pos.idx());
+        positions
+    }
+
+    fn normalized(mut threat: Threat) -> Threat {
+        threat.stones.sort_by_key(|pos| pos.idx());
+        threat.completion_points.sort_by_key(|pos| pos.idx());
+        threat.defense_points.sort_by_key(|pos| pos.idx());
+        threat
+    }
+
+    macro_rules! assert_threat {
+        ($threats:expr, $kind:expr, $color:expr, $direction:expr, $stones:expr, $completions:expr, $defenses:expr) => {{
+            let expected = Threat {
+                kind: $kind,
+                color: $color,
+                direction: $direction,
+                stones: positions($stones),
+                completion_points: positions($completions),
+                defense_points: positions($defenses),
+            };
+
+            assert!($threats.contains(&expected), "{:?}", $threats);
+        }};
+    }
+
+    #[test]
+    fn basic_threats() {
+        let case = board!(indoc! {"
+           A B C D E F G H I J K L M N O
+        15 . . . . . . . . . . . . . . . 15
+        14 . . . . . . . . . . . . . . . 14
+        13 . . . . . . . . . . . . . . . 13
+        12 . . . . . . . . . . . . . . . 12
+        11 . . . . . . . . . . . . . . . 11
+        10 . . . . . . . . . . . . . . . 10
+         9 . . . . . . . . . . . . . . . 9
+         8 . . . . . . . X X X . . . . . 8
+         7 . . . . . . . . . . . . O . . 7
+         6 . . O . . . . . . . . . X . . 6
+         5 . . . . . . . . . . . . X . . 5
+         4 . . O . . . . . . . . . X . . 4
+         3 . . O . . . . . . . . . X . . 3
+         2 . . . . . . . . . . . . . . . 2
+         1 . . . . . . . . . . . . . . . 1
+           A B C D E F G H I J K L M N O"});
+
+        let threats = case.threats().into_iter()
+            .map(normalized)
+            .collect::<Vec<_>>();
+
+        assert_eq!(threats.len(), 3, "{threats:?}");
+
+        assert_threat!(threats, ThreatKind::Three, Color::Black, Direction::Horizontal,
+            "h8,i8,j8", "g8,k8", "g8,k8");
+        assert_threat!(threats, ThreatKind::ClosedFour, Color::Black, Direction::Vertical,
+            "m3,m4,m5,m6", "m2", "m2");
+        assert_threat!(threats, ThreatKind::SplitThree, Color::White, Direction::Vertical,
+            "c3,c4,c6", "c5", "c2,c5,c7");
+
+        assert_eq!(case.threats_of(Color::White).len(), 1);
+    }
+
+    #[test]
+    fn fours_and_fives() {
+        let source = indoc! {"
+           A B C D E F G H I J K L M N O
+        15 . . . . . . . . . . . . . . . 15
+        14 . . . . . . . . . . . . . . . 14
+        13 . . . . . . . . . . . . . . . 13
+        12 . . . . . . . . . . . . . . . 12
+        11 . . . . . . . . . . . . . . . 11
+        10 . . . . . . . . . . . . . . . 10
+         9 . . . . . . . . . . . . . . . 9
+         8 . . . X X X X X X . . . . . . 8
+         7 . . . . . . . . . . . . . . . 7
+         6 . . . . . . . . . . . . . . . 6
+         5 . . . . . O O O O . . . . . . 5
+         4 . . . . . . . . . . . . . . . 4
+         3 . . . . . . . . . . . . . . . 3
+         2 . . . . . . . . . . . . . . . 2
+         1 . . . . . . . . . . . . . . . 1
+           A B C D E F G H I J K L M N O"};
+
+        let renju = Board::<{ RuleKind::Renju }>::from_str(source).unwrap();
+        let freestyle = Board::<{ RuleKind::Freestyle }>::from_str(source).unwrap();
+
+        let renju_threats = renju.threats().into_iter().map(normalized).collect::<Vec<_>>();
+        let freestyle_threats = freestyle.threats().into_iter().map(normalized).collect::<Vec<_>>();
+
+        assert!(renju_threats.iter().all(|threat| threat.kind != ThreatKind::Five));
+        assert_threat!(renju_threats, ThreatKind::OpenFour, Color::White, Direction::Horizontal,
+            "f5,g5,h5,i5", "e5,j5", "e5,j5");
+
+        assert_threat!(freestyle_threats, ThreatKind::Five, Color::Black, Direction::Horizontal,
+            "d8,e8,f8,g8,h8,i8", "", "");
+    }
+}
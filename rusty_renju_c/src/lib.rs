@@ -35,6 +35,26 @@ pub extern "C" fn rusty_renju_forbidden_kind_double_four() -> u8 { rusty_renju::
 #[unsafe(no_mangle)]
 pub extern "C" fn rusty_renju_forbidden_kind_overline() -> u8 { rusty_renju::notation::rule::ForbiddenKind::Overline as u8 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn rusty_renju_direction_horizontal() -> u8 { rusty_renju::notation::direction::Direction::Horizontal as u8 }
+#[unsafe(no_mangle)]
+pub extern "C" fn rusty_renju_direction_vertical() -> u8 { rusty_renju::notation::direction::Direction::Vertical as u8 }
+#[unsafe(no_mangle)]
+pub extern "C" fn rusty_renju_direction_ascending() -> u8 { rusty_renju::notation::direction::Direction::Ascending as u8 }
+#[unsafe(no_mangle)]
+pub extern "C" fn rusty_renju_direction_descending() -> u8 { rusty_renju::notation::direction::Direction::Descending as u8 }
+
+#[unsafe(no_mangle)]
+pub extern "C" fn rusty_renju_threat_kind_five() -> u8 { rusty_renju::threat::ThreatKind::Five as u8 }
+#[unsafe(no_mangle)]
+pub extern "C" fn rusty_renju_threat_kind_open_four() -> u8 { rusty_renju::threat::ThreatKind::OpenFour as u8 }
+#[unsafe(no_mangle)]
+pub extern "C" fn rusty_renju_threat_kind_closed_four() -> u8 { rusty_renju::threat::ThreatKind::ClosedFour as u8 }
+#[unsafe(no_mangle)]
+pub extern "C" fn rusty_renju_threat_kind_three() -> u8 { rusty_renju::threat::ThreatKind::Three as u8 }
+#[unsafe(no_mangle)]
+pub extern "C" fn rusty_renju_threat_kind_split_three() -> u8 { rusty_renju::threat::ThreatKind::SplitThree as u8 }
+
 const MAYBE_POS_NONE: u32 = rusty_renju::notation::pos::MaybePos::INVALID_POS.idx() as u32;
 
 #[unsafe(no_mangle)]
@@ -136,6 +156,43 @@ pub struct BoardPattens {
     pub white_pattens: [u32; rusty_renju::pattern::PATTERN_SIZE],
 }
 
+const THREAT_LINE_CAPACITY: usize = rusty_renju::notation::pos::U_BOARD_WIDTH;
+
+#[repr(C)]
+pub struct Threat {
+    pub kind: u8,
+    pub color: u8,
+    pub direction: u8,
+    pub stones_len: u8,
+    pub stones: [u32; THREAT_LINE_CAPACITY],
+    pub completion_points_len: u8,
+    pub completion_points: [u32; THREAT_LINE_CAPACITY],
+    pub defense_points_len: u8,
+    pub defense_points: [u32; THREAT_LINE_CAPACITY],
+}
+
+impl From<rusty_renju::threat::Threat> for Threat {
+    fn from(value: rusty_renju::threat::Threat) -> Self {
+        fn into_raw_positions(positions: &[rusty_renju::notation::pos::Pos]) -> [u32; THREAT_LINE_CAPACITY] {
+            std::array::from_fn(|idx| positions.get(idx)
+                .map_or(MAYBE_POS_NONE, |pos| pos.idx() as u32)
+            )
+        }
+
+        Self {
+            kind: value.kind as u8,
+            color: value.color as u8,
+            direction: value.direction as u8,
+            stones_len: value.stones.len() as u8,
+            stones: into_raw_positions(&value.stones),
+            completion_points_len: value.completion_points.len() as u8,
+            completion_points: into_raw_positions(&value.completion_points),
+            defense_points_len: value.defense_points.len() as u8,
+            defense_points: into_raw_positions(&value.defense_points),
+        }
+    }
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn rusty_renju_empty_hash() -> u64 {
     rusty_renju::hash_key::HashKey::empty().into()
@@ -334,3 +391,24 @@ pub extern "C" fn rusty_renju_board_pattens(
         false
     }
 }
+
+#[unsafe(no_mangle)]
+pub extern "C" fn rusty_renju_board_threats(
+    board: *const rusty_renju::board_io::AnyBoard,
+    out: *mut Threat,
+    capacity: usize,
+) -> usize {
+    if let Some(board) = unsafe { board.as_ref() } {
+        let threats = dispatch_any_board!(board, board => board.threats());
+
+        if !out.is_null() {
+            for (idx, threat) in threats.iter().take(capacity).enumerate() {
+                unsafe { out.add(idx).write(threat.clone().into()) }
+            }
+        }
+
+        threats.len()
+    } else {
+        0
+    }
+}
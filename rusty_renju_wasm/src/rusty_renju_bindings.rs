@@ -15,6 +15,9 @@ extern "C" {
     #[wasm_bindgen(typescript_type = "History")]
     pub type History;
 
+    #[wasm_bindgen(typescript_type = "HistoryFormat")]
+    pub type HistoryFormat;
+
     #[wasm_bindgen(typescript_type = "Color")]
     pub type Color;
     
@@ -32,6 +35,9 @@ extern "C" {
 
     #[wasm_bindgen(typescript_type = "Score")]
     pub type Score;
+
+    #[wasm_bindgen(typescript_type = "Threat[]")]
+    pub type Threats;
 }
 
 #[wasm_bindgen(js_name = defaultBoard)]
@@ -53,6 +59,21 @@ pub fn calculate_win_rate(score: Score) -> f32 {
     rusty_renju::win_rate::calculate_win_rate(score)
 }
 
+#[wasm_bindgen(js_name = parseHistory)]
+pub fn parse_history(source: &str) -> Result<History, JsError> {
+    let history = rusty_renju::history::History::from_str(source).map_err(JsError::new)?;
+
+    Ok(to_js_value(&history))
+}
+
+#[wasm_bindgen(js_name = formatHistory)]
+pub fn format_history(history: History, format: HistoryFormat) -> Result<String, JsError> {
+    let history: rusty_renju::history::History = try_from_js_value(history)?;
+    let format: rusty_renju::history_format::HistoryFormat = try_from_js_value(format)?;
+
+    history.to_string_with_format(format).map_err(JsError::new)
+}
+
 impl_wrapper! {
     pub BoardWorker { inner: rusty_renju::board_io::AnyBoard } <-> Board
 }
@@ -117,6 +138,12 @@ impl BoardWorker {
         to_js_value(&describe)
     }
 
+    pub fn threats(&self) -> Threats {
+        let threats = dispatch_any_board!(self.inner, board => board.threats());
+
+        to_js_value(&threats)
+    }
+
     #[wasm_bindgen(js_name = isLegalMove)]
     pub fn is_legal_move(&self, pos: Pos) -> bool {
         let pos = try_from_js_value(pos).unwrap();